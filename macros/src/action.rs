@@ -0,0 +1,185 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+	parse::{Parse, ParseStream},
+	punctuated::Punctuated,
+	Error, Expr, FnArg, GenericArgument, Ident, ItemFn, PathArguments, Result, ReturnType, Token,
+	Type,
+};
+
+struct ActionArg {
+	name: Ident,
+	value: Expr,
+}
+
+impl Parse for ActionArg {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let name = input.parse()?;
+		input.parse::<Token![=]>()?;
+		let value = input.parse()?;
+		Ok(Self { name, value })
+	}
+}
+
+enum Scope {
+	User,
+	Moderator,
+	Automatic,
+}
+
+pub struct ActionArgs {
+	scope: Scope,
+	action_type: Expr,
+	name: Ident,
+}
+
+impl Parse for ActionArgs {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let span = input.span();
+		let mut scope = None;
+		let mut name = format_ident!("Action");
+
+		for arg in Punctuated::<ActionArg, Token![,]>::parse_terminated(input)? {
+			let arg_scope = match arg.name.to_string().as_str() {
+				"user" => Scope::User,
+				"moderator" => Scope::Moderator,
+				"automatic" => Scope::Automatic,
+				"name" => {
+					name = syn::parse2(arg.value.to_token_stream())?;
+					continue;
+				}
+				_ => return Err(Error::new_spanned(arg.name, "unknown action argument")),
+			};
+
+			if scope.is_some() {
+				return Err(Error::new_spanned(arg.name, "the scope is already defined"));
+			}
+			scope = Some((arg_scope, arg.value));
+		}
+
+		let (scope, action_type) = scope.ok_or_else(|| {
+			Error::new(
+				span,
+				"expected the scope and the action type: user, moderator or automatic",
+			)
+		})?;
+
+		Ok(Self {
+			scope,
+			action_type,
+			name,
+		})
+	}
+}
+
+// the generic arguments of the last segment of the type (`Result<O, E>` ->
+// `[O, E]`)
+fn type_arguments(ty: &Type) -> Vec<&Type> {
+	let segment = match ty {
+		Type::Path(path) => path.path.segments.last(),
+		_ => None,
+	};
+
+	match segment.map(|segment| &segment.arguments) {
+		Some(PathArguments::AngleBracketed(arguments)) => arguments
+			.args
+			.iter()
+			.filter_map(|argument| match argument {
+				GenericArgument::Type(ty) => Some(ty),
+				_ => None,
+			})
+			.collect(),
+		_ => vec![],
+	}
+}
+
+pub fn expand(args: ActionArgs, function: ItemFn) -> Result<TokenStream> {
+	let ActionArgs {
+		scope,
+		action_type,
+		name,
+	} = args;
+	let sig = &function.sig;
+	let function_name = &sig.ident;
+
+	if sig.asyncness.is_none() {
+		return Err(Error::new_spanned(sig.fn_token, "the action must be async"));
+	}
+
+	let request_input = match sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+		[FnArg::Typed(arg)] => &*arg.ty,
+		_ => {
+			return Err(Error::new_spanned(
+				&sig.inputs,
+				"the action receives only the request input",
+			))
+		}
+	};
+	let input = match type_arguments(request_input).as_slice() {
+		[input] => *input,
+		_ => {
+			return Err(Error::new_spanned(
+				request_input,
+				"expected the request input of the scope, like `UserRequestInput<Input>`",
+			))
+		}
+	};
+
+	let (output, error) = match &sig.output {
+		ReturnType::Type(_, ty) => match type_arguments(ty).as_slice() {
+			[output, error] => (*output, *error),
+			_ => return Err(Error::new_spanned(ty, "expected `Result<Output, Error>`")),
+		},
+		ReturnType::Default => {
+			return Err(Error::new_spanned(sig, "expected `Result<Output, Error>`"))
+		}
+	};
+
+	let (action_trait, action_type_ty, scope_input, scope_error) = match scope {
+		Scope::User => (
+			quote!(UserAction),
+			quote!(crate::core::action::action_type::user_action_type::UserActionType),
+			quote!(crate::core::action::data::user_action_data::UserRequestInput),
+			quote!(crate::core::action::data::user_action_data::UserActionError),
+		),
+		Scope::Moderator => (
+			quote!(ModeratorAction),
+			quote!(crate::core::action::action_type::moderator_action_type::ModeratorActionType),
+			quote!(crate::core::action::data::moderator_action_data::ModeratorRequestInput),
+			quote!(crate::core::action::data::moderator_action_data::ModeratorActionError),
+		),
+		Scope::Automatic => (
+			quote!(AutomaticAction),
+			quote!(crate::core::action::action_type::automatic_action_type::AutomaticActionType),
+			quote!(crate::core::action::data::automatic_action_data::AutomaticRequestInput),
+			quote!(crate::core::action::data::automatic_action_data::AutomaticActionError),
+		),
+	};
+
+	Ok(quote! {
+		#function
+
+		#[derive(Debug)]
+		pub struct #name(#request_input);
+
+		#[rocket::async_trait]
+		impl crate::core::action::definition::action::#action_trait<#input, #output, #error>
+			for #name
+		{
+			fn action_type() -> #action_type_ty {
+				#action_type
+			}
+
+			async fn new(input: #scope_input<#input>) -> Result<Self, #error> {
+				Result::<#request_input, #scope_error>::from(input)
+					.map(Self)
+					.map_err(<#error>::from)
+			}
+
+			async fn run_inner(self) -> Result<#output, #error> {
+				let Self(input) = self;
+				#function_name(input).await
+			}
+		}
+	})
+}
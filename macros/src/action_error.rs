@@ -0,0 +1,148 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Ident, Lit, Meta, NestedMeta, Result};
+
+#[derive(Default)]
+struct VariantArgs {
+	code: Option<Lit>,
+	msg: Option<Lit>,
+	category: Option<Ident>,
+	skip_from: bool,
+}
+
+impl VariantArgs {
+	fn parse(attrs: &[Attribute]) -> Result<Self> {
+		let mut args = Self::default();
+
+		for attr in attrs
+			.iter()
+			.filter(|attr| attr.path.is_ident("action_error"))
+		{
+			let list = match attr.parse_meta()? {
+				Meta::List(list) => list,
+				meta => return Err(Error::new_spanned(meta, "expected #[action_error(...)]")),
+			};
+
+			for nested in list.nested {
+				match nested {
+					NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip_from") => {
+						args.skip_from = true;
+					}
+					NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("code") => {
+						args.code = Some(value.lit);
+					}
+					NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("msg") => {
+						args.msg = Some(value.lit);
+					}
+					NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("category") => {
+						args.category = match value.lit {
+							Lit::Str(lit) => Some(lit.parse()?),
+							lit => return Err(Error::new_spanned(lit, "expected a category name")),
+						};
+					}
+					nested => {
+						return Err(Error::new_spanned(nested, "unknown action_error argument"))
+					}
+				}
+			}
+		}
+
+		Ok(args)
+	}
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+	let name = &input.ident;
+	let data = match &input.data {
+		Data::Enum(data) => data,
+		_ => {
+			return Err(Error::new_spanned(
+				name,
+				"ActionError can only be derived for enums",
+			))
+		}
+	};
+
+	let mut private_errors = vec![];
+	let mut public_errors = vec![];
+	let mut categories = vec![];
+	let mut from_impls = vec![];
+
+	for variant in &data.variants {
+		let args = VariantArgs::parse(&variant.attrs)?;
+		let ident = &variant.ident;
+
+		match &variant.fields {
+			Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+				let ty = &fields.unnamed[0].ty;
+
+				private_errors.push(quote!(#name::#ident(error) => error.private_error(),));
+				public_errors.push(quote!(#name::#ident(error) => error.public_error(),));
+				categories.push(quote!(#name::#ident(error) => error.category(),));
+
+				if !args.skip_from {
+					from_impls.push(quote! {
+						impl From<#ty> for #name {
+							fn from(error: #ty) -> Self {
+								Self::#ident(error)
+							}
+						}
+					});
+				}
+			}
+			Fields::Unit => {
+				let category = args.category.ok_or_else(|| {
+					Error::new_spanned(ident, "the unit variants need an error category")
+				})?;
+				let public_error = match (args.code, args.msg) {
+					(Some(code), Some(msg)) => quote!(Self::error_msg(#code, #msg.into())),
+					(None, None) => quote!(None),
+					_ => {
+						return Err(Error::new_spanned(
+							ident,
+							"the public error needs both the code and the msg",
+						))
+					}
+				};
+
+				private_errors.push(quote!(#name::#ident => None,));
+				public_errors.push(quote!(#name::#ident => #public_error,));
+				categories.push(quote! {
+					#name::#ident => crate::core::action::data::action_data::ErrorCategory::#category,
+				});
+			}
+			_ => {
+				return Err(Error::new_spanned(
+					ident,
+					"the variants must be units or wrap a single error",
+				))
+			}
+		}
+	}
+
+	Ok(quote! {
+		impl crate::core::action::definition::action::ActionError for #name {
+			fn private_error(
+				&self,
+			) -> Option<crate::core::action::data::action_data::DescriptiveError> {
+				match self {
+					#(#private_errors)*
+				}
+			}
+
+			fn public_error(&self) -> Option<crate::core::action::data::action_data::ErrorData> {
+				match self {
+					#(#public_errors)*
+				}
+			}
+
+			fn category(&self) -> crate::core::action::data::action_data::ErrorCategory {
+				match self {
+					#(#categories)*
+				}
+			}
+		}
+
+		#(#from_impls)*
+	})
+}
@@ -0,0 +1,52 @@
+mod action;
+mod action_error;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemFn};
+
+/// Implements `ActionError` for an error enum. The variants that wrap another
+/// error forward to it (and get a `From` impl, unless `skip_from`), the unit
+/// variants declare their error:
+///
+/// ```ignore
+/// #[derive(Debug, PartialEq, ActionError)]
+/// pub enum Error {
+///     UserError(UserActionError),
+///     #[action_error(
+///         code = "user.invalid_credentials",
+///         msg = "Invalid username or password.",
+///         category = "Unauthenticated"
+///     )]
+///     InvalidCredentials,
+///     #[action_error(skip_from)]
+///     ExternalError(ExternalException),
+/// }
+/// ```
+#[proc_macro_derive(ActionError, attributes(action_error))]
+pub fn derive_action_error(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	action_error::derive(input)
+		.unwrap_or_else(|error| error.to_compile_error())
+		.into()
+}
+
+/// Declares the action of the scope from the function, which receives the
+/// request input (the context type defines the session that is required) and
+/// returns the result of the action:
+///
+/// ```ignore
+/// #[action(user = USER_ACTION_TYPE)]
+/// async fn logout(input: UserAuthRequestInput<Input>) -> Result<(), Error> {
+///     ...
+/// }
+/// ```
+///
+/// The action is named `Action`, unless another `name` is given.
+#[proc_macro_attribute]
+pub fn action(args: TokenStream, input: TokenStream) -> TokenStream {
+	let args = parse_macro_input!(args as action::ActionArgs);
+	let input = parse_macro_input!(input as ItemFn);
+	action::expand(args, input)
+		.unwrap_or_else(|error| error.to_compile_error())
+		.into()
+}
@@ -0,0 +1,8 @@
+CREATE TABLE user_session (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL REFERENCES user (id) ON DELETE CASCADE,
+    token_hash VARCHAR NOT NULL UNIQUE,
+    confirmed BOOLEAN NOT NULL DEFAULT 0,
+    created_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL
+);
@@ -0,0 +1,12 @@
+CREATE TABLE user_session_old (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL REFERENCES user (id) ON DELETE CASCADE,
+    token_hash VARCHAR NOT NULL UNIQUE,
+    confirmed BOOLEAN NOT NULL DEFAULT 0,
+    created_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL
+);
+INSERT INTO user_session_old (id, user_id, token_hash, confirmed, created_at, expires_at)
+    SELECT id, user_id, token_hash, confirmed, created_at, expires_at FROM user_session;
+DROP TABLE user_session;
+ALTER TABLE user_session_old RENAME TO user_session;
@@ -0,0 +1 @@
+ALTER TABLE user_session ADD COLUMN revoked_at BIGINT;
@@ -0,0 +1,12 @@
+DROP TABLE user_confirmation;
+
+CREATE TABLE user_old (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    email VARCHAR NOT NULL,
+    `name` VARCHAR NOT NULL,
+    encrypted_pass VARCHAR NOT NULL DEFAULT 0
+);
+INSERT INTO user_old (id, email, `name`, encrypted_pass)
+    SELECT id, email, `name`, encrypted_pass FROM user;
+DROP TABLE user;
+ALTER TABLE user_old RENAME TO user;
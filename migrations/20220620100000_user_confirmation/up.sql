@@ -0,0 +1,11 @@
+-- users created before the confirmation flow are considered confirmed
+ALTER TABLE user ADD COLUMN confirmed BOOLEAN NOT NULL DEFAULT 1;
+
+CREATE TABLE user_confirmation (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL REFERENCES user (id) ON DELETE CASCADE,
+    token_hash VARCHAR NOT NULL UNIQUE,
+    created_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL,
+    used_at BIGINT
+);
@@ -0,0 +1,16 @@
+CREATE TABLE action_audit (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    scope VARCHAR NOT NULL,
+    action_id INTEGER NOT NULL,
+    actor_type VARCHAR NOT NULL,
+    actor_id BIGINT,
+    ip VARCHAR,
+    created_at BIGINT NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    success BOOLEAN NOT NULL,
+    error_code VARCHAR,
+    input VARCHAR NOT NULL
+);
+
+CREATE INDEX action_audit_actor ON action_audit (actor_type, actor_id, created_at);
+CREATE INDEX action_audit_action ON action_audit (scope, action_id, created_at);
@@ -0,0 +1,6 @@
+-- the allowed actions are the comma separated ids of the moderator action types
+CREATE TABLE moderator (
+    user_id INTEGER NOT NULL PRIMARY KEY REFERENCES user (id) ON DELETE CASCADE,
+    admin BOOLEAN NOT NULL DEFAULT 0,
+    allowed_actions VARCHAR NOT NULL DEFAULT ''
+);
@@ -0,0 +1,6 @@
+-- the token buckets of the rate limits, by action and key
+CREATE TABLE rate_limit (
+    key VARCHAR NOT NULL PRIMARY KEY,
+    tokens DOUBLE NOT NULL,
+    updated_at BIGINT NOT NULL
+);
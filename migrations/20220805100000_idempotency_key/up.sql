@@ -0,0 +1,11 @@
+-- the response is null while the first request is still running
+CREATE TABLE idempotency_key (
+    actor VARCHAR NOT NULL,
+    action VARCHAR NOT NULL,
+    key VARCHAR NOT NULL,
+    fingerprint VARCHAR NOT NULL,
+    response VARCHAR,
+    created_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL,
+    PRIMARY KEY (actor, action, key)
+);
@@ -0,0 +1,12 @@
+-- the events waiting for their queued subscribers, one row per subscriber
+CREATE TABLE event_outbox (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    action_id INTEGER NOT NULL,
+    event VARCHAR NOT NULL,
+    created_at BIGINT NOT NULL,
+    delivered_at BIGINT,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error VARCHAR
+);
+
+CREATE INDEX event_outbox_pending ON event_outbox (delivered_at, attempts, id);
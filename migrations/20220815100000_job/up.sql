@@ -0,0 +1,18 @@
+-- the queued internal requests of the automatic actions; a running job is
+-- leased by a worker until `locked_until`, so a crashed worker releases it
+CREATE TABLE job (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    action_id INTEGER NOT NULL,
+    input VARCHAR NOT NULL,
+    state VARCHAR NOT NULL,
+    run_at BIGINT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL,
+    last_error VARCHAR,
+    locked_by VARCHAR,
+    locked_until BIGINT,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+
+CREATE INDEX job_ready ON job (state, run_at, id);
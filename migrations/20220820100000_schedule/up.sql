@@ -0,0 +1,14 @@
+-- the state of the configured schedules; a running schedule is leased until
+-- `locked_until`, so its runs never overlap
+CREATE TABLE schedule (
+    name VARCHAR NOT NULL PRIMARY KEY,
+    action_id INTEGER NOT NULL,
+    cron VARCHAR,
+    interval_seconds BIGINT,
+    paused BOOLEAN NOT NULL DEFAULT 0,
+    triggered BOOLEAN NOT NULL DEFAULT 0,
+    locked_until BIGINT,
+    last_run_at BIGINT,
+    last_error VARCHAR,
+    next_run_at BIGINT NOT NULL
+);
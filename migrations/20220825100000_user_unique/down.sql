@@ -0,0 +1,2 @@
+DROP INDEX user_email;
+DROP INDEX user_name;
@@ -0,0 +1,3 @@
+-- the login is either the name or the email, so each must identify one user
+CREATE UNIQUE INDEX user_name ON user (`name`);
+CREATE UNIQUE INDEX user_email ON user (email);
@@ -0,0 +1,16 @@
+CREATE TABLE schedule_old (
+    name VARCHAR NOT NULL PRIMARY KEY,
+    action_id INTEGER NOT NULL,
+    cron VARCHAR,
+    interval_seconds BIGINT,
+    paused BOOLEAN NOT NULL DEFAULT 0,
+    triggered BOOLEAN NOT NULL DEFAULT 0,
+    locked_until BIGINT,
+    last_run_at BIGINT,
+    last_error VARCHAR,
+    next_run_at BIGINT NOT NULL
+);
+INSERT INTO schedule_old (name, action_id, cron, interval_seconds, paused, triggered, locked_until, last_run_at, last_error, next_run_at)
+    SELECT name, action_id, cron, interval_seconds, paused, triggered, locked_until, last_run_at, last_error, next_run_at FROM schedule;
+DROP TABLE schedule;
+ALTER TABLE schedule_old RENAME TO schedule;
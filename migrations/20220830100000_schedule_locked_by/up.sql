@@ -0,0 +1,3 @@
+-- the run that leased the schedule, so that a run whose lease expired can't
+-- finish the next one
+ALTER TABLE schedule ADD COLUMN locked_by VARCHAR;
@@ -0,0 +1 @@
+pub mod select_audit_action;
@@ -0,0 +1,381 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{TextRule, ValidationResult, Validator};
+use crate::core::action::definition::action::{ActionInput, ActionOutput};
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
+use crate::{
+	core::{
+		action::{
+			action_type::moderator_action_type::ModeratorActionType,
+			data::moderator_action_data::{ModeratorActionError, ModeratorRequestInput},
+			definition::action::ModeratorAction,
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::action_audit_dao,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const MODERATOR_ACTION_TYPE: ModeratorActionType = ModeratorActionType::SelectAudit;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(MODERATOR_ACTION_TYPE, ActionAuth::Moderator)
+			.input::<Input>()
+			.output::<Output>()
+			.route("GET", "/moderator/audit"),
+	);
+}
+
+pub const DEFAULT_LIMIT: u32 = 100;
+
+pub const MAX_LIMIT: u32 = 1000;
+
+const SCOPES: [&str; 3] = ["User", "Moderator", "Automatic"];
+
+const ACTOR_TYPES: [&str; 5] = ["anonymous", "user", "moderator", "internal", "hook"];
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The time range is in unix seconds, from the `from` (inclusive) to the `to`
+/// (exclusive).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, FromForm)]
+pub struct Input {
+	pub actor_type: Option<String>,
+	pub actor_id: Option<i64>,
+	pub scope: Option<String>,
+	pub action_id: Option<u32>,
+	pub from: Option<i64>,
+	pub to: Option<i64>,
+	pub limit: Option<u32>,
+}
+
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		let scope_rule = TextRule::Format {
+			code: "scope",
+			check: |value| SCOPES.contains(&value),
+		};
+		let actor_type_rule = TextRule::Format {
+			code: "actor_type",
+			check: |value| ACTOR_TYPES.contains(&value),
+		};
+
+		Validator::new()
+			.text(
+				"actor_type",
+				self.actor_type.as_deref().unwrap_or("user"),
+				&[actor_type_rule],
+			)
+			.text(
+				"scope",
+				self.scope.as_deref().unwrap_or("User"),
+				&[scope_rule],
+			)
+			.range("limit", self.limit.unwrap_or(DEFAULT_LIMIT), 1, MAX_LIMIT)
+			.check(
+				"to",
+				"range",
+				match (self.from, self.to) {
+					(Some(from), Some(to)) => from < to,
+					_ => true,
+				},
+			)
+			.result()
+	}
+}
+
+impl From<Input> for action_audit_dao::SelectInput {
+	fn from(input: Input) -> Self {
+		let Input {
+			actor_type,
+			actor_id,
+			scope,
+			action_id,
+			from,
+			to,
+			limit,
+		} = input;
+
+		Self {
+			actor_type,
+			actor_id,
+			scope,
+			action_id: action_id.map(i64::from),
+			from,
+			to,
+			limit: limit.unwrap_or(DEFAULT_LIMIT).into(),
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemOutput {
+	pub id: i64,
+	pub scope: String,
+	pub action_id: i64,
+	pub actor_type: String,
+	pub actor_id: Option<i64>,
+	pub ip: Option<String>,
+	pub created_at: i64,
+	pub duration_ms: i64,
+	pub success: bool,
+	pub error_code: Option<String>,
+	pub input: String,
+}
+
+impl From<action_audit_dao::ActionAudit> for ItemOutput {
+	fn from(data: action_audit_dao::ActionAudit) -> Self {
+		let action_audit_dao::ActionAudit {
+			id,
+			scope,
+			action_id,
+			actor_type,
+			actor_id,
+			ip,
+			created_at,
+			duration_ms,
+			success,
+			error_code,
+			input,
+		} = data;
+
+		Self {
+			id,
+			scope,
+			action_id,
+			actor_type,
+			actor_id,
+			ip,
+			created_at,
+			duration_ms,
+			success,
+			error_code,
+			input,
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+	pub items: Vec<ItemOutput>,
+}
+
+impl ActionOutput for Output {}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	ModeratorError(ModeratorActionError),
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(ModeratorRequestInput<Input>);
+
+#[rocket::async_trait]
+impl ModeratorAction<Input, Output, Error> for Action {
+	fn action_type() -> ModeratorActionType {
+		MODERATOR_ACTION_TYPE
+	}
+
+	async fn new(input: ModeratorRequestInput<Input>) -> Result<Self, Error> {
+		Ok(Self(input))
+	}
+
+	async fn run_inner(self) -> Result<Output, Error> {
+		let Self(input) = self;
+		let action_audit_dao::SelectOutput(items) =
+			action_audit_dao::Select::run(input.data.into()).await?;
+		let items = items.into_iter().map(ItemOutput::from).collect();
+		Ok(Output { items })
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::collections::BTreeMap;
+
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::moderator_action_data::tests::{
+		ModeratorRequestContextBuilder, ModeratorSessionBuilder,
+	};
+	use crate::core::action::data::moderator_action_data::{
+		ModeratorActionError, ModeratorOutputInfo, ModeratorRequestContext,
+	};
+	use crate::core::action::data::validation_data::ValidationError;
+	use crate::core::action::definition::action::Action;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::action_audit_dao;
+	use crate::tests::test_utils::tests::run_test;
+
+	fn moderator_context() -> ModeratorRequestContext {
+		ModeratorRequestContextBuilder::new()
+			.session(
+				ModeratorSessionBuilder::new()
+					.allowed_actions(vec![super::MODERATOR_ACTION_TYPE])
+					.build(),
+			)
+			.build()
+	}
+
+	#[tokio::test]
+	async fn test_not_allowed() {
+		run_test(|_| async {
+			let context = ModeratorRequestContextBuilder::new().build();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input::default(),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::ModeratorError(ModeratorActionError::NotAllowed(
+						super::MODERATOR_ACTION_TYPE
+					)),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_invalid_input() {
+		run_test(|_| async {
+			let context = moderator_context();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					actor_type: Some("robot".into()),
+					scope: Some("user".into()),
+					from: Some(1_650_000_100),
+					to: Some(1_650_000_000),
+					limit: Some(super::MAX_LIMIT + 1),
+					..Default::default()
+				},
+				context,
+			}))
+			.await;
+
+			let fields = BTreeMap::from([
+				("actor_type".into(), "actor_type".into()),
+				("scope".into(), "scope".into()),
+				("limit".into(), "range".into()),
+				("to".into(), "range".into()),
+			]);
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::ModeratorError(ModeratorActionError::InvalidInput(
+						ValidationError { fields }
+					)),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let context = moderator_context();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let item = action_audit_dao::ActionAudit {
+				id: 31,
+				scope: "User".into(),
+				action_id: 2,
+				actor_type: "user".into(),
+				actor_id: Some(4),
+				ip: Some("10.0.0.4".into()),
+				created_at: 1_650_000_050,
+				duration_ms: 3,
+				success: false,
+				error_code: Some("user.invalid_credentials".into()),
+				input: r#"{"login":"user-04","pass":"***"}"#.into(),
+			};
+
+			let _m = action_audit_dao::Select::mock(
+				action_audit_dao::SelectInput {
+					actor_type: Some("user".into()),
+					actor_id: Some(4),
+					scope: Some("User".into()),
+					action_id: Some(2),
+					from: Some(1_650_000_000),
+					to: Some(1_650_000_100),
+					limit: super::DEFAULT_LIMIT.into(),
+				},
+				action_audit_dao::SelectOutput(vec![item.clone()]),
+			);
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					actor_type: Some("user".into()),
+					actor_id: Some(4),
+					scope: Some("User".into()),
+					action_id: Some(2),
+					from: Some(1_650_000_000),
+					to: Some(1_650_000_100),
+					limit: None,
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(ModeratorOutputInfo {
+					action_context,
+					data: super::Output {
+						items: vec![item.into()],
+					},
+				}),
+			);
+		})
+		.await;
+	}
+}
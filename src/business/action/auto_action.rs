@@ -1,14 +1,22 @@
+use action_macros::ActionError;
+
 use crate::core::action::{
 	action_type::automatic_action_type::AutomaticActionType,
 	data::{
-		action_data::{DescriptiveError, ErrorData, RequestContext, RequestInput},
+		action_data::{RequestContext, RequestInput},
 		automatic_action_data::{AutomaticActionError, HookRequestInput, InternalRequestInput},
 	},
 };
 use crate::core::action::{
 	data::automatic_action_data::{AutomaticRequestInput, HookInputResult, InternalInputResult},
-	definition::action::{ActionError, ActionInput, ActionOutput, AutomaticAction},
+	data::validation_data::{TextRule, ValidationResult, Validator},
+	definition::action::{ActionInput, ActionOutput, AutomaticAction},
+};
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::{job_impl, registry_impl},
 };
+use crate::core::web::main_impl::hook_web_impl;
 
 ////////////////////////////////////////////////
 ///////////////////// TYPE /////////////////////
@@ -16,23 +24,42 @@ use crate::core::action::{
 
 const AUTOMATIC_ACTION_TYPE: AutomaticActionType = AutomaticActionType::Auto;
 
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(AUTOMATIC_ACTION_TYPE, ActionAuth::Automatic)
+			.input::<Input>()
+			.output::<Output>()
+			.route("POST", "/hook/<source>/<action>"),
+	);
+	job_impl::register::<Internal, Input, Output, Error>(job_impl::DEFAULT_MAX_ATTEMPTS);
+	hook_web_impl::register::<Hook, Input, Output, Error>();
+}
+
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Input {
 	pub param1: String,
 	pub param2: u64,
 }
 
-impl ActionInput for Input {}
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		Validator::new()
+			.text("param1", &self.param1, &[TextRule::Required])
+			.result()
+	}
+}
 
 ////////////////////////////////////////////////
 //////////////////// OUTPUT ////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
 	pub id: u64,
 	pub auto: String,
@@ -46,31 +73,11 @@ impl ActionOutput for Output {}
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum Error {
 	AutomaticError(AutomaticActionError),
 }
 
-impl ActionError for Error {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match self {
-			Error::AutomaticError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match self {
-			Error::AutomaticError(error) => error.public_error(),
-		}
-	}
-}
-
-impl From<AutomaticActionError> for Error {
-	fn from(error: AutomaticActionError) -> Self {
-		Self::AutomaticError(error)
-	}
-}
-
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
@@ -143,10 +150,13 @@ fn run<C: RequestContext>(
 
 #[cfg(test)]
 mod tests {
+	use std::collections::BTreeMap;
+
 	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
 	use crate::core::action::data::automatic_action_data::tests::AutomaticRequestContextBuilder;
 	use crate::core::action::data::automatic_action_data::AutomaticActionError;
 	use crate::core::action::data::automatic_action_data::AutomaticOutputInfo;
+	use crate::core::action::data::validation_data::ValidationError;
 	use crate::core::action::definition::action::Action;
 	use crate::tests::test_utils::tests::run_test;
 
@@ -179,6 +189,39 @@ mod tests {
 		.await;
 	}
 
+	#[tokio::test]
+	async fn test_internal_error_invalid_input() {
+		run_test(|_| async {
+			let context = AutomaticRequestContextBuilder::build_internal();
+			let action_context = ActionContext {
+				action_type: super::AUTOMATIC_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Internal::run(Ok(RequestInput {
+				data: super::Input {
+					param1: "".into(),
+					param2: 5,
+				},
+				context,
+			}))
+			.await;
+
+			let fields = BTreeMap::from([("param1".into(), "required".into())]);
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::AutomaticError(AutomaticActionError::InvalidInput(
+						ValidationError { fields }
+					)),
+				}),
+			);
+		})
+		.await;
+	}
+
 	#[tokio::test]
 	async fn test_internal_ok() {
 		run_test(|_| async {
@@ -1,10 +1,12 @@
-use crate::core::action::definition::action::{ActionError, ModeratorAction};
+use action_macros::{action, ActionError};
+
 use crate::core::action::{
 	action_type::moderator_action_type::ModeratorActionType,
-	data::{
-		action_data::{DescriptiveError, ErrorData},
-		moderator_action_data::{ModeratorActionError, ModeratorRequestInput},
-	},
+	data::moderator_action_data::{ModeratorActionError, ModeratorRequestInput},
+};
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
 };
 
 ////////////////////////////////////////////////
@@ -13,56 +15,31 @@ use crate::core::action::{
 
 const MODERATOR_ACTION_TYPE: ModeratorActionType = ModeratorActionType::EchoError;
 
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(ActionInfo::new(
+		MODERATOR_ACTION_TYPE,
+		ActionAuth::Moderator,
+	));
+}
+
 ////////////////////////////////////////////////
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum Error {
 	ModeratorError(ModeratorActionError),
 }
 
-impl ActionError for Error {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match self {
-			Error::ModeratorError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match self {
-			Error::ModeratorError(error) => error.public_error(),
-		}
-	}
-}
-
-impl From<ModeratorActionError> for Error {
-	fn from(error: ModeratorActionError) -> Self {
-		Self::ModeratorError(error)
-	}
-}
-
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug)]
-pub struct Action(ModeratorRequestInput<()>);
-
-#[rocket::async_trait]
-impl ModeratorAction<(), (), Error> for Action {
-	fn action_type() -> ModeratorActionType {
-		MODERATOR_ACTION_TYPE
-	}
-
-	async fn new(input: ModeratorRequestInput<()>) -> Result<Self, Error> {
-		Ok(Self(input))
-	}
-
-	async fn run_inner(self) -> Result<(), Error> {
-		error!("echo error action");
-		Ok(())
-	}
+#[action(moderator = MODERATOR_ACTION_TYPE)]
+async fn echo(_: ModeratorRequestInput<()>) -> Result<(), Error> {
+	error!("echo error action");
+	Ok(())
 }
 
 ////////////////////////////////////////////////
@@ -0,0 +1 @@
+pub mod select_jobs_action;
@@ -0,0 +1,309 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{TextRule, ValidationResult, Validator};
+use crate::core::action::definition::action::{ActionInput, ActionOutput};
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
+use crate::{
+	core::{
+		action::{
+			action_type::moderator_action_type::ModeratorActionType,
+			data::moderator_action_data::{ModeratorActionError, ModeratorRequestInput},
+			definition::action::ModeratorAction,
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::job_dao,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const MODERATOR_ACTION_TYPE: ModeratorActionType = ModeratorActionType::SelectJobs;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(MODERATOR_ACTION_TYPE, ActionAuth::Moderator)
+			.input::<Input>()
+			.output::<Output>()
+			.route("GET", "/moderator/jobs"),
+	);
+}
+
+pub const DEFAULT_LIMIT: u32 = 100;
+
+pub const MAX_LIMIT: u32 = 1000;
+
+const STATES: [&str; 4] = [
+	job_dao::STATE_PENDING,
+	job_dao::STATE_RUNNING,
+	job_dao::STATE_DONE,
+	job_dao::STATE_DEAD,
+];
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The jobs of the automatic action (by its id), from the last updated; the
+/// dead jobs are the ones that failed all their attempts.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, FromForm)]
+pub struct Input {
+	pub state: Option<String>,
+	pub action_id: Option<u32>,
+	pub limit: Option<u32>,
+}
+
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		let state_rule = TextRule::Format {
+			code: "state",
+			check: |value| STATES.contains(&value),
+		};
+
+		Validator::new()
+			.text(
+				"state",
+				self.state.as_deref().unwrap_or(job_dao::STATE_DEAD),
+				&[state_rule],
+			)
+			.range("limit", self.limit.unwrap_or(DEFAULT_LIMIT), 1, MAX_LIMIT)
+			.result()
+	}
+}
+
+impl From<Input> for job_dao::SelectInput {
+	fn from(input: Input) -> Self {
+		let Input {
+			state,
+			action_id,
+			limit,
+		} = input;
+
+		Self {
+			state,
+			action_id: action_id.map(i64::from),
+			limit: limit.unwrap_or(DEFAULT_LIMIT).into(),
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemOutput {
+	pub id: i64,
+	pub action_id: i64,
+	pub input: String,
+	pub state: String,
+	pub run_at: i64,
+	pub attempts: i64,
+	pub max_attempts: i64,
+	pub last_error: Option<String>,
+	pub created_at: i64,
+	pub updated_at: i64,
+}
+
+impl From<job_dao::Job> for ItemOutput {
+	fn from(data: job_dao::Job) -> Self {
+		let job_dao::Job {
+			id,
+			action_id,
+			input,
+			state,
+			run_at,
+			attempts,
+			max_attempts,
+			last_error,
+			created_at,
+			updated_at,
+		} = data;
+
+		Self {
+			id,
+			action_id,
+			input,
+			state,
+			run_at,
+			attempts,
+			max_attempts,
+			last_error,
+			created_at,
+			updated_at,
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+	pub items: Vec<ItemOutput>,
+}
+
+impl ActionOutput for Output {}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	ModeratorError(ModeratorActionError),
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(ModeratorRequestInput<Input>);
+
+#[rocket::async_trait]
+impl ModeratorAction<Input, Output, Error> for Action {
+	fn action_type() -> ModeratorActionType {
+		MODERATOR_ACTION_TYPE
+	}
+
+	async fn new(input: ModeratorRequestInput<Input>) -> Result<Self, Error> {
+		Ok(Self(input))
+	}
+
+	async fn run_inner(self) -> Result<Output, Error> {
+		let Self(input) = self;
+		let job_dao::SelectOutput(items) = job_dao::Select::run(input.data.into()).await?;
+		let items = items.into_iter().map(ItemOutput::from).collect();
+		Ok(Output { items })
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::collections::BTreeMap;
+
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::moderator_action_data::tests::{
+		ModeratorRequestContextBuilder, ModeratorSessionBuilder,
+	};
+	use crate::core::action::data::moderator_action_data::{
+		ModeratorActionError, ModeratorOutputInfo, ModeratorRequestContext,
+	};
+	use crate::core::action::data::validation_data::ValidationError;
+	use crate::core::action::definition::action::Action;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::job_dao;
+	use crate::tests::test_utils::tests::run_test;
+
+	fn moderator_context() -> ModeratorRequestContext {
+		ModeratorRequestContextBuilder::new()
+			.session(
+				ModeratorSessionBuilder::new()
+					.allowed_actions(vec![super::MODERATOR_ACTION_TYPE])
+					.build(),
+			)
+			.build()
+	}
+
+	#[tokio::test]
+	async fn test_invalid_input() {
+		run_test(|_| async {
+			let context = moderator_context();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					state: Some("failed".into()),
+					action_id: None,
+					limit: Some(0),
+				},
+				context,
+			}))
+			.await;
+
+			let fields = BTreeMap::from([
+				("state".into(), "state".into()),
+				("limit".into(), "range".into()),
+			]);
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::ModeratorError(ModeratorActionError::InvalidInput(
+						ValidationError { fields }
+					)),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let context = moderator_context();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let item = job_dao::Job {
+				id: 41,
+				action_id: 1,
+				input: r#"{"param1":"Param 01","param2":1}"#.into(),
+				state: job_dao::STATE_DEAD.into(),
+				run_at: 1_650_000_000,
+				attempts: 5,
+				max_attempts: 5,
+				last_error: Some("Timeout(TimeoutError { timeout: 1000 })".into()),
+				created_at: 1_650_000_000,
+				updated_at: 1_650_000_300,
+			};
+
+			let _m = job_dao::Select::mock(
+				job_dao::SelectInput {
+					state: Some(job_dao::STATE_DEAD.into()),
+					action_id: Some(1),
+					limit: super::DEFAULT_LIMIT.into(),
+				},
+				job_dao::SelectOutput(vec![item.clone()]),
+			);
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					state: Some(job_dao::STATE_DEAD.into()),
+					action_id: Some(1),
+					limit: None,
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(ModeratorOutputInfo {
+					action_context,
+					data: super::Output {
+						items: vec![item.into()],
+					},
+				}),
+			);
+		})
+		.await;
+	}
+}
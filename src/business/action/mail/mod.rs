@@ -0,0 +1,2 @@
+pub mod send_confirmation_action;
+pub mod send_password_reset_action;
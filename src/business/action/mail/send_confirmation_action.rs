@@ -0,0 +1,172 @@
+use action_macros::ActionError;
+
+use crate::core::action::{
+	action_type::automatic_action_type::AutomaticActionType,
+	data::{
+		automatic_action_data::{
+			AutomaticActionError, AutomaticRequestInput, InternalInputResult, InternalRequestInput,
+		},
+		event_data::{DomainEvent, EventDelivery, EventType},
+		registry_data::{ActionAuth, ActionInfo},
+	},
+	definition::action::AutomaticAction,
+	main_impl::{event_impl, registry_impl},
+};
+use crate::{
+	business::service::confirmation_service,
+	core::external::data::external_exception::ExternalException,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const AUTOMATIC_ACTION_TYPE: AutomaticActionType = AutomaticActionType::SendConfirmation;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(AUTOMATIC_ACTION_TYPE, ActionAuth::Internal).input::<DomainEvent>(),
+	);
+	// queued, so that the mail is sent (and retried) out of the registration
+	event_impl::subscribe::<Action, (), Error>(EventType::UserRegistered, EventDelivery::Queued);
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	AutomaticError(AutomaticActionError),
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(InternalRequestInput<DomainEvent>);
+
+#[rocket::async_trait]
+impl AutomaticAction<DomainEvent, (), Error> for Action {
+	fn action_type() -> AutomaticActionType {
+		AUTOMATIC_ACTION_TYPE
+	}
+
+	async fn new(input: AutomaticRequestInput<DomainEvent>) -> Result<Self, Error> {
+		InternalInputResult::from(input)
+			.map(Self)
+			.map_err(Error::from)
+	}
+
+	async fn run_inner(self) -> Result<(), Error> {
+		let Self(input) = self;
+
+		// only subscribed to the registrations
+		match input.data {
+			DomainEvent::UserRegistered { user_id, email } => {
+				confirmation_service::send_confirmation(user_id, email).await?;
+				Ok(())
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use mockito::Mock;
+
+	use crate::business::service::confirmation_service;
+	use crate::core::action::action_type::general_action_type::ActionType;
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::automatic_action_data::tests::AutomaticRequestContextBuilder;
+	use crate::core::action::data::automatic_action_data::{
+		AutomaticActionError, AutomaticOutputInfo,
+	};
+	use crate::core::action::data::event_data::DomainEvent;
+	use crate::core::action::definition::action::Action;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::event_outbox_dao;
+	use crate::shared::data::user_data::UserId;
+	use crate::tests::test_utils::tests::run_test;
+
+	/// The registration event, as stored in the outbox for this action.
+	pub fn mock_queued(user_id: UserId, email: &str) -> Mock {
+		let UserId(id) = user_id;
+		event_outbox_dao::Insert::mock(
+			event_outbox_dao::InsertInput(vec![event_outbox_dao::NewEvent {
+				action_id: super::AUTOMATIC_ACTION_TYPE.id().into(),
+				event: format!(r#"{{"type":"user_registered","user_id":{id},"email":"{email}"}}"#),
+			}]),
+			(),
+		)
+	}
+
+	#[tokio::test]
+	async fn test_error_hook() {
+		run_test(|_| async {
+			let context = AutomaticRequestContextBuilder::build_hook();
+			let action_context = ActionContext {
+				action_type: super::AUTOMATIC_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: DomainEvent::UserRegistered {
+					user_id: UserId(1),
+					email: "user-01@domain.test".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::AutomaticError(AutomaticActionError::NotInternal),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let _m =
+				confirmation_service::tests::mock_confirmation(UserId(2), "user-02@domain.test");
+
+			let context = AutomaticRequestContextBuilder::build_internal();
+			let action_context = ActionContext {
+				action_type: super::AUTOMATIC_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: DomainEvent::UserRegistered {
+					user_id: UserId(2),
+					email: "user-02@domain.test".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(AutomaticOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+}
@@ -0,0 +1,252 @@
+use action_macros::ActionError;
+
+use crate::core::action::{
+	action_type::automatic_action_type::AutomaticActionType,
+	data::{
+		automatic_action_data::{
+			AutomaticActionError, AutomaticRequestInput, InternalInputResult, InternalRequestInput,
+		},
+		registry_data::{ActionAuth, ActionInfo},
+	},
+	definition::action::{ActionInput, AutomaticAction},
+	main_impl::{job_impl, registry_impl},
+};
+use crate::{
+	business::service::token_service,
+	core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	},
+	external::{
+		dao::main::{user_dao, user_password_reset_dao},
+		mail::mailer::{self, Mail},
+	},
+	shared::data::user_data::UserId,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const AUTOMATIC_ACTION_TYPE: AutomaticActionType = AutomaticActionType::SendPasswordReset;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(AUTOMATIC_ACTION_TYPE, ActionAuth::Internal).input::<Input>(),
+	);
+	job_impl::register::<Action, Input, (), Error>(job_impl::DEFAULT_MAX_ATTEMPTS);
+}
+
+const RESET_TTL_SECONDS: i64 = 60 * 60;
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+	pub email: String,
+}
+
+impl ActionInput for Input {}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	AutomaticError(AutomaticActionError),
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(InternalRequestInput<Input>);
+
+#[rocket::async_trait]
+impl AutomaticAction<Input, (), Error> for Action {
+	fn action_type() -> AutomaticActionType {
+		AUTOMATIC_ACTION_TYPE
+	}
+
+	async fn new(input: AutomaticRequestInput<Input>) -> Result<Self, Error> {
+		InternalInputResult::from(input)
+			.map(Self)
+			.map_err(Error::from)
+	}
+
+	async fn run_inner(self) -> Result<(), Error> {
+		let Self(input) = self;
+		let Input { email } = input.data;
+
+		// nothing is sent to the emails that aren't registered
+		let user = match user_dao::Select::run(user_dao::SelectInput::ByEmail(email)).await {
+			Ok(user_dao::SelectOutput(user)) => user,
+			Err(ExternalException::NotFound) => return Ok(()),
+			Err(error) => return Err(error.into()),
+		};
+
+		let token = token_service::generate();
+
+		user_password_reset_dao::Insert::run(user_password_reset_dao::InsertInput {
+			user_id: UserId(user.id),
+			token: token.clone(),
+			ttl_seconds: RESET_TTL_SECONDS,
+		})
+		.await?;
+
+		mailer::SendMail::run(reset_mail(user.email, &token)).await?;
+
+		Ok(())
+	}
+}
+
+////////////////////////////////////////////////
+////////////////// FUNCTIONS ///////////////////
+////////////////////////////////////////////////
+
+fn reset_mail(to: String, token: &str) -> Mail {
+	Mail {
+		to,
+		subject: "Reset your password".into(),
+		body: format!(
+			"Use the following token to reset your password (valid for 1 hour):\n\n{token}\n\n\
+			If you didn't request a password reset, you can ignore this email."
+		),
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use mockito::Mock;
+
+	use crate::business::service::token_service;
+	use crate::core::action::action_type::general_action_type::ActionType;
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::automatic_action_data::tests::AutomaticRequestContextBuilder;
+	use crate::core::action::data::automatic_action_data::{
+		AutomaticActionError, AutomaticOutputInfo,
+	};
+	use crate::core::action::definition::action::Action;
+	use crate::core::action::main_impl::job_impl;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::{job_dao, user_dao, user_password_reset_dao};
+	use crate::external::mail::mailer;
+	use crate::shared::data::user_data::UserId;
+	use crate::tests::test_utils::tests::run_test;
+
+	/// The job of the password reset, as enqueued for this action.
+	pub fn mock_enqueue(email: &str) -> Mock {
+		job_dao::Insert::mock(
+			job_dao::InsertInput {
+				action_id: super::AUTOMATIC_ACTION_TYPE.id().into(),
+				input: format!(r#"{{"email":"{email}"}}"#),
+				delay: 0,
+				max_attempts: job_impl::DEFAULT_MAX_ATTEMPTS,
+			},
+			job_dao::InsertOutput { id: 1 },
+		)
+	}
+
+	pub fn mock_action(user_id: UserId, email: &str) -> Vec<Mock> {
+		let UserId(id) = user_id;
+		let token = token_service::generate();
+		vec![
+			user_dao::Select::mock(
+				user_dao::SelectInput::ByEmail(email.into()),
+				user_dao::SelectOutput(user_dao::User {
+					id,
+					name: format!("User {id:02}"),
+					email: email.into(),
+					encrypted_pass: "p4$$w0rd".into(),
+					confirmed: true,
+				}),
+			),
+			user_password_reset_dao::Insert::mock(
+				user_password_reset_dao::InsertInput {
+					user_id,
+					token: token.clone(),
+					ttl_seconds: super::RESET_TTL_SECONDS,
+				},
+				user_password_reset_dao::InsertOutput { id: 1 },
+			),
+			mailer::SendMail::mock(super::reset_mail(email.into(), &token), ()),
+		]
+	}
+
+	#[tokio::test]
+	async fn test_error_hook() {
+		run_test(|_| async {
+			let context = AutomaticRequestContextBuilder::build_hook();
+			let action_context = ActionContext {
+				action_type: super::AUTOMATIC_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					email: "user-01@domain.test".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::AutomaticError(AutomaticActionError::NotInternal),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let _m = mock_action(UserId(2), "user-02@domain.test");
+
+			let context = AutomaticRequestContextBuilder::build_internal();
+			let action_context = ActionContext {
+				action_type: super::AUTOMATIC_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					email: "user-02@domain.test".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(AutomaticOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_reset_mail() {
+		run_test(|_| async {
+			let mail = super::reset_mail("user-03@domain.test".into(), "token-03");
+			assert_eq!(&mail.to, "user-03@domain.test");
+			assert!(mail.body.contains("token-03"));
+		})
+		.await;
+	}
+}
@@ -1,4 +1,10 @@
+pub mod audit;
 pub mod auto_action;
 pub mod echo;
+pub mod job;
+pub mod mail;
+pub mod registry;
+pub mod schedule;
+pub mod session;
 pub mod user;
 pub mod web_action;
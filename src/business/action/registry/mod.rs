@@ -0,0 +1 @@
+pub mod select_actions_action;
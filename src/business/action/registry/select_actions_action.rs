@@ -0,0 +1,151 @@
+use action_macros::ActionError;
+
+use crate::core::action::definition::action::{ActionOutput, ModeratorAction};
+use crate::core::action::{
+	action_type::moderator_action_type::ModeratorActionType,
+	data::{
+		moderator_action_data::{ModeratorActionError, ModeratorRequestInput},
+		registry_data::{ActionAuth, ActionInfo},
+	},
+	main_impl::registry_impl,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const MODERATOR_ACTION_TYPE: ModeratorActionType = ModeratorActionType::SelectActions;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(MODERATOR_ACTION_TYPE, ActionAuth::Moderator)
+			.output::<Output>()
+			.route("GET", "/moderator/actions"),
+	);
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+	pub items: Vec<ActionInfo>,
+}
+
+impl ActionOutput for Output {}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	ModeratorError(ModeratorActionError),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(ModeratorRequestInput<()>);
+
+#[rocket::async_trait]
+impl ModeratorAction<(), Output, Error> for Action {
+	fn action_type() -> ModeratorActionType {
+		MODERATOR_ACTION_TYPE
+	}
+
+	async fn new(input: ModeratorRequestInput<()>) -> Result<Self, Error> {
+		Ok(Self(input))
+	}
+
+	async fn run_inner(self) -> Result<Output, Error> {
+		Ok(Output {
+			items: registry_impl::list(),
+		})
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::moderator_action_data::tests::{
+		ModeratorRequestContextBuilder, ModeratorSessionBuilder,
+	};
+	use crate::core::action::data::moderator_action_data::{
+		ModeratorActionError, ModeratorOutputInfo,
+	};
+	use crate::core::action::data::registry_data::ActionAuth;
+	use crate::core::action::definition::action::Action;
+	use crate::core::action::main_impl::registry_impl;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_not_allowed() {
+		run_test(|_| async {
+			let context = ModeratorRequestContextBuilder::new().build();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput { data: (), context })).await;
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::ModeratorError(ModeratorActionError::NotAllowed(
+						super::MODERATOR_ACTION_TYPE
+					)),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let context = ModeratorRequestContextBuilder::new()
+				.session(
+					ModeratorSessionBuilder::new()
+						.allowed_actions(vec![super::MODERATOR_ACTION_TYPE])
+						.build(),
+				)
+				.build();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput { data: (), context })).await;
+			assert_eq!(
+				&result,
+				&Ok(ModeratorOutputInfo {
+					action_context,
+					data: super::Output {
+						items: registry_impl::list(),
+					},
+				}),
+			);
+
+			let items = result.map(|info| info.data.items).unwrap_or_default();
+			let item = items
+				.iter()
+				.find(|item| item.scope == "Moderator" && item.name == "SelectActions");
+			assert_eq!(item.map(|item| item.auth), Some(ActionAuth::Moderator));
+			assert_eq!(
+				item.map(|item| item.output.fields.clone()),
+				Some(vec!["items".into()]),
+			);
+		})
+		.await;
+	}
+}
@@ -0,0 +1,3 @@
+pub mod select_schedules_action;
+pub mod trigger_schedule_action;
+pub mod update_schedule_action;
@@ -0,0 +1,212 @@
+use action_macros::ActionError;
+use chrono::Utc;
+
+use crate::core::action::definition::action::ActionOutput;
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
+use crate::{
+	core::{
+		action::{
+			action_type::moderator_action_type::ModeratorActionType,
+			data::moderator_action_data::{ModeratorActionError, ModeratorRequestInput},
+			definition::action::ModeratorAction,
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::schedule_dao,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const MODERATOR_ACTION_TYPE: ModeratorActionType = ModeratorActionType::SelectSchedules;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(MODERATOR_ACTION_TYPE, ActionAuth::Moderator)
+			.output::<Output>()
+			.route("GET", "/moderator/schedules"),
+	);
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+/// The times are in unix seconds; a triggered schedule runs on the next tick
+/// of the scheduler.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemOutput {
+	pub name: String,
+	pub action_id: i64,
+	pub cron: Option<String>,
+	pub interval_seconds: Option<i64>,
+	pub paused: bool,
+	pub triggered: bool,
+	pub running: bool,
+	pub last_run_at: Option<i64>,
+	pub last_error: Option<String>,
+	pub next_run_at: i64,
+}
+
+impl ItemOutput {
+	fn new(data: schedule_dao::Schedule, now: i64) -> Self {
+		let schedule_dao::Schedule {
+			name,
+			action_id,
+			cron,
+			interval_seconds,
+			paused,
+			triggered,
+			locked_until,
+			last_run_at,
+			last_error,
+			next_run_at,
+		} = data;
+
+		Self {
+			name,
+			action_id,
+			cron,
+			interval_seconds,
+			paused,
+			triggered,
+			running: locked_until.is_some_and(|locked_until| locked_until > now),
+			last_run_at,
+			last_error,
+			next_run_at,
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+	pub items: Vec<ItemOutput>,
+}
+
+impl ActionOutput for Output {}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	ModeratorError(ModeratorActionError),
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(ModeratorRequestInput<()>);
+
+#[rocket::async_trait]
+impl ModeratorAction<(), Output, Error> for Action {
+	fn action_type() -> ModeratorActionType {
+		MODERATOR_ACTION_TYPE
+	}
+
+	async fn new(input: ModeratorRequestInput<()>) -> Result<Self, Error> {
+		Ok(Self(input))
+	}
+
+	async fn run_inner(self) -> Result<Output, Error> {
+		let schedule_dao::SelectOutput(items) = schedule_dao::Select::run(()).await?;
+		let now = Utc::now().timestamp();
+		let items = items
+			.into_iter()
+			.map(|item| ItemOutput::new(item, now))
+			.collect();
+		Ok(Output { items })
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use crate::core::action::data::action_data::{ActionContext, RequestInput};
+	use crate::core::action::data::moderator_action_data::tests::{
+		ModeratorRequestContextBuilder, ModeratorSessionBuilder,
+	};
+	use crate::core::action::data::moderator_action_data::ModeratorOutputInfo;
+	use crate::core::action::definition::action::Action;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::schedule_dao;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let context = ModeratorRequestContextBuilder::new()
+				.session(
+					ModeratorSessionBuilder::new()
+						.allowed_actions(vec![super::MODERATOR_ACTION_TYPE])
+						.build(),
+				)
+				.build();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let item = |name: &str, locked_until| schedule_dao::Schedule {
+				name: name.into(),
+				action_id: 1,
+				cron: Some("0 3 * * *".into()),
+				interval_seconds: None,
+				paused: false,
+				triggered: false,
+				locked_until,
+				last_run_at: Some(1_650_000_000),
+				last_error: None,
+				next_run_at: 1_650_086_400,
+			};
+
+			let _m = schedule_dao::Select::mock(
+				(),
+				schedule_dao::SelectOutput(vec![
+					item("nightly", Some(1_650_000_060)),
+					item("running", Some(i64::MAX)),
+				]),
+			);
+
+			let result = super::Action::run(Ok(RequestInput { data: (), context })).await;
+
+			let output = |name: &str, running| super::ItemOutput {
+				name: name.into(),
+				action_id: 1,
+				cron: Some("0 3 * * *".into()),
+				interval_seconds: None,
+				paused: false,
+				triggered: false,
+				running,
+				last_run_at: Some(1_650_000_000),
+				last_error: None,
+				next_run_at: 1_650_086_400,
+			};
+
+			assert_eq!(
+				&result,
+				&Ok(ModeratorOutputInfo {
+					action_context,
+					data: super::Output {
+						items: vec![output("nightly", false), output("running", true)],
+					},
+				}),
+			);
+		})
+		.await;
+	}
+}
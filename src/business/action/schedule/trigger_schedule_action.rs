@@ -0,0 +1,160 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{TextRule, ValidationResult, Validator};
+use crate::core::action::definition::action::ActionInput;
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
+use crate::{
+	core::{
+		action::{
+			action_type::moderator_action_type::ModeratorActionType,
+			data::moderator_action_data::{ModeratorActionError, ModeratorRequestInput},
+			definition::action::ModeratorAction,
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::schedule_dao,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const MODERATOR_ACTION_TYPE: ModeratorActionType = ModeratorActionType::TriggerSchedule;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(MODERATOR_ACTION_TYPE, ActionAuth::Moderator)
+			.input::<Input>()
+			.route("POST", "/moderator/schedules/<name>/trigger"),
+	);
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The schedule runs on the next tick of the scheduler, even when it's
+/// paused, once its current run (if any) finished.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+	pub name: String,
+}
+
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		Validator::new()
+			.text("name", &self.name, &[TextRule::Required])
+			.result()
+	}
+}
+
+impl From<Input> for schedule_dao::TriggerInput {
+	fn from(input: Input) -> Self {
+		Self { name: input.name }
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	ModeratorError(ModeratorActionError),
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(ModeratorRequestInput<Input>);
+
+#[rocket::async_trait]
+impl ModeratorAction<Input, (), Error> for Action {
+	fn action_type() -> ModeratorActionType {
+		MODERATOR_ACTION_TYPE
+	}
+
+	async fn new(input: ModeratorRequestInput<Input>) -> Result<Self, Error> {
+		Ok(Self(input))
+	}
+
+	async fn run_inner(self) -> Result<(), Error> {
+		let Self(input) = self;
+		let schedule_dao::UpdateOutput { updated } =
+			schedule_dao::Trigger::run(input.data.into()).await?;
+
+		if updated {
+			Ok(())
+		} else {
+			Err(Error::from(ExternalException::NotFound))
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use crate::core::action::data::action_data::{ActionContext, RequestInput};
+	use crate::core::action::data::moderator_action_data::tests::{
+		ModeratorRequestContextBuilder, ModeratorSessionBuilder,
+	};
+	use crate::core::action::data::moderator_action_data::ModeratorOutputInfo;
+	use crate::core::action::definition::action::Action;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::schedule_dao;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let context = ModeratorRequestContextBuilder::new()
+				.session(
+					ModeratorSessionBuilder::new()
+						.allowed_actions(vec![super::MODERATOR_ACTION_TYPE])
+						.build(),
+				)
+				.build();
+			let action_context = ActionContext {
+				action_type: super::MODERATOR_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let _m = schedule_dao::Trigger::mock(
+				schedule_dao::TriggerInput {
+					name: "nightly".into(),
+				},
+				schedule_dao::UpdateOutput { updated: true },
+			);
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					name: "nightly".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(ModeratorOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+}
@@ -0,0 +1 @@
+pub mod revoke_sessions_action;
@@ -0,0 +1,205 @@
+use action_macros::ActionError;
+
+use crate::core::action::{
+	action_type::automatic_action_type::AutomaticActionType,
+	data::{
+		automatic_action_data::{
+			AutomaticActionError, AutomaticRequestInput, InternalInputResult, InternalRequestInput,
+		},
+		event_data::{DomainEvent, EventDelivery, EventType},
+		registry_data::{ActionAuth, ActionInfo},
+	},
+	definition::action::AutomaticAction,
+	main_impl::{event_impl, registry_impl},
+};
+use crate::{
+	core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	},
+	external::dao::main::user_session_dao,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const AUTOMATIC_ACTION_TYPE: AutomaticActionType = AutomaticActionType::RevokeSessions;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(AUTOMATIC_ACTION_TYPE, ActionAuth::Internal).input::<DomainEvent>(),
+	);
+	// inline, so that the sessions of the deleted user stop working right away
+	event_impl::subscribe::<Action, (), Error>(EventType::UserDeleted, EventDelivery::Inline);
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	AutomaticError(AutomaticActionError),
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(InternalRequestInput<DomainEvent>);
+
+#[rocket::async_trait]
+impl AutomaticAction<DomainEvent, (), Error> for Action {
+	fn action_type() -> AutomaticActionType {
+		AUTOMATIC_ACTION_TYPE
+	}
+
+	async fn new(input: AutomaticRequestInput<DomainEvent>) -> Result<Self, Error> {
+		InternalInputResult::from(input)
+			.map(Self)
+			.map_err(Error::from)
+	}
+
+	async fn run_inner(self) -> Result<(), Error> {
+		let Self(input) = self;
+
+		// only subscribed to the deletions
+		match input.data {
+			DomainEvent::UserDeleted { user_id } => {
+				user_session_dao::Revoke::run(user_session_dao::RevokeInput::ByUser(user_id))
+					.await?;
+				Ok(())
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use mockito::Mock;
+
+	use crate::core::action::data::action_data::{
+		ActionContext, ActionErrorInfo, Application, RequestInput,
+	};
+	use crate::core::action::data::automatic_action_data::tests::AutomaticRequestContextBuilder;
+	use crate::core::action::data::automatic_action_data::{
+		AutomaticActionError, AutomaticOutputInfo,
+	};
+	use crate::core::action::data::event_data::{DomainEvent, EventDelivery, EventType};
+	use crate::core::action::definition::action::Action;
+	use crate::core::action::main_impl::event_impl;
+	use crate::core::external::data::external_exception::ExternalException;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::user_session_dao;
+	use crate::shared::data::user_data::UserId;
+	use crate::tests::test_utils::tests::run_test;
+
+	pub fn mock_action(user_id: UserId) -> Mock {
+		user_session_dao::Revoke::mock(
+			user_session_dao::RevokeInput::ByUser(user_id),
+			user_session_dao::RevokeOutput { revoked: 1 },
+		)
+	}
+
+	#[tokio::test]
+	async fn test_error_hook() {
+		run_test(|_| async {
+			let context = AutomaticRequestContextBuilder::build_hook();
+			let action_context = ActionContext {
+				action_type: super::AUTOMATIC_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: DomainEvent::UserDeleted { user_id: UserId(1) },
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::AutomaticError(AutomaticActionError::NotInternal),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let _m = mock_action(UserId(2));
+
+			let context = AutomaticRequestContextBuilder::build_internal();
+			let action_context = ActionContext {
+				action_type: super::AUTOMATIC_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: DomainEvent::UserDeleted { user_id: UserId(2) },
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(AutomaticOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_subscribed() {
+		run_test(|helper| async move {
+			let subscribed = event_impl::subscriptions().iter().any(|subscription| {
+				subscription.event_type == EventType::UserDeleted
+					&& subscription.delivery == EventDelivery::Inline
+					&& subscription.action_type == super::AUTOMATIC_ACTION_TYPE
+			});
+			assert!(subscribed);
+
+			let _m = mock_action(UserId(3));
+			let result = event_impl::run(
+				&Application::default(),
+				async {
+					event_impl::publish(DomainEvent::UserDeleted { user_id: UserId(3) });
+					Ok::<_, ExternalException>(())
+				},
+				|error| error,
+			)
+			.await;
+			assert_eq!(result, Ok(()));
+
+			// not mocked, so the delivery fails
+			let result = event_impl::run(
+				&Application::default(),
+				async {
+					event_impl::publish(DomainEvent::UserDeleted { user_id: UserId(4) });
+					Ok::<_, ExternalException>(())
+				},
+				|error| error,
+			)
+			.await;
+			assert_eq!(result, Ok(()));
+			assert!(helper.pop_log().unwrap_or_default().starts_with(
+				"ERROR - [event] UserDeleted could not be delivered to RevokeSessions"
+			));
+		})
+		.await;
+	}
+}
@@ -0,0 +1,199 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{TextRule, ValidationResult, Validator};
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
+use crate::{
+	core::{
+		action::{
+			action_type::user_action_type::UserActionType,
+			data::user_action_data::{UserActionError, UserRequestInput},
+			definition::action::{ActionInput, ActionOutput, UserAction},
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::user_confirmation_dao,
+	shared::data::user_data::UserId,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const USER_ACTION_TYPE: UserActionType = UserActionType::ConfirmEmail;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::Any)
+			.input::<Input>()
+			.output::<Output>()
+			.route("POST", "/user/confirmation"),
+	);
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+	pub token: String,
+}
+
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		Validator::new()
+			.text("token", &self.token, &[TextRule::Required])
+			.result()
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+	pub id: UserId,
+}
+
+impl ActionOutput for Output {}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	UserError(UserActionError),
+	#[action_error(
+		code = "user.confirmation_token_invalid",
+		msg = "The confirmation token is invalid or has expired.",
+		category = "Validation"
+	)]
+	InvalidToken,
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+// The token is received by mail, so it can be used with or without a session.
+#[derive(Debug)]
+pub struct Action(UserRequestInput<Input>);
+
+#[rocket::async_trait]
+impl UserAction<Input, Output, Error> for Action {
+	fn action_type() -> UserActionType {
+		USER_ACTION_TYPE
+	}
+
+	async fn new(input: UserRequestInput<Input>) -> Result<Self, Error> {
+		Ok(Self(input))
+	}
+
+	async fn run_inner(self) -> Result<Output, Error> {
+		let Self(input) = self;
+		let Input { token } = input.data;
+
+		let user_confirmation_dao::UseOutput(user_id) =
+			user_confirmation_dao::Use::run(user_confirmation_dao::UseInput { token }).await?;
+		let id = user_id.ok_or(Error::InvalidToken)?;
+
+		Ok(Output { id })
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use mockito::Mock;
+
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::user_action_data::tests::UserRequestContextBuilder;
+	use crate::core::action::data::user_action_data::UserOutputInfo;
+	use crate::core::action::definition::action::Action;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::user_confirmation_dao;
+	use crate::shared::data::user_data::UserId;
+	use crate::tests::test_utils::tests::run_test;
+
+	pub fn mock_action(token: &str, user_id: Option<UserId>) -> Mock {
+		user_confirmation_dao::Use::mock(
+			user_confirmation_dao::UseInput {
+				token: token.into(),
+			},
+			user_confirmation_dao::UseOutput(user_id),
+		)
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let _m = mock_action("token-confirm-01", Some(UserId(5)));
+
+			let context = UserRequestContextBuilder::build_unconfirmed();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					token: "token-confirm-01".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(UserOutputInfo {
+					action_context,
+					data: super::Output { id: UserId(5) },
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_invalid_token() {
+		run_test(|_| async {
+			let _m = mock_action("token-confirm-02", None);
+
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					token: "token-confirm-02".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::InvalidToken,
+				}),
+			);
+		})
+		.await;
+	}
+}
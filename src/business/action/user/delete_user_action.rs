@@ -1,6 +1,14 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{ValidationResult, Validator};
+use crate::core::action::{
+	data::event_data::DomainEvent,
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::{event_impl, registry_impl},
+};
 use crate::{
 	core::{
-		action::definition::action::{ActionError, ActionInput, UserAction},
+		action::definition::action::{ActionInput, UserAction},
 		external::data::external_exception::ExternalException,
 	},
 	shared::data::user_data::UserId,
@@ -9,27 +17,35 @@ use crate::{
 	core::{
 		action::{
 			action_type::user_action_type::UserActionType,
-			data::{
-				action_data::{DescriptiveError, ErrorData},
-				user_action_data::{UserActionError, UserRequestInput},
+			data::user_action_data::{
+				UserActionError, UserAuthInputResult, UserAuthRequestInput, UserRequestInput,
 			},
 		},
 		external::definition::external::ExternalAction,
 	},
-	external::dao::main::user_dao,
+	external::dao::main::{moderator_dao, user_dao},
 };
 
 ////////////////////////////////////////////////
 ///////////////////// TYPE /////////////////////
 ////////////////////////////////////////////////
 
-const USER_ACTION_TYPE: UserActionType = UserActionType::Register;
+const USER_ACTION_TYPE: UserActionType = UserActionType::DeleteUser;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::Auth)
+			.input::<Input>()
+			.route("DELETE", "/user/<id>"),
+	);
+}
 
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Input(pub UserId);
 
 impl From<Input> for user_dao::DeleteInput {
@@ -38,52 +54,31 @@ impl From<Input> for user_dao::DeleteInput {
 	}
 }
 
-impl ActionInput for Input {}
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		Validator::new()
+			.range("id", self.0 .0, 1, i64::MAX)
+			.result()
+	}
+}
 
 ////////////////////////////////////////////////
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum Error {
 	UserError(UserActionError),
 	ExternalError(ExternalException),
 }
 
-impl ActionError for Error {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match self {
-			Error::UserError(error) => error.private_error(),
-			Error::ExternalError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match self {
-			Error::UserError(error) => error.public_error(),
-			Error::ExternalError(error) => error.public_error(),
-		}
-	}
-}
-
-impl From<UserActionError> for Error {
-	fn from(error: UserActionError) -> Self {
-		Self::UserError(error)
-	}
-}
-
-impl From<ExternalException> for Error {
-	fn from(error: ExternalException) -> Self {
-		Self::ExternalError(error)
-	}
-}
-
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
 
 #[derive(Debug)]
-pub struct Action(UserRequestInput<Input>);
+pub struct Action(UserAuthRequestInput<Input>);
 
 #[rocket::async_trait]
 impl UserAction<Input, (), Error> for Action {
@@ -92,14 +87,33 @@ impl UserAction<Input, (), Error> for Action {
 	}
 
 	async fn new(input: UserRequestInput<Input>) -> Result<Self, Error> {
+		let input = UserAuthInputResult::from(input)?;
+		let caller = UserId(input.context.session.user_id as i64);
+
+		// the users can delete themselves, the moderators can delete anyone
+		if input.data.0 != caller {
+			let moderator_dao::SelectOutput(moderator) =
+				moderator_dao::Select::run(moderator_dao::SelectInput::ByUser(caller)).await?;
+
+			if moderator.is_none() {
+				return Err(Error::from(UserActionError::Forbidden));
+			}
+		}
+
 		Ok(Self(input))
 	}
 
 	async fn run_inner(self) -> Result<(), Error> {
 		let Self(input) = self;
-		user_dao::Delete::run(input.data.into())
-			.await
-			.map_err(Error::from)
+		let user_id = input.data.0;
+		let user_dao::DeleteOutput { deleted } = user_dao::Delete::run(input.data.into()).await?;
+
+		if deleted {
+			event_impl::publish(DomainEvent::UserDeleted { user_id });
+			Ok(())
+		} else {
+			Err(Error::from(ExternalException::NotFound))
+		}
 	}
 }
 
@@ -111,12 +125,18 @@ impl UserAction<Input, (), Error> for Action {
 pub mod tests {
 	use mockito::Mock;
 
-	use crate::core::action::data::action_data::{ActionContext, RequestInput};
-	use crate::core::action::data::user_action_data::tests::UserRequestContextBuilder;
-	use crate::core::action::data::user_action_data::UserOutputInfo;
+	use crate::business::action::session::revoke_sessions_action;
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::user_action_data::tests::{
+		UserAuthSessionBuilder, UserRequestContextBuilder,
+	};
+	use crate::core::action::data::user_action_data::{
+		UserActionError, UserOutputInfo, UserRequestContext, UserSession,
+	};
 	use crate::core::action::definition::action::Action;
+	use crate::core::external::data::external_exception::ExternalException;
 	use crate::core::external::definition::external::tests::ExternalMocker;
-	use crate::external::dao::main::user_dao;
+	use crate::external::dao::main::{moderator_dao, user_dao};
 	use crate::shared::data::user_data::UserId;
 	use crate::tests::test_utils::tests::run_test;
 
@@ -126,16 +146,41 @@ pub mod tests {
 	}
 
 	pub fn mock_action(user_id: UserId) -> ActionMock {
-		let mocks = vec![user_dao::Delete::mock(user_dao::DeleteInput(user_id), ())];
+		let mocks = vec![
+			user_dao::Delete::mock(
+				user_dao::DeleteInput(user_id),
+				user_dao::DeleteOutput { deleted: true },
+			),
+			revoke_sessions_action::tests::mock_action(user_id),
+		];
 		ActionMock { user_id, mocks }
 	}
 
+	fn auth_context(user_id: u64) -> UserRequestContext {
+		UserRequestContextBuilder::new()
+			.session(UserSession::Auth(
+				UserAuthSessionBuilder::new().user_id(user_id).build(),
+			))
+			.build()
+	}
+
+	fn mock_moderator(user_id: i64, moderator: bool) -> Mock {
+		moderator_dao::Select::mock(
+			moderator_dao::SelectInput::ByUser(UserId(user_id)),
+			moderator_dao::SelectOutput(moderator.then(|| moderator_dao::Moderator {
+				user_id: UserId(user_id),
+				admin: false,
+				allowed_actions: vec![],
+			})),
+		)
+	}
+
 	#[tokio::test]
 	async fn test_ok() {
 		run_test(|_| async {
 			let ActionMock { user_id, mocks: _m } = mock_action(UserId(12));
 
-			let context = UserRequestContextBuilder::build_no_auth();
+			let context = auth_context(12);
 			let action_context = ActionContext {
 				action_type: super::USER_ACTION_TYPE,
 				context: Some(context.clone()),
@@ -157,4 +202,119 @@ pub mod tests {
 		})
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_ok_moderator() {
+		run_test(|_| async {
+			let ActionMock { user_id, mocks: _m } = mock_action(UserId(12));
+			let _m2 = mock_moderator(7, true);
+
+			let context = auth_context(7);
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input(user_id),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(UserOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_error_other_user() {
+		run_test(|_| async {
+			let _m = mock_moderator(7, false);
+
+			let context = auth_context(7);
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input(UserId(12)),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::UserError(UserActionError::Forbidden),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_error_no_auth() {
+		run_test(|_| async {
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input(UserId(12)),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::UserError(UserActionError::Unauthenticated),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_not_found() {
+		run_test(|_| async {
+			let user_id = UserId(13);
+			let _m = user_dao::Delete::mock(
+				user_dao::DeleteInput(user_id),
+				user_dao::DeleteOutput { deleted: false },
+			);
+
+			let context = auth_context(13);
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input(user_id),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::ExternalError(ExternalException::NotFound),
+				}),
+			);
+		})
+		.await;
+	}
 }
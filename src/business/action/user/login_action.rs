@@ -1,15 +1,31 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{TextRule, ValidationResult, Validator};
+use crate::core::action::{
+	data::event_data::DomainEvent,
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::{event_impl, registry_impl},
+};
 use crate::core::action::{
 	data::user_action_data::{UserNoAuthInputResult, UserRequestInput},
-	definition::action::{ActionError, ActionInput, ActionOutput, UserAction},
+	definition::action::{ActionInput, ActionOutput, UserAction},
 };
 use crate::{
-	core::action::{
-		action_type::user_action_type::UserActionType,
-		data::{
-			action_data::{DescriptiveError, ErrorData},
-			user_action_data::{UserActionError, UserNoAuthRequestInput},
+	business::service::{
+		password_service::{self, PasswordError, PasswordVerification},
+		token_service,
+	},
+	core::{
+		action::{
+			action_type::user_action_type::UserActionType,
+			data::user_action_data::{UserActionError, UserNoAuthRequestInput},
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
 		},
 	},
+	external::dao::main::{user_dao, user_session_dao},
+	shared::data::user_data::UserId,
 };
 
 ////////////////////////////////////////////////
@@ -18,26 +34,47 @@ use crate::{
 
 const USER_ACTION_TYPE: UserActionType = UserActionType::Login;
 
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::NoAuth)
+			.input::<Input>()
+			.output::<Output>()
+			.route("POST", "/user/session"),
+	);
+}
+
+const SESSION_TTL_SECONDS: i64 = 30 * 24 * 60 * 60;
+
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Input {
-	pub name: String,
+	pub login: String,
 	pub pass: String,
 }
 
-impl ActionInput for Input {}
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		Validator::new()
+			.text("login", &self.login, &[TextRule::Required])
+			.text("pass", &self.pass, &[TextRule::Required])
+			.result()
+	}
+}
 
 ////////////////////////////////////////////////
 //////////////////// OUTPUT ////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
-	pub id: u64,
+	pub id: UserId,
 	pub name: String,
+	pub token: String,
 }
 
 impl ActionOutput for Output {}
@@ -46,31 +83,30 @@ impl ActionOutput for Output {}
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum Error {
 	UserError(UserActionError),
+	#[action_error(
+		code = "user.invalid_credentials",
+		msg = "Invalid username or password.",
+		category = "Unauthenticated"
+	)]
+	InvalidCredentials,
+	PasswordError(PasswordError),
+	#[action_error(skip_from)]
+	ExternalError(ExternalException),
 }
 
-impl ActionError for Error {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match self {
-			Error::UserError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match self {
-			Error::UserError(error) => error.public_error(),
+impl From<ExternalException> for Error {
+	fn from(error: ExternalException) -> Self {
+		match error {
+			// don't reveal whether the user exists
+			ExternalException::NotFound => Self::InvalidCredentials,
+			error => Self::ExternalError(error),
 		}
 	}
 }
 
-impl From<UserActionError> for Error {
-	fn from(error: UserActionError) -> Self {
-		Self::UserError(error)
-	}
-}
-
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
@@ -91,12 +127,47 @@ impl UserAction<Input, Output, Error> for Action {
 	}
 
 	async fn run_inner(self) -> Result<Output, Error> {
-		let Self(input) = &self;
-		let Input { name, pass } = &input.data;
-		println!("TODO: login: {name} ({pass})");
+		let Self(input) = self;
+		let Input { login, pass } = input.data;
+
+		let user = match user_dao::Select::run(user_dao::SelectInput::ByLogin(login)).await {
+			Ok(user_dao::SelectOutput(user)) => user,
+			Err(ExternalException::NotFound) => {
+				// verified anyway, so that an unknown login takes as long as a
+				// wrong password
+				password_service::verify_dummy(pass).await?;
+				return Err(Error::InvalidCredentials);
+			}
+			Err(error) => return Err(Error::from(error)),
+		};
+		let user_id = UserId(user.id);
+
+		match password_service::verify(pass, user.encrypted_pass).await? {
+			PasswordVerification::Invalid => Err(Error::InvalidCredentials)?,
+			PasswordVerification::Valid => (),
+			PasswordVerification::ValidOutdated(encrypted_pass) => {
+				user_dao::UpdatePass::run(user_dao::UpdatePassInput {
+					id: user_id,
+					encrypted_pass,
+				})
+				.await?;
+			}
+		}
+
+		let token = token_service::generate();
+		user_session_dao::Insert::run(user_session_dao::InsertInput {
+			user_id,
+			token: token.clone(),
+			confirmed: user.confirmed,
+			ttl_seconds: SESSION_TTL_SECONDS,
+		})
+		.await?;
+		event_impl::publish(DomainEvent::SessionCreated { user_id });
+
 		let result = Output {
-			id: 1,
-			name: name.into(),
+			id: user_id,
+			name: user.name,
+			token,
 		};
 		Ok(result)
 	}
@@ -107,14 +178,75 @@ impl UserAction<Input, Output, Error> for Action {
 ////////////////////////////////////////////////
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
+	use argon2::{
+		password_hash::{PasswordHasher, SaltString},
+		Argon2,
+	};
+	use mockito::Mock;
+
+	use crate::business::service::{password_service, token_service};
 	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
 	use crate::core::action::data::user_action_data::tests::UserRequestContextBuilder;
 	use crate::core::action::data::user_action_data::UserActionError;
 	use crate::core::action::data::user_action_data::UserOutputInfo;
-	use crate::core::action::definition::action::Action;
+	use crate::core::action::definition::action::{Action, ActionError};
+	use crate::core::external::data::external_exception::ExternalException;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::{user_dao, user_session_dao};
+	use crate::shared::data::user_data::UserId;
 	use crate::tests::test_utils::tests::run_test;
 
+	pub struct ActionMock {
+		pub output: super::Output,
+		pub mocks: Vec<Mock>,
+	}
+
+	fn mock_user(user_id: UserId, login: &str, encrypted_pass: String) -> Mock {
+		let UserId(id) = user_id;
+		user_dao::Select::mock(
+			user_dao::SelectInput::ByLogin(login.into()),
+			user_dao::SelectOutput(user_dao::User {
+				id,
+				name: format!("User {id:02}"),
+				email: format!("user-{id:02}@domain.test"),
+				encrypted_pass,
+				confirmed: true,
+			}),
+		)
+	}
+
+	fn mock_session(user_id: UserId) -> Mock {
+		user_session_dao::Insert::mock(
+			user_session_dao::InsertInput {
+				user_id,
+				token: token_service::generate(),
+				confirmed: true,
+				ttl_seconds: super::SESSION_TTL_SECONDS,
+			},
+			user_session_dao::InsertOutput { id: 1 },
+		)
+	}
+
+	pub fn mock_action(input: super::Input, user_id: UserId) -> ActionMock {
+		let super::Input { login, pass } = input;
+		let UserId(id) = user_id;
+		let encrypted_pass = password_service::hash_sync(&pass).unwrap();
+
+		let output = super::Output {
+			id: user_id,
+			name: format!("User {id:02}"),
+			token: token_service::generate(),
+		};
+
+		let mocks = vec![
+			mock_user(user_id, &login, encrypted_pass),
+			mock_session(user_id),
+		];
+
+		ActionMock { output, mocks }
+	}
+
 	#[tokio::test]
 	async fn test_error_auth() {
 		run_test(|_| async {
@@ -122,7 +254,7 @@ mod tests {
 
 			let result = super::Action::run(Ok(RequestInput {
 				data: super::Input {
-					name: "User 01".into(),
+					login: "User 01".into(),
 					pass: "p4$$w0rd".into(),
 				},
 				context: context.clone(),
@@ -146,6 +278,118 @@ mod tests {
 	#[tokio::test]
 	async fn test_ok() {
 		run_test(|_| async {
+			let input = super::Input {
+				login: "User 02".into(),
+				pass: "p4$$w0rd2".into(),
+			};
+			let ActionMock { output, mocks: _m } = mock_action(input.clone(), UserId(2));
+
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: input,
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(UserOutputInfo {
+					action_context,
+					data: output,
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok_email() {
+		run_test(|_| async {
+			let input = super::Input {
+				login: "user-05@domain.test".into(),
+				pass: "p4$$w0rd5".into(),
+			};
+			let ActionMock { output, mocks: _m } = mock_action(input.clone(), UserId(5));
+
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: input,
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(UserOutputInfo {
+					action_context,
+					data: output,
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_invalid_pass() {
+		run_test(|_| async {
+			let encrypted_pass = password_service::hash_sync("p4$$w0rd3").unwrap();
+			let _m = mock_user(UserId(3), "User 03", encrypted_pass);
+
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					login: "User 03".into(),
+					pass: "wrong-p4$$w0rd".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::InvalidCredentials,
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_rehash_outdated() {
+		run_test(|_| async {
+			let user_id = UserId(4);
+			let salt = SaltString::new("b2xkc2FsdG9sZHNhbHQ").unwrap();
+			let old_pass = Argon2::default()
+				.hash_password("p4$$w0rd4".as_bytes(), &salt)
+				.map(|hash| hash.to_string())
+				.unwrap();
+			let _m1 = mock_user(user_id, "User 04", old_pass);
+			let _m2 = user_dao::UpdatePass::mock(
+				user_dao::UpdatePassInput {
+					id: user_id,
+					encrypted_pass: password_service::hash_sync("p4$$w0rd4").unwrap(),
+				},
+				user_dao::UpdatePassOutput { updated: true },
+			);
+			let _m3 = mock_session(user_id);
+
 			let context = UserRequestContextBuilder::build_no_auth();
 			let action_context = ActionContext {
 				action_type: super::USER_ACTION_TYPE,
@@ -154,8 +398,8 @@ mod tests {
 
 			let result = super::Action::run(Ok(RequestInput {
 				data: super::Input {
-					name: "User 02".into(),
-					pass: "p4$$w0rd2".into(),
+					login: "User 04".into(),
+					pass: "p4$$w0rd4".into(),
 				},
 				context,
 			}))
@@ -166,12 +410,25 @@ mod tests {
 				&Ok(UserOutputInfo {
 					action_context,
 					data: super::Output {
-						id: 1,
-						name: "User 02".into(),
+						id: user_id,
+						name: "User 04".into(),
+						token: token_service::generate(),
 					},
 				}),
 			);
 		})
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_invalid_credentials_message() {
+		run_test(|_| async {
+			let unknown_user = super::Error::from(ExternalException::NotFound);
+			let invalid_pass = super::Error::InvalidCredentials;
+
+			assert_eq!(&unknown_user, &invalid_pass);
+			assert_eq!(unknown_user.public_error(), invalid_pass.public_error());
+		})
+		.await;
+	}
 }
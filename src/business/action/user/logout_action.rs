@@ -1,11 +1,21 @@
-use crate::core::action::definition::action::{ActionError, UserAction};
+use action_macros::ActionError;
+
+use crate::core::action::definition::action::{ActionInput, UserAction};
 use crate::core::action::{
 	action_type::user_action_type::UserActionType,
-	data::{
-		action_data::{DescriptiveError, ErrorData},
-		user_action_data::{UserActionError, UserRequestInput},
+	data::user_action_data::{
+		UserActionError, UserAuthInputResult, UserAuthRequestInput, UserRequestInput,
 	},
 };
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
+use crate::core::external::{
+	data::external_exception::ExternalException, definition::external::ExternalAction,
+};
+use crate::external::dao::main::user_session_dao;
+use crate::shared::data::user_data::UserId;
 
 ////////////////////////////////////////////////
 ///////////////////// TYPE /////////////////////
@@ -13,33 +23,34 @@ use crate::core::action::{
 
 const USER_ACTION_TYPE: UserActionType = UserActionType::Logout;
 
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::Auth)
+			.input::<Input>()
+			.route("DELETE", "/user/session"),
+	);
+}
+
 ////////////////////////////////////////////////
-//////////////////// ERROR /////////////////////
+//////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
-pub enum Error {
-	UserError(UserActionError),
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+	pub everywhere: bool,
 }
 
-impl ActionError for Error {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match self {
-			Error::UserError(error) => error.private_error(),
-		}
-	}
+impl ActionInput for Input {}
 
-	fn public_error(&self) -> Option<ErrorData> {
-		match self {
-			Error::UserError(error) => error.public_error(),
-		}
-	}
-}
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
 
-impl From<UserActionError> for Error {
-	fn from(error: UserActionError) -> Self {
-		Self::UserError(error)
-	}
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	UserError(UserActionError),
+	ExternalError(ExternalException),
 }
 
 ////////////////////////////////////////////////
@@ -47,20 +58,29 @@ impl From<UserActionError> for Error {
 ////////////////////////////////////////////////
 
 #[derive(Debug)]
-pub struct Action(UserRequestInput<()>);
+pub struct Action(UserAuthRequestInput<Input>);
 
 #[rocket::async_trait]
-impl UserAction<(), (), Error> for Action {
+impl UserAction<Input, (), Error> for Action {
 	fn action_type() -> UserActionType {
 		USER_ACTION_TYPE
 	}
 
-	async fn new(input: UserRequestInput<()>) -> Result<Self, Error> {
-		Ok(Self(input))
+	async fn new(input: UserRequestInput<Input>) -> Result<Self, Error> {
+		UserAuthInputResult::from(input)
+			.map(Self)
+			.map_err(Error::from)
 	}
 
 	async fn run_inner(self) -> Result<(), Error> {
-		println!("TODO: logout");
+		let Self(input) = self;
+		let session = input.context.session;
+		let revoke_input = if input.data.everywhere {
+			user_session_dao::RevokeInput::ByUser(UserId(session.user_id as i64))
+		} else {
+			user_session_dao::RevokeInput::ById(session.session_id)
+		};
+		user_session_dao::Revoke::run(revoke_input).await?;
 		Ok(())
 	}
 }
@@ -70,22 +90,107 @@ impl UserAction<(), (), Error> for Action {
 ////////////////////////////////////////////////
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
+	use mockito::Mock;
+
 	use crate::core::action::data::{
-		action_data::{ActionContext, RequestInput},
-		user_action_data::{tests::UserRequestContextBuilder, UserOutputInfo},
+		action_data::{ActionContext, ActionErrorInfo, RequestInput},
+		user_action_data::{
+			tests::{UserAuthSessionBuilder, UserRequestContextBuilder},
+			UserActionError, UserOutputInfo, UserRequestContext, UserSession,
+		},
 	};
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::user_session_dao;
+	use crate::shared::data::user_data::UserId;
 	use crate::{core::action::definition::action::Action, tests::test_utils::tests::run_test};
 
+	pub fn mock_action(input: user_session_dao::RevokeInput) -> Mock {
+		user_session_dao::Revoke::mock(input, user_session_dao::RevokeOutput { revoked: 1 })
+	}
+
+	fn auth_context(session_id: i64, user_id: u64) -> UserRequestContext {
+		UserRequestContextBuilder::new()
+			.session(UserSession::Auth(
+				UserAuthSessionBuilder::new()
+					.session_id(session_id)
+					.user_id(user_id)
+					.build(),
+			))
+			.build()
+	}
+
 	#[tokio::test]
-	async fn main() {
+	async fn test_error_no_auth() {
 		run_test(|_| async {
 			let context = UserRequestContextBuilder::build_no_auth();
 			let action_context = ActionContext {
 				action_type: super::USER_ACTION_TYPE,
 				context: Some(context.clone()),
 			};
-			let result = super::Action::run(Ok(RequestInput { data: (), context })).await;
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input::default(),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::UserError(UserActionError::Unauthenticated),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let _m = mock_action(user_session_dao::RevokeInput::ById(11));
+
+			let context = auth_context(11, 3);
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input::default(),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(UserOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok_everywhere() {
+		run_test(|_| async {
+			let _m = mock_action(user_session_dao::RevokeInput::ByUser(UserId(3)));
+
+			let context = auth_context(12, 3);
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input { everywhere: true },
+				context,
+			}))
+			.await;
+
 			assert_eq!(
 				&result,
 				&Ok(UserOutputInfo {
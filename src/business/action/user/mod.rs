@@ -1,5 +1,9 @@
+pub mod confirm_email_action;
 pub mod delete_user_action;
 pub mod login_action;
 pub mod logout_action;
 pub mod register_user_action;
+pub mod request_password_reset_action;
+pub mod resend_confirmation_action;
+pub mod reset_password_action;
 pub mod select_user_action;
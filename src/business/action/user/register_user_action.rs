@@ -1,11 +1,17 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{TextRule, ValidationResult, Validator};
+use crate::core::action::{
+	data::event_data::DomainEvent,
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::{event_impl, registry_impl},
+};
 use crate::{
+	business::service::password_service::{self, PasswordError},
 	core::{
 		action::{
 			action_type::user_action_type::UserActionType,
-			data::{
-				action_data::{DescriptiveError, ErrorData},
-				user_action_data::{UserActionError, UserNoAuthRequestInput, UserRequestInput},
-			},
+			data::user_action_data::{UserActionError, UserNoAuthRequestInput, UserRequestInput},
 		},
 		external::definition::external::ExternalAction,
 	},
@@ -14,7 +20,7 @@ use crate::{
 use crate::{
 	core::{
 		action::{
-			data::user_action_data::UserNoAuthInputResult,
+			data::{action_data::ErrorCategory, user_action_data::UserNoAuthInputResult},
 			definition::action::{ActionError, ActionInput, ActionOutput, UserAction},
 		},
 		external::data::external_exception::ExternalException,
@@ -28,6 +34,16 @@ use crate::{
 
 const USER_ACTION_TYPE: UserActionType = UserActionType::Register;
 
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::NoAuth)
+			.input::<Input>()
+			.output::<Output>()
+			.route("POST", "/user"),
+	);
+}
+
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
@@ -39,16 +55,28 @@ pub struct Input {
 	pub pass: String,
 }
 
-impl ActionInput for Input {}
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		// the login is an email when it has an `@`, so the names can't have one
+		let name_rule = TextRule::Format {
+			code: "name",
+			check: |value| !value.contains('@'),
+		};
 
-impl From<Input> for user_dao::InsertInput {
-	fn from(input: Input) -> Self {
-		let Input { name, email, pass } = input;
-		user_dao::InsertInput {
-			name: name,
-			email,
-			pass,
-		}
+		Validator::new()
+			.text(
+				"name",
+				&self.name,
+				&[
+					TextRule::Required,
+					TextRule::Length { min: 3, max: 32 },
+					name_rule,
+				],
+			)
+			.text("email", &self.email, &[TextRule::Email])
+			.text("pass", &self.pass, &password_service::PASS_RULES)
+			.result()
 	}
 }
 
@@ -68,37 +96,27 @@ impl ActionOutput for Output {}
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum Error {
 	UserError(UserActionError),
+	PasswordError(PasswordError),
+	#[action_error(
+		code = "user.already_registered",
+		msg = "The name or the email is already registered.",
+		category = "Conflict"
+	)]
+	AlreadyRegistered,
+	#[action_error(skip_from)]
 	ExternalError(ExternalException),
 }
 
-impl ActionError for Error {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match self {
-			Error::UserError(error) => error.private_error(),
-			Error::ExternalError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match self {
-			Error::UserError(error) => error.public_error(),
-			Error::ExternalError(error) => error.public_error(),
-		}
-	}
-}
-
-impl From<UserActionError> for Error {
-	fn from(error: UserActionError) -> Self {
-		Self::UserError(error)
-	}
-}
-
 impl From<ExternalException> for Error {
 	fn from(error: ExternalException) -> Self {
-		Self::ExternalError(error)
+		match error.category() {
+			// the names and the emails are unique
+			ErrorCategory::Conflict => Self::AlreadyRegistered,
+			_ => Self::ExternalError(error),
+		}
 	}
 }
 
@@ -123,9 +141,16 @@ impl UserAction<Input, Output, Error> for Action {
 
 	async fn run_inner(self) -> Result<Output, Error> {
 		let Self(input) = self;
-		let name = input.data.name.to_string();
-		let user_dao::InsertOutput { id } =
-			user_dao::Insert::run(user_dao::InsertInput::from(input.data)).await?;
+		let Input { name, email, pass } = input.data;
+		let encrypted_pass = password_service::hash(pass).await?;
+		let user_dao::InsertOutput { id } = user_dao::Insert::run(user_dao::InsertInput {
+			name: name.clone(),
+			email: email.clone(),
+			encrypted_pass,
+		})
+		.await?;
+		// the confirmation is sent by its subscriber, from the outbox
+		event_impl::publish(DomainEvent::UserRegistered { user_id: id, email });
 		let result = Output { id, name };
 		Ok(result)
 	}
@@ -137,13 +162,22 @@ impl UserAction<Input, Output, Error> for Action {
 
 #[cfg(test)]
 pub mod tests {
+	use std::collections::BTreeMap;
+
+	use diesel::result::{DatabaseErrorKind, Error as DieselError};
 	use mockito::Mock;
 
-	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::business::action::mail::send_confirmation_action;
+	use crate::business::service::password_service;
+	use crate::core::action::data::action_data::{
+		ActionContext, ActionErrorInfo, ErrorCategory, RequestInput,
+	};
 	use crate::core::action::data::user_action_data::tests::UserRequestContextBuilder;
 	use crate::core::action::data::user_action_data::UserActionError;
 	use crate::core::action::data::user_action_data::UserOutputInfo;
-	use crate::core::action::definition::action::Action;
+	use crate::core::action::data::validation_data::ValidationError;
+	use crate::core::action::definition::action::{Action, ActionError};
+	use crate::core::external::data::external_exception::{DbErrorWrapper, ExternalException};
 	use crate::core::external::definition::external::tests::ExternalMocker;
 	use crate::external::dao::main::user_dao;
 	use crate::shared::data::user_data::UserId;
@@ -155,7 +189,12 @@ pub mod tests {
 	}
 
 	pub fn mock_action(input: super::Input) -> ActionMock {
-		let input = user_dao::InsertInput::from(input);
+		let super::Input { name, email, pass } = input;
+		let input = user_dao::InsertInput {
+			name,
+			email: email.clone(),
+			encrypted_pass: password_service::hash_sync(&pass).unwrap(),
+		};
 		let user_id = UserId(7);
 		let dao_result = user_dao::InsertOutput { id: user_id };
 
@@ -164,7 +203,10 @@ pub mod tests {
 			name: input.name.to_string(),
 		};
 
-		let mocks = vec![user_dao::Insert::mock(input, dao_result)];
+		let mocks = vec![
+			user_dao::Insert::mock(input, dao_result),
+			send_confirmation_action::tests::mock_queued(user_id, &email),
+		];
 
 		ActionMock { output, mocks }
 	}
@@ -198,6 +240,96 @@ pub mod tests {
 		.await;
 	}
 
+	#[tokio::test]
+	async fn test_error_invalid_input() {
+		run_test(|_| async {
+			let context = UserRequestContextBuilder::build_no_auth();
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					name: "U1".into(),
+					email: "user-01.domain.test".into(),
+					pass: "p4$$w0rd".into(),
+				},
+				context: context.clone(),
+			}))
+			.await;
+
+			let fields = BTreeMap::from([
+				("name".into(), "length".into()),
+				("email".into(), "email".into()),
+			]);
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context: ActionContext {
+						action_type: super::USER_ACTION_TYPE,
+						context: Some(context),
+					},
+					error: super::Error::UserError(UserActionError::InvalidInput(
+						ValidationError { fields }
+					)),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_error_name_email() {
+		run_test(|_| async {
+			let context = UserRequestContextBuilder::build_no_auth();
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					name: "user-01@domain.test".into(),
+					email: "user-01@domain.test".into(),
+					pass: "p4$$w0rd".into(),
+				},
+				context: context.clone(),
+			}))
+			.await;
+
+			let fields = BTreeMap::from([("name".into(), "name".into())]);
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context: ActionContext {
+						action_type: super::USER_ACTION_TYPE,
+						context: Some(context),
+					},
+					error: super::Error::UserError(UserActionError::InvalidInput(
+						ValidationError { fields }
+					)),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_error_already_registered() {
+		run_test(|_| async {
+			let error = super::Error::from(ExternalException::DbError(DbErrorWrapper(
+				DieselError::DatabaseError(
+					DatabaseErrorKind::UniqueViolation,
+					Box::new(String::from("UNIQUE constraint failed: user.email")),
+				),
+			)));
+			assert_eq!(error, super::Error::AlreadyRegistered);
+			assert_eq!(error.category(), ErrorCategory::Conflict);
+
+			let error = super::Error::from(ExternalException::NotFound);
+			assert_eq!(
+				error,
+				super::Error::ExternalError(ExternalException::NotFound)
+			);
+		})
+		.await;
+	}
+
 	#[tokio::test]
 	async fn test_ok() {
 		run_test(|_| async {
@@ -0,0 +1,174 @@
+use action_macros::ActionError;
+
+use crate::business::action::mail::send_password_reset_action as send_action;
+use crate::core::action::data::validation_data::{TextRule, ValidationResult, Validator};
+use crate::core::action::{
+	action_type::user_action_type::UserActionType,
+	data::{
+		job_data::EnqueueError,
+		user_action_data::{
+			UserActionError, UserNoAuthInputResult, UserNoAuthRequestInput, UserRequestInput,
+		},
+	},
+	definition::action::{ActionInput, UserAction},
+};
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::{job_impl, registry_impl},
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const USER_ACTION_TYPE: UserActionType = UserActionType::RequestPasswordReset;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::NoAuth)
+			.input::<Input>()
+			.route("POST", "/user/password-reset"),
+	);
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+	pub email: String,
+}
+
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		Validator::new()
+			.text("email", &self.email, &[TextRule::Email])
+			.result()
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	UserError(UserActionError),
+	EnqueueError(EnqueueError),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(UserNoAuthRequestInput<Input>);
+
+#[rocket::async_trait]
+impl UserAction<Input, (), Error> for Action {
+	fn action_type() -> UserActionType {
+		USER_ACTION_TYPE
+	}
+
+	async fn new(input: UserRequestInput<Input>) -> Result<Self, Error> {
+		UserNoAuthInputResult::from(input)
+			.map(Self)
+			.map_err(Error::from)
+	}
+
+	async fn run_inner(self) -> Result<(), Error> {
+		let Self(input) = self;
+		let Input { email } = input.data;
+
+		// the user is looked up (and mailed) by the job, so that the response,
+		// and its latency, are the same whether the email is registered or not
+		job_impl::enqueue::<send_action::Action, send_action::Input, (), send_action::Error>(
+			&send_action::Input { email },
+			0,
+		)
+		.await?;
+
+		Ok(())
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use mockito::Mock;
+
+	use crate::business::action::mail::send_password_reset_action;
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::user_action_data::tests::UserRequestContextBuilder;
+	use crate::core::action::data::user_action_data::{UserActionError, UserOutputInfo};
+	use crate::core::action::definition::action::Action;
+	use crate::tests::test_utils::tests::run_test;
+
+	pub fn mock_action(email: &str) -> Mock {
+		send_password_reset_action::tests::mock_enqueue(email)
+	}
+
+	#[tokio::test]
+	async fn test_error_auth() {
+		run_test(|_| async {
+			let context = UserRequestContextBuilder::build_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					email: "user-01@domain.test".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::UserError(UserActionError::Authenticated),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let _m = mock_action("user-02@domain.test");
+
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input {
+					email: "user-02@domain.test".into(),
+				},
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(UserOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+}
@@ -0,0 +1,241 @@
+use action_macros::ActionError;
+
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
+use chrono::Utc;
+
+use crate::{
+	business::service::confirmation_service,
+	core::{
+		action::{
+			action_type::user_action_type::UserActionType,
+			data::user_action_data::{
+				UserActionError, UserRequestInput, UserUnconfirmedInputResult,
+				UserUnconfirmedRequestInput,
+			},
+			definition::action::UserAction,
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::{user_confirmation_dao, user_dao},
+	shared::data::user_data::UserId,
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const USER_ACTION_TYPE: UserActionType = UserActionType::ResendConfirmation;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::Unconfirmed)
+			.route("POST", "/user/confirmation/resend"),
+	);
+}
+
+const RESEND_INTERVAL_SECONDS: i64 = 60;
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	UserError(UserActionError),
+	#[action_error(
+		code = "user.confirmation_resend_too_soon",
+		msg = "A confirmation email was sent recently. Please wait before requesting another one.",
+		category = "RateLimited"
+	)]
+	TooManyRequests,
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(UserUnconfirmedRequestInput<()>);
+
+#[rocket::async_trait]
+impl UserAction<(), (), Error> for Action {
+	fn action_type() -> UserActionType {
+		USER_ACTION_TYPE
+	}
+
+	async fn new(input: UserRequestInput<()>) -> Result<Self, Error> {
+		UserUnconfirmedInputResult::from(input)
+			.map(Self)
+			.map_err(Error::from)
+	}
+
+	async fn run_inner(self) -> Result<(), Error> {
+		let Self(input) = self;
+		let user_id = UserId(input.context.session.user_id as i64);
+
+		let user_confirmation_dao::SelectOutput(last) = user_confirmation_dao::Select::run(
+			user_confirmation_dao::SelectInput::LastByUser(user_id),
+		)
+		.await?;
+
+		if let Some(last) = last {
+			if last.created_at + RESEND_INTERVAL_SECONDS > Utc::now().timestamp() {
+				return Err(Error::TooManyRequests);
+			}
+		}
+
+		let user_dao::SelectOutput(user) =
+			user_dao::Select::run(user_dao::SelectInput::ById(user_id)).await?;
+
+		confirmation_service::send_confirmation(user_id, user.email).await?;
+
+		Ok(())
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use chrono::Utc;
+	use mockito::Mock;
+
+	use crate::business::service::confirmation_service;
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::user_action_data::tests::{
+		UserRequestContextBuilder, UserUnconfirmedSessionBuilder,
+	};
+	use crate::core::action::data::user_action_data::{
+		UserActionError, UserOutputInfo, UserRequestContext, UserSession,
+	};
+	use crate::core::action::definition::action::Action;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::{user_confirmation_dao, user_dao};
+	use crate::shared::data::user_data::UserId;
+	use crate::tests::test_utils::tests::run_test;
+
+	fn unconfirmed_context(user_id: u64) -> UserRequestContext {
+		UserRequestContextBuilder::new()
+			.session(UserSession::Unconfirmed(
+				UserUnconfirmedSessionBuilder::new()
+					.user_id(user_id)
+					.build(),
+			))
+			.build()
+	}
+
+	fn mock_last(user_id: UserId, sent_seconds_ago: Option<i64>) -> Mock {
+		let last = sent_seconds_ago.map(|seconds| {
+			let created_at = Utc::now().timestamp() - seconds;
+			user_confirmation_dao::UserConfirmation {
+				id: 1,
+				user_id,
+				created_at,
+				expires_at: created_at + 3600,
+				used_at: None,
+			}
+		});
+		user_confirmation_dao::Select::mock(
+			user_confirmation_dao::SelectInput::LastByUser(user_id),
+			user_confirmation_dao::SelectOutput(last),
+		)
+	}
+
+	fn mock_user(user_id: UserId, email: &str) -> Mock {
+		let UserId(id) = user_id;
+		user_dao::Select::mock(
+			user_dao::SelectInput::ById(user_id),
+			user_dao::SelectOutput(user_dao::User {
+				id,
+				name: format!("User {id:02}"),
+				email: email.into(),
+				encrypted_pass: "p4$$w0rd".into(),
+				confirmed: false,
+			}),
+		)
+	}
+
+	#[tokio::test]
+	async fn test_error_auth() {
+		run_test(|_| async {
+			let context = UserRequestContextBuilder::build_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput { data: (), context })).await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::UserError(UserActionError::Authenticated),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let user_id = UserId(6);
+			let email = "user-06@domain.test";
+			let _m1 = mock_last(user_id, Some(super::RESEND_INTERVAL_SECONDS + 10));
+			let _m2 = mock_user(user_id, email);
+			let _m3 = confirmation_service::tests::mock_confirmation(user_id, email);
+
+			let context = unconfirmed_context(6);
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput { data: (), context })).await;
+
+			assert_eq!(
+				&result,
+				&Ok(UserOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_too_many_requests() {
+		run_test(|_| async {
+			let user_id = UserId(7);
+			let _m = mock_last(user_id, Some(5));
+
+			let context = unconfirmed_context(7);
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput { data: (), context })).await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::TooManyRequests,
+				}),
+			);
+		})
+		.await;
+	}
+}
@@ -0,0 +1,219 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{TextRule, ValidationResult, Validator};
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
+use crate::{
+	business::service::password_service::{self, PasswordError},
+	core::{
+		action::{
+			action_type::user_action_type::UserActionType,
+			data::user_action_data::{
+				UserActionError, UserNoAuthInputResult, UserNoAuthRequestInput, UserRequestInput,
+			},
+			definition::action::{ActionInput, UserAction},
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::{user_password_reset_dao, user_session_dao},
+};
+
+////////////////////////////////////////////////
+///////////////////// TYPE /////////////////////
+////////////////////////////////////////////////
+
+const USER_ACTION_TYPE: UserActionType = UserActionType::ResetPassword;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::NoAuth)
+			.input::<Input>()
+			.route("PUT", "/user/password-reset"),
+	);
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+	pub token: String,
+	pub pass: String,
+}
+
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		Validator::new()
+			.text("token", &self.token, &[TextRule::Required])
+			.text("pass", &self.pass, &password_service::PASS_RULES)
+			.result()
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ActionError)]
+pub enum Error {
+	UserError(UserActionError),
+	#[action_error(
+		code = "user.password_reset_token_invalid",
+		msg = "The password reset token is invalid or has expired.",
+		category = "Validation"
+	)]
+	InvalidToken,
+	PasswordError(PasswordError),
+	ExternalError(ExternalException),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct Action(UserNoAuthRequestInput<Input>);
+
+#[rocket::async_trait]
+impl UserAction<Input, (), Error> for Action {
+	fn action_type() -> UserActionType {
+		USER_ACTION_TYPE
+	}
+
+	async fn new(input: UserRequestInput<Input>) -> Result<Self, Error> {
+		UserNoAuthInputResult::from(input)
+			.map(Self)
+			.map_err(Error::from)
+	}
+
+	async fn run_inner(self) -> Result<(), Error> {
+		let Self(input) = self;
+		let Input { token, pass } = input.data;
+
+		// hashed before the token is used, so that a hash error doesn't waste it
+		let encrypted_pass = password_service::hash(pass).await?;
+
+		let user_password_reset_dao::UseOutput(user_id) =
+			user_password_reset_dao::Use::run(user_password_reset_dao::UseInput {
+				token,
+				encrypted_pass,
+			})
+			.await?;
+		let user_id = user_id.ok_or(Error::InvalidToken)?;
+
+		user_session_dao::Revoke::run(user_session_dao::RevokeInput::ByUser(user_id)).await?;
+
+		Ok(())
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use mockito::Mock;
+
+	use crate::business::service::password_service;
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::user_action_data::tests::UserRequestContextBuilder;
+	use crate::core::action::data::user_action_data::UserOutputInfo;
+	use crate::core::action::definition::action::Action;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::{user_password_reset_dao, user_session_dao};
+	use crate::shared::data::user_data::UserId;
+	use crate::tests::test_utils::tests::run_test;
+
+	pub fn mock_action(input: super::Input, user_id: Option<UserId>) -> Vec<Mock> {
+		let super::Input { token, pass } = input;
+
+		let mut mocks = vec![user_password_reset_dao::Use::mock(
+			user_password_reset_dao::UseInput {
+				token,
+				encrypted_pass: password_service::hash_sync(&pass).unwrap(),
+			},
+			user_password_reset_dao::UseOutput(user_id),
+		)];
+
+		if let Some(user_id) = user_id {
+			mocks.push(user_session_dao::Revoke::mock(
+				user_session_dao::RevokeInput::ByUser(user_id),
+				user_session_dao::RevokeOutput { revoked: 2 },
+			));
+		}
+
+		mocks
+	}
+
+	#[tokio::test]
+	async fn test_ok() {
+		run_test(|_| async {
+			let input = super::Input {
+				token: "token-reset-01".into(),
+				pass: "n3w-p4$$w0rd".into(),
+			};
+			let _m = mock_action(input.clone(), Some(UserId(4)));
+
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: input,
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Ok(UserOutputInfo {
+					action_context,
+					data: (),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_invalid_token() {
+		run_test(|_| async {
+			let input = super::Input {
+				token: "token-reset-02".into(),
+				pass: "n3w-p4$$w0rd".into(),
+			};
+			let _m = mock_action(input.clone(), None);
+
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: input,
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::InvalidToken,
+				}),
+			);
+		})
+		.await;
+	}
+}
@@ -1,6 +1,13 @@
+use action_macros::ActionError;
+
+use crate::core::action::data::validation_data::{ValidationResult, Validator};
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::registry_impl,
+};
 use crate::{
 	core::{
-		action::definition::action::{ActionError, ActionInput, ActionOutput, UserAction},
+		action::definition::action::{ActionInput, ActionOutput, UserAction},
 		external::data::external_exception::ExternalException,
 	},
 	shared::data::user_data::UserId,
@@ -9,9 +16,8 @@ use crate::{
 	core::{
 		action::{
 			action_type::user_action_type::UserActionType,
-			data::{
-				action_data::{DescriptiveError, ErrorData},
-				user_action_data::{UserActionError, UserRequestInput},
+			data::user_action_data::{
+				UserActionError, UserAuthInputResult, UserAuthRequestInput, UserRequestInput,
 			},
 		},
 		external::definition::external::ExternalAction,
@@ -23,37 +29,56 @@ use crate::{
 ///////////////////// TYPE /////////////////////
 ////////////////////////////////////////////////
 
-const USER_ACTION_TYPE: UserActionType = UserActionType::Register;
+const USER_ACTION_TYPE: UserActionType = UserActionType::SelectUser;
+
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::Auth)
+			.input::<Input>()
+			.output::<Output>()
+			.route("GET", "/user/<id>"),
+	);
+}
 
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Input(pub UserId);
 
-impl ActionInput for Input {}
+#[rocket::async_trait]
+impl ActionInput for Input {
+	async fn validate(&self) -> ValidationResult {
+		Validator::new()
+			.range("id", self.0 .0, 1, i64::MAX)
+			.result()
+	}
+}
 
 ////////////////////////////////////////////////
 //////////////////// OUTPUT ////////////////////
 ////////////////////////////////////////////////
 
+/// The email is only returned to the user themself.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ItemOutput {
 	pub id: UserId,
 	pub name: String,
-	pub email: String,
+	pub email: Option<String>,
 }
 
-impl From<user_dao::SelectOutput> for ItemOutput {
-	fn from(data: user_dao::SelectOutput) -> Self {
+impl ItemOutput {
+	pub fn new(data: user_dao::SelectOutput, caller: UserId) -> Self {
 		let user_dao::SelectOutput(user_dao::User {
 			id, name, email, ..
 		}) = data;
+		let id = UserId(id);
 		Self {
-			id: UserId(id),
+			id,
 			name,
-			email,
+			email: (id == caller).then_some(email),
 		}
 	}
 }
@@ -71,46 +96,18 @@ impl ActionOutput for Output {}
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum Error {
 	UserError(UserActionError),
 	ExternalError(ExternalException),
 }
 
-impl ActionError for Error {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match self {
-			Error::UserError(error) => error.private_error(),
-			Error::ExternalError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match self {
-			Error::UserError(error) => error.public_error(),
-			Error::ExternalError(error) => error.public_error(),
-		}
-	}
-}
-
-impl From<UserActionError> for Error {
-	fn from(error: UserActionError) -> Self {
-		Self::UserError(error)
-	}
-}
-
-impl From<ExternalException> for Error {
-	fn from(error: ExternalException) -> Self {
-		Self::ExternalError(error)
-	}
-}
-
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
 
 #[derive(Debug)]
-pub struct Action(UserRequestInput<Input>);
+pub struct Action(UserAuthRequestInput<Input>);
 
 #[rocket::async_trait]
 impl UserAction<Input, Output, Error> for Action {
@@ -119,24 +116,24 @@ impl UserAction<Input, Output, Error> for Action {
 	}
 
 	async fn new(input: UserRequestInput<Input>) -> Result<Self, Error> {
-		Ok(Self(input))
+		UserAuthInputResult::from(input)
+			.map(Self)
+			.map_err(Error::from)
 	}
 
 	async fn run_inner(self) -> Result<Output, Error> {
 		let Self(input) = self;
+		let caller = UserId(input.context.session.user_id as i64);
 		let Input(id) = input.data;
 
-		let first = user_dao::Select::run(user_dao::SelectInput::First)
-			.await?
-			.into();
+		let first = user_dao::Select::run(user_dao::SelectInput::First).await?;
+		let first = ItemOutput::new(first, caller);
 
-		let last = user_dao::Select::run(user_dao::SelectInput::Last)
-			.await?
-			.into();
+		let last = user_dao::Select::run(user_dao::SelectInput::Last).await?;
+		let last = ItemOutput::new(last, caller);
 
-		let by_id = user_dao::Select::run(user_dao::SelectInput::ById(id))
-			.await?
-			.into();
+		let by_id = user_dao::Select::run(user_dao::SelectInput::ById(id)).await?;
+		let by_id = ItemOutput::new(by_id, caller);
 
 		let result = Output { first, last, by_id };
 		Ok(result)
@@ -149,11 +146,18 @@ impl UserAction<Input, Output, Error> for Action {
 
 #[cfg(test)]
 pub mod tests {
+	use std::collections::BTreeMap;
+
 	use mockito::Mock;
 
-	use crate::core::action::data::action_data::{ActionContext, RequestInput};
-	use crate::core::action::data::user_action_data::tests::UserRequestContextBuilder;
-	use crate::core::action::data::user_action_data::UserOutputInfo;
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, RequestInput};
+	use crate::core::action::data::user_action_data::tests::{
+		UserAuthSessionBuilder, UserRequestContextBuilder,
+	};
+	use crate::core::action::data::user_action_data::{
+		UserActionError, UserOutputInfo, UserSession,
+	};
+	use crate::core::action::data::validation_data::ValidationError;
 	use crate::core::action::definition::action::Action;
 	use crate::core::external::definition::external::tests::ExternalMocker;
 	use crate::external::dao::main::user_dao;
@@ -166,7 +170,9 @@ pub mod tests {
 		pub mocks: Vec<Mock>,
 	}
 
+	/// The user selects themself, so only their own email is returned.
 	pub fn mock_action(user_id: UserId) -> ActionMock {
+		let caller = user_id;
 		let UserId(user_id) = user_id;
 
 		let first = user_dao::SelectOutput(user_dao::User {
@@ -174,13 +180,15 @@ pub mod tests {
 			name: "User 20".into(),
 			email: "user-20@domain.test".into(),
 			encrypted_pass: "p4$$w0rd20".into(),
+			confirmed: true,
 		});
 
 		let by_id = user_dao::SelectOutput(user_dao::User {
 			id: user_id,
-			name: format!("User {user_id}").into(),
-			email: format!("user-{user_id}@domain.test").into(),
-			encrypted_pass: format!("p4$$w0rd{user_id}").into(),
+			name: format!("User {user_id}"),
+			email: format!("user-{user_id}@domain.test"),
+			encrypted_pass: format!("p4$$w0rd{user_id}"),
+			confirmed: true,
 		});
 
 		let last = user_dao::SelectOutput(user_dao::User {
@@ -188,12 +196,13 @@ pub mod tests {
 			name: "User 13".into(),
 			email: "user-13@domain.test".into(),
 			encrypted_pass: "p4$$w0rd13".into(),
+			confirmed: true,
 		});
 
 		let output = super::Output {
-			first: first.clone().into(),
-			by_id: by_id.clone().into(),
-			last: last.clone().into(),
+			first: super::ItemOutput::new(first.clone(), caller),
+			by_id: super::ItemOutput::new(by_id.clone(), caller),
+			last: super::ItemOutput::new(last.clone(), caller),
 		};
 
 		let mocks = vec![
@@ -217,8 +226,15 @@ pub mod tests {
 				output,
 				mocks: _m,
 			} = mock_action(UserId(12));
-
-			let context = UserRequestContextBuilder::build_no_auth();
+			assert_eq!(output.by_id.email, Some("user-12@domain.test".into()));
+			assert_eq!(output.first.email, None);
+			assert_eq!(output.last.email, None);
+
+			let context = UserRequestContextBuilder::new()
+				.session(UserSession::Auth(
+					UserAuthSessionBuilder::new().user_id(12).build(),
+				))
+				.build();
 			let action_context = ActionContext {
 				action_type: super::USER_ACTION_TYPE,
 				context: Some(context.clone()),
@@ -240,4 +256,92 @@ pub mod tests {
 		})
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_error_no_auth() {
+		run_test(|_| async {
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input(UserId(12)),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::UserError(UserActionError::Unauthenticated),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_error_invalid_input() {
+		run_test(|_| async {
+			let context = UserRequestContextBuilder::new()
+				.session(UserSession::Auth(
+					UserAuthSessionBuilder::new().user_id(12).build(),
+				))
+				.build();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input(UserId(0)),
+				context,
+			}))
+			.await;
+
+			let fields = BTreeMap::from([("id".into(), "range".into())]);
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::UserError(UserActionError::InvalidInput(
+						ValidationError { fields }
+					)),
+				}),
+			);
+		})
+		.await;
+	}
+
+	// the authentication is checked first, so the invalid fields aren't
+	// reported to anyone
+	#[tokio::test]
+	async fn test_error_no_auth_invalid_input() {
+		run_test(|_| async {
+			let context = UserRequestContextBuilder::build_no_auth();
+			let action_context = ActionContext {
+				action_type: super::USER_ACTION_TYPE,
+				context: Some(context.clone()),
+			};
+
+			let result = super::Action::run(Ok(RequestInput {
+				data: super::Input(UserId(0)),
+				context,
+			}))
+			.await;
+
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: super::Error::UserError(UserActionError::Unauthenticated),
+				}),
+			);
+		})
+		.await;
+	}
 }
@@ -1,3 +1,5 @@
+use action_macros::ActionError;
+
 use crate::core::action::definition::action::{
 	ActionError, ActionInput, ActionOutput, AutomaticAction, ModeratorAction, UserAction,
 };
@@ -7,12 +9,16 @@ use crate::core::action::{
 		user_action_type::UserActionType,
 	},
 	data::{
-		action_data::{DescriptiveError, ErrorData, ErrorInfo},
+		action_data::{DescriptiveError, ErrorCategory, ErrorData, ErrorInfo},
 		automatic_action_data::{AutomaticActionError, AutomaticRequestInput},
 		moderator_action_data::{ModeratorActionError, ModeratorRequestInput},
 		user_action_data::{UserActionError, UserRequestInput},
 	},
 };
+use crate::core::action::{
+	data::registry_data::{ActionAuth, ActionInfo},
+	main_impl::{metrics_impl, registry_impl},
+};
 
 ////////////////////////////////////////////////
 ///////////////////// TYPE /////////////////////
@@ -22,6 +28,25 @@ const USER_ACTION_TYPE: UserActionType = UserActionType::Web;
 const MODERATOR_ACTION_TYPE: ModeratorActionType = ModeratorActionType::Web;
 const AUTOMATIC_ACTION_TYPE: AutomaticActionType = AutomaticActionType::Web;
 
+#[ctor::ctor]
+fn register() {
+	registry_impl::register(
+		ActionInfo::new(USER_ACTION_TYPE, ActionAuth::Any)
+			.input::<Input>()
+			.output::<Output>(),
+	);
+	registry_impl::register(
+		ActionInfo::new(MODERATOR_ACTION_TYPE, ActionAuth::Moderator)
+			.input::<Input>()
+			.output::<Output>(),
+	);
+	registry_impl::register(
+		ActionInfo::new(AUTOMATIC_ACTION_TYPE, ActionAuth::Automatic)
+			.input::<Input>()
+			.output::<Output>(),
+	);
+}
+
 ////////////////////////////////////////////////
 //////////////////// STATIC ////////////////////
 ////////////////////////////////////////////////
@@ -44,7 +69,7 @@ fn httpbin_base_url() -> String {
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Input {
 	pub error: bool,
 	pub status: Option<u16>,
@@ -56,10 +81,10 @@ impl ActionInput for Input {}
 //////////////////// OUTPUT ////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct WebResultArgs {}
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
 	pub url: String,
 	pub args: WebResultArgs,
@@ -71,40 +96,12 @@ impl ActionOutput for Output {}
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum UserError {
 	UserError(UserActionError),
 	WebError(WebSharedError),
 }
 
-impl ActionError for UserError {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match &self {
-			UserError::UserError(error) => error.private_error(),
-			UserError::WebError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match &self {
-			UserError::UserError(error) => error.public_error(),
-			UserError::WebError(error) => error.public_error(),
-		}
-	}
-}
-
-impl From<UserActionError> for UserError {
-	fn from(error: UserActionError) -> Self {
-		Self::UserError(error)
-	}
-}
-
-impl From<WebSharedError> for UserError {
-	fn from(error: WebSharedError) -> Self {
-		Self::WebError(error)
-	}
-}
-
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
@@ -132,40 +129,12 @@ impl UserAction<Input, Output, UserError> for User {
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum ModeratorError {
 	ModeratorError(ModeratorActionError),
 	WebError(WebSharedError),
 }
 
-impl ActionError for ModeratorError {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match &self {
-			ModeratorError::ModeratorError(error) => error.private_error(),
-			ModeratorError::WebError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match &self {
-			ModeratorError::ModeratorError(error) => error.public_error(),
-			ModeratorError::WebError(error) => error.public_error(),
-		}
-	}
-}
-
-impl From<ModeratorActionError> for ModeratorError {
-	fn from(error: ModeratorActionError) -> Self {
-		Self::ModeratorError(error)
-	}
-}
-
-impl From<WebSharedError> for ModeratorError {
-	fn from(error: WebSharedError) -> Self {
-		Self::WebError(error)
-	}
-}
-
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
@@ -193,40 +162,12 @@ impl ModeratorAction<Input, Output, ModeratorError> for Moderator {
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ActionError)]
 pub enum AutomaticError {
 	AutomaticError(AutomaticActionError),
 	WebError(WebSharedError),
 }
 
-impl ActionError for AutomaticError {
-	fn private_error(&self) -> Option<DescriptiveError> {
-		match &self {
-			AutomaticError::AutomaticError(error) => error.private_error(),
-			AutomaticError::WebError(error) => error.private_error(),
-		}
-	}
-
-	fn public_error(&self) -> Option<ErrorData> {
-		match &self {
-			AutomaticError::AutomaticError(error) => error.public_error(),
-			AutomaticError::WebError(error) => error.public_error(),
-		}
-	}
-}
-
-impl From<AutomaticActionError> for AutomaticError {
-	fn from(error: AutomaticActionError) -> Self {
-		Self::AutomaticError(error)
-	}
-}
-
-impl From<WebSharedError> for AutomaticError {
-	fn from(error: WebSharedError) -> Self {
-		Self::WebError(error)
-	}
-}
-
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
@@ -273,7 +214,7 @@ impl ActionError for WebSharedError {
 	}
 
 	fn public_error(&self) -> Option<ErrorData> {
-		let msg = match &self {
+		let (code, msg) = match &self {
 			WebSharedError::Reqwest(info) => {
 				#[cfg(not(test))]
 				let url_data = &info.data;
@@ -283,15 +224,24 @@ impl ActionError for WebSharedError {
 
 				match url_data.status {
 					Some(status_code) => match &status_code {
-						403 => "Web Action - Forbidden".into(),
-						404 => "Web Action - Not Found".into(),
-						status => format!("Web error -> Status: {status}").into(),
+						403 => ("web.forbidden", "Web Action - Forbidden".into()),
+						404 => ("web.not_found", "Web Action - Not Found".into()),
+						status => (
+							"web.status",
+							format!("Web error -> Status: {status}").into(),
+						),
 					},
-					None => "Web error occured".into(),
+					None => ("web.error", "Web error occured".into()),
 				}
 			}
 		};
-		Self::error_msg(msg)
+		Self::error_msg(code, msg)
+	}
+
+	fn category(&self) -> ErrorCategory {
+		match &self {
+			WebSharedError::Reqwest(_) => ErrorCategory::Upstream,
+		}
 	}
 }
 
@@ -336,19 +286,22 @@ async fn run(data: &Input) -> Result<Output, WebSharedError> {
 		}
 	);
 
-	reqwest::get(&url)
-		.await
-		.and_then(|req| {
-			if data.error {
-				Ok(req)
-			} else {
-				req.error_for_status()
-			}
-		})
-		.map_err(|error| error.to_error(url.to_string()))?
-		.json::<Output>()
-		.await
-		.map_err(|error| error.to_error(url))
+	metrics_impl::external("web_action::httpbin", async {
+		reqwest::get(&url)
+			.await
+			.and_then(|req| {
+				if data.error {
+					Ok(req)
+				} else {
+					req.error_for_status()
+				}
+			})
+			.map_err(|error| error.to_error(url.to_string()))?
+			.json::<Output>()
+			.await
+			.map_err(|error| error.to_error(url.to_string()))
+	})
+	.await
 }
 
 ////////////////////////////////////////////////
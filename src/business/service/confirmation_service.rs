@@ -0,0 +1,82 @@
+use super::token_service;
+use crate::{
+	core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	},
+	external::{
+		dao::main::user_confirmation_dao,
+		mail::mailer::{self, Mail},
+	},
+	shared::data::user_data::UserId,
+};
+
+const CONFIRMATION_TTL_SECONDS: i64 = 24 * 60 * 60;
+
+fn confirmation_mail(to: String, token: &str) -> Mail {
+	Mail {
+		to,
+		subject: "Confirm your email".into(),
+		body: format!(
+			"Use the following token to confirm your email (valid for 24 hours):\n\n{token}"
+		),
+	}
+}
+
+pub async fn send_confirmation(user_id: UserId, email: String) -> Result<(), ExternalException> {
+	let token = token_service::generate();
+
+	user_confirmation_dao::Insert::run(user_confirmation_dao::InsertInput {
+		user_id,
+		token: token.clone(),
+		ttl_seconds: CONFIRMATION_TTL_SECONDS,
+	})
+	.await?;
+
+	mailer::SendMail::run(confirmation_mail(email, &token)).await
+}
+
+#[cfg(test)]
+pub mod tests {
+	use mockito::Mock;
+
+	use crate::business::service::token_service;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::{dao::main::user_confirmation_dao, mail::mailer};
+	use crate::shared::data::user_data::UserId;
+	use crate::tests::test_utils::tests::run_test;
+
+	pub fn mock_confirmation(user_id: UserId, email: &str) -> Vec<Mock> {
+		let token = token_service::generate();
+		vec![
+			user_confirmation_dao::Insert::mock(
+				user_confirmation_dao::InsertInput {
+					user_id,
+					token: token.clone(),
+					ttl_seconds: super::CONFIRMATION_TTL_SECONDS,
+				},
+				user_confirmation_dao::InsertOutput { id: 1 },
+			),
+			mailer::SendMail::mock(super::confirmation_mail(email.into(), &token), ()),
+		]
+	}
+
+	#[tokio::test]
+	async fn test_send_confirmation() {
+		run_test(|_| async {
+			let _m = mock_confirmation(UserId(3), "user-03@domain.test");
+			let result = super::send_confirmation(UserId(3), "user-03@domain.test".into()).await;
+			assert_eq!(result, Ok(()));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_confirmation_mail() {
+		run_test(|_| async {
+			let mail = super::confirmation_mail("user-04@domain.test".into(), "token-04");
+			assert_eq!(&mail.to, "user-04@domain.test");
+			assert!(mail.body.contains("token-04"));
+		})
+		.await;
+	}
+}
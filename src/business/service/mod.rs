@@ -0,0 +1,3 @@
+pub mod confirmation_service;
+pub mod password_service;
+pub mod token_service;
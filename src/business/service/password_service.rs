@@ -0,0 +1,326 @@
+use std::sync::OnceLock;
+
+use argon2::{
+	password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+	Algorithm, Argon2, Params, Version,
+};
+use rocket::fairing::AdHoc;
+
+use crate::{
+	core::action::{
+		data::{
+			action_data::{DescriptiveError, ErrorCategory, ErrorData},
+			validation_data::TextRule,
+		},
+		definition::action::ActionError,
+	},
+	lib::data::str::Str,
+};
+
+////////////////////////////////////////////////
+//////////////////// PARAMS ////////////////////
+////////////////////////////////////////////////
+
+// Hashes are stored in the PHC string format
+// (`$argon2id$v=19$m=<memory>,t=<time>,p=<parallelism>$<salt>$<hash>`),
+// so the algorithm and the cost parameters used for each password are known
+// when verifying it, and outdated hashes can be upgraded on login.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+pub struct PasswordParams {
+	pub memory_cost: u32,
+	pub time_cost: u32,
+	pub parallelism: u32,
+}
+
+impl Default for PasswordParams {
+	fn default() -> Self {
+		Self {
+			memory_cost: 19 * 1024,
+			time_cost: 2,
+			parallelism: 1,
+		}
+	}
+}
+
+static PASSWORD_PARAMS: OnceLock<PasswordParams> = OnceLock::new();
+
+// the hash verified for the unknown users, with the current params
+static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+
+const DUMMY_PASS: &str = "dummy-password";
+
+pub fn stage() -> AdHoc {
+	AdHoc::on_ignite("Password Hash Config", |rocket| async {
+		let params = rocket
+			.figment()
+			.extract_inner::<PasswordParams>("password_hash")
+			.unwrap_or_default();
+		let _ = PASSWORD_PARAMS.set(params);
+
+		// hashed before the first login, so that it doesn't take longer
+		let dummy = tokio::task::spawn_blocking(dummy_hash)
+			.await
+			.map_err(|error| PasswordError::Hash(error.to_string().into()))
+			.and_then(|hash| hash);
+		if let Err(error) = dummy {
+			error!("[password] the dummy hash could not be computed: {error:?}");
+		}
+		rocket
+	})
+}
+
+fn current_params() -> PasswordParams {
+	PASSWORD_PARAMS.get().copied().unwrap_or_default()
+}
+
+////////////////////////////////////////////////
+//////////////////// RULES /////////////////////
+////////////////////////////////////////////////
+
+pub const PASS_RULES: [TextRule; 1] = [TextRule::Length { min: 8, max: 128 }];
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum PasswordError {
+	Params(Str),
+	Hash(Str),
+	InvalidHash(Str),
+}
+
+impl ActionError for PasswordError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		let msg = match self {
+			PasswordError::Params(msg) => format!("invalid password hash params: {msg}"),
+			PasswordError::Hash(msg) => format!("password hash error: {msg}"),
+			PasswordError::InvalidHash(msg) => format!("invalid stored password hash: {msg}"),
+		};
+
+		Some(DescriptiveError {
+			msg: Some(msg.into()),
+			data: None,
+			source: None,
+		})
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		None
+	}
+
+	fn category(&self) -> ErrorCategory {
+		ErrorCategory::Internal
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum PasswordVerification {
+	Invalid,
+	Valid,
+	/// The password is correct, but was hashed with an older algorithm or
+	/// parameters, so it should be replaced by the new hash.
+	ValidOutdated(String),
+}
+
+////////////////////////////////////////////////
+////////////////// FUNCTIONS ///////////////////
+////////////////////////////////////////////////
+
+fn hasher(params: PasswordParams) -> Result<Argon2<'static>, PasswordError> {
+	let PasswordParams {
+		memory_cost,
+		time_cost,
+		parallelism,
+	} = params;
+	let params = Params::new(memory_cost, time_cost, parallelism, None)
+		.map_err(|error| PasswordError::Params(error.to_string().into()))?;
+	Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+#[cfg(not(test))]
+fn salt() -> SaltString {
+	SaltString::generate(&mut argon2::password_hash::rand_core::OsRng)
+}
+
+#[cfg(test)]
+fn salt() -> SaltString {
+	// deterministic in tests, so that the hashes can be matched by the mocks
+	SaltString::new("dGVzdHNhbHR0ZXN0c2FsdA").unwrap()
+}
+
+fn hash_with(params: PasswordParams, pass: &str) -> Result<String, PasswordError> {
+	let salt = salt();
+	let hash = hasher(params)?
+		.hash_password(pass.as_bytes(), &salt)
+		.map_err(|error| PasswordError::Hash(error.to_string().into()))?;
+	Ok(hash.to_string())
+}
+
+fn is_outdated(params: PasswordParams, hash: &PasswordHash) -> bool {
+	let current = Params::try_from(hash)
+		.ok()
+		.map(|hash_params| PasswordParams {
+			memory_cost: hash_params.m_cost(),
+			time_cost: hash_params.t_cost(),
+			parallelism: hash_params.p_cost(),
+		});
+
+	hash.algorithm != Algorithm::Argon2id.ident()
+		|| hash.version != Some(Version::V0x13.into())
+		|| current != Some(params)
+}
+
+fn verify_with(
+	params: PasswordParams,
+	pass: &str,
+	encrypted_pass: &str,
+) -> Result<PasswordVerification, PasswordError> {
+	let hash = PasswordHash::new(encrypted_pass)
+		.map_err(|error| PasswordError::InvalidHash(error.to_string().into()))?;
+
+	let algorithm = Algorithm::try_from(hash.algorithm)
+		.map_err(|error| PasswordError::InvalidHash(error.to_string().into()))?;
+
+	// the stored hash has its own params, used to verify it
+	let valid = Argon2::new(algorithm, Version::default(), Params::default())
+		.verify_password(pass.as_bytes(), &hash)
+		.is_ok();
+
+	if !valid {
+		Ok(PasswordVerification::Invalid)
+	} else if is_outdated(params, &hash) {
+		Ok(PasswordVerification::ValidOutdated(hash_with(
+			params, pass,
+		)?))
+	} else {
+		Ok(PasswordVerification::Valid)
+	}
+}
+
+fn dummy_hash() -> Result<String, PasswordError> {
+	if let Some(hash) = DUMMY_HASH.get() {
+		return Ok(hash.clone());
+	}
+	let hash = hash_with(current_params(), DUMMY_PASS)?;
+	Ok(DUMMY_HASH.get_or_init(|| hash).clone())
+}
+
+pub fn hash_sync(pass: &str) -> Result<String, PasswordError> {
+	hash_with(current_params(), pass)
+}
+
+pub async fn hash(pass: String) -> Result<String, PasswordError> {
+	tokio::task::spawn_blocking(move || hash_sync(&pass))
+		.await
+		.map_err(|error| PasswordError::Hash(error.to_string().into()))?
+}
+
+pub async fn verify(
+	pass: String,
+	encrypted_pass: String,
+) -> Result<PasswordVerification, PasswordError> {
+	tokio::task::spawn_blocking(move || verify_with(current_params(), &pass, &encrypted_pass))
+		.await
+		.map_err(|error| PasswordError::Hash(error.to_string().into()))?
+}
+
+/// Verifies the password of a user that doesn't exist, so that it takes as
+/// long as the verification of a wrong password.
+pub async fn verify_dummy(pass: String) -> Result<(), PasswordError> {
+	tokio::task::spawn_blocking(move || {
+		let hash = dummy_hash()?;
+		verify_with(current_params(), &pass, &hash).map(|_| ())
+	})
+	.await
+	.map_err(|error| PasswordError::Hash(error.to_string().into()))?
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+	use super::{PasswordError, PasswordParams, PasswordVerification};
+	use crate::tests::test_utils::tests::run_test;
+
+	fn params(memory_cost: u32) -> PasswordParams {
+		PasswordParams {
+			memory_cost,
+			time_cost: 1,
+			parallelism: 1,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_hash_format() {
+		run_test(|_| async {
+			let hash = super::hash_with(params(1024), "p4$$w0rd").unwrap();
+			assert!(hash.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+			assert!(!hash.contains("p4$$w0rd"));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_verify() {
+		run_test(|_| async {
+			let hash = super::hash_with(params(1024), "p4$$w0rd").unwrap();
+
+			assert_eq!(
+				super::verify_with(params(1024), "p4$$w0rd", &hash),
+				Ok(PasswordVerification::Valid)
+			);
+			assert_eq!(
+				super::verify_with(params(1024), "wrong", &hash),
+				Ok(PasswordVerification::Invalid)
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_verify_dummy() {
+		run_test(|_| async {
+			assert_eq!(super::verify_dummy("p4$$w0rd".into()).await, Ok(()));
+			assert_eq!(super::verify_dummy(super::DUMMY_PASS.into()).await, Ok(()));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_verify_outdated() {
+		run_test(|_| async {
+			let old_hash = super::hash_with(params(1024), "p4$$w0rd").unwrap();
+			let new_hash = super::hash_with(params(2048), "p4$$w0rd").unwrap();
+
+			assert_eq!(
+				super::verify_with(params(2048), "p4$$w0rd", &old_hash),
+				Ok(PasswordVerification::ValidOutdated(new_hash.clone()))
+			);
+			assert_eq!(
+				super::verify_with(params(2048), "p4$$w0rd", &new_hash),
+				Ok(PasswordVerification::Valid)
+			);
+			assert_eq!(
+				super::verify_with(params(2048), "wrong", &old_hash),
+				Ok(PasswordVerification::Invalid)
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_verify_invalid_hash() {
+		run_test(|_| async {
+			let result = super::verify_with(params(1024), "p4$$w0rd", "p4$$w0rd");
+			assert!(matches!(result, Err(PasswordError::InvalidHash(_))));
+		})
+		.await;
+	}
+}
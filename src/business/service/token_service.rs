@@ -0,0 +1,33 @@
+const TOKEN_BYTES: usize = 32;
+
+// Opaque tokens are only returned to the client; the database stores a hash
+// of them (see `user_session_dao`), so a leaked table can't be used to log in.
+#[cfg(not(test))]
+pub fn generate() -> String {
+	use argon2::password_hash::rand_core::{OsRng, RngCore};
+
+	let mut bytes = [0u8; TOKEN_BYTES];
+	OsRng.fill_bytes(&mut bytes);
+	hex::encode(bytes)
+}
+
+#[cfg(test)]
+pub fn generate() -> String {
+	// deterministic in tests, so that the tokens can be matched by the mocks
+	hex::encode([7u8; TOKEN_BYTES])
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_generate() {
+		run_test(|_| async {
+			let token = super::generate();
+			assert_eq!(token.len(), 2 * super::TOKEN_BYTES);
+			assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+		})
+		.await;
+	}
+}
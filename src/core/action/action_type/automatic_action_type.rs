@@ -1,49 +1,28 @@
-use super::general_action_type::{ActionScope, ActionType};
-use std::{collections::HashMap, iter::FromIterator};
-use strum::{EnumIter, IntoEnumIterator};
+use super::general_action_type::action_type;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, EnumIter)]
-pub enum AutomaticActionType {
-	Test,
-	Auto,
-	Web,
-}
+// The background actions (run from the jobs, the schedules and the event
+// outbox) don't hold a request, but they must end before their lease (60
+// seconds by default) expires, or they could run twice.
+const MAIL_TIMEOUT: u32 = 15_000;
 
-#[ctor::ctor]
-static ID_ACTION_MAP: HashMap<u32, AutomaticActionType> =
-	HashMap::from_iter(AutomaticActionType::iter().map(|item| (item.id(), item)));
+const BACKGROUND_TIMEOUT: u32 = 30_000;
 
-impl ActionType for AutomaticActionType {
-	fn scope() -> ActionScope {
-		ActionScope::Automatic
+action_type! {
+	pub enum AutomaticActionType(Automatic) {
+		Test = 0,
+		Auto = 1,
+		Web = 2,
+		SendConfirmation = 3,
+		SendPasswordReset = 4,
+		RevokeSessions = 5,
 	}
 
-	fn id(&self) -> u32 {
+	fn timeout(&self) -> Option<u32> {
 		match self {
-			AutomaticActionType::Test => 0,
-			AutomaticActionType::Auto => 1,
-			AutomaticActionType::Web => 2,
+			AutomaticActionType::Auto => Some(BACKGROUND_TIMEOUT),
+			AutomaticActionType::SendConfirmation => Some(MAIL_TIMEOUT),
+			AutomaticActionType::SendPasswordReset => Some(MAIL_TIMEOUT),
+			_ => None,
 		}
 	}
-
-	fn from_id(id: u32) -> Option<Self> {
-		ID_ACTION_MAP.get(&id).copied()
-	}
-}
-
-#[cfg(test)]
-mod tests {
-	use super::ID_ACTION_MAP;
-	use crate::{
-		core::action::action_type::general_action_type::tests::test_enum_action_type,
-		tests::test_utils::tests::run_test,
-	};
-
-	#[tokio::test]
-	async fn main() {
-		run_test(|_| async {
-			test_enum_action_type(&ID_ACTION_MAP);
-		})
-		.await;
-	}
 }
@@ -1,4 +1,11 @@
-use std::fmt::{Debug, Display, Formatter};
+use std::{
+	fmt::{Debug, Display, Formatter},
+	marker::PhantomData,
+};
+
+use serde::de::{Error, Unexpected, Visitor};
+
+use crate::{core::action::data::rate_limit_data::RateLimit, lib::data::str::Str};
 
 #[derive(Debug)]
 pub enum ActionScope {
@@ -13,25 +20,195 @@ impl Display for ActionScope {
 	}
 }
 
-pub trait ActionType: Clone + Copy + Debug + Eq + PartialEq {
+pub trait ActionType: Clone + Copy + Debug + Eq + PartialEq + Send + Sync {
 	fn scope() -> ActionScope;
 	fn id(&self) -> u32;
 	fn from_id(id: u32) -> Option<Self>;
+
+	/// The name of the action, shown to the moderators and accepted instead
+	/// of the id.
+	fn name(&self) -> Str {
+		format!("{:?}", self).into()
+	}
+
+	fn from_name(_name: &str) -> Option<Self> {
+		None
+	}
+
+	/// Overrides the application request timeout (in milliseconds) for the
+	/// action.
+	fn timeout(&self) -> Option<u32> {
+		None
+	}
+
+	/// Limits how often the action can be called for the same key.
+	fn rate_limit(&self) -> Option<RateLimit> {
+		None
+	}
+
+	/// The action isn't executed again for a request with the same
+	/// idempotency key; the first result is replayed instead.
+	fn idempotent(&self) -> bool {
+		false
+	}
 }
 
+/// Deserializes the action type from its id or from its name.
+pub struct ActionTypeVisitor<T>(PhantomData<T>);
+
+impl<T> Default for ActionTypeVisitor<T> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<'de, T: ActionType> Visitor<'de> for ActionTypeVisitor<T> {
+	type Value = T;
+
+	fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+		write!(f, "the id or the name of a {} action", T::scope())
+	}
+
+	fn visit_u64<E: Error>(self, value: u64) -> Result<T, E> {
+		u32::try_from(value)
+			.ok()
+			.and_then(T::from_id)
+			.ok_or_else(|| E::invalid_value(Unexpected::Unsigned(value), &self))
+	}
+
+	fn visit_i64<E: Error>(self, value: i64) -> Result<T, E> {
+		u32::try_from(value)
+			.ok()
+			.and_then(T::from_id)
+			.ok_or_else(|| E::invalid_value(Unexpected::Signed(value), &self))
+	}
+
+	fn visit_str<E: Error>(self, value: &str) -> Result<T, E> {
+		T::from_name(value).ok_or_else(|| E::invalid_value(Unexpected::Str(value), &self))
+	}
+}
+
+/// Declares the action types of a scope. The ids are the discriminants of the
+/// enum, so the compiler rejects an id used twice; they are stored in the
+/// audit and sent to the clients, so they must never change (see
+/// `action_type_ids.txt`). The items after the enum are added to the
+/// `ActionType` impl, to override its defaults.
+macro_rules! action_type {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $name:ident($scope:ident) {
+			$($variant:ident = $id:literal),* $(,)?
+		}
+
+		$($item:item)*
+	) => {
+		$(#[$meta])*
+		#[derive(Clone, Copy, Debug, Eq, PartialEq, strum::EnumIter)]
+		#[repr(u32)]
+		$vis enum $name {
+			$($variant = $id),*
+		}
+
+		impl $crate::core::action::action_type::general_action_type::ActionType for $name {
+			fn scope() -> $crate::core::action::action_type::general_action_type::ActionScope {
+				$crate::core::action::action_type::general_action_type::ActionScope::$scope
+			}
+
+			fn id(&self) -> u32 {
+				*self as u32
+			}
+
+			fn from_id(id: u32) -> Option<Self> {
+				match id {
+					$($id => Some(Self::$variant),)*
+					_ => None,
+				}
+			}
+
+			fn name(&self) -> $crate::lib::data::str::Str {
+				match self {
+					$(Self::$variant => stringify!($variant).into(),)*
+				}
+			}
+
+			fn from_name(name: &str) -> Option<Self> {
+				match name {
+					$(stringify!($variant) => Some(Self::$variant),)*
+					_ => None,
+				}
+			}
+
+			$($item)*
+		}
+
+		impl serde::Serialize for $name {
+			fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				use $crate::core::action::action_type::general_action_type::ActionType;
+
+				serializer.serialize_u32(self.id())
+			}
+		}
+
+		impl<'de> serde::Deserialize<'de> for $name {
+			fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+				deserializer.deserialize_any(
+					$crate::core::action::action_type::general_action_type::ActionTypeVisitor::default(),
+				)
+			}
+		}
+
+		#[cfg(test)]
+		mod tests {
+			#[tokio::test]
+			async fn main() {
+				$crate::tests::test_utils::tests::run_test(|_| async {
+					$crate::core::action::action_type::general_action_type::tests::test_enum_action_type::<
+						super::$name,
+					>();
+				})
+				.await;
+			}
+		}
+	};
+}
+
+pub(crate) use action_type;
+
 #[cfg(test)]
 pub mod tests {
-	use std::collections::HashMap;
 	use std::collections::HashSet;
 	use std::iter::FromIterator;
 
+	use rocket::serde::json::serde_json::{self, json};
+	use serde::{de::DeserializeOwned, Serialize};
 	use strum::IntoEnumIterator;
 
 	use super::ActionType;
 
-	pub fn test_enum_action_type<T: 'static + ActionType + IntoEnumIterator>(
-		id_action_map: &HashMap<u32, T>,
-	) {
+	const ACTION_TYPE_IDS: &str = include_str!("action_type_ids.txt");
+
+	// the ids assigned until now, including the ones of removed actions
+	fn snapshot(scope: &str) -> Vec<(u32, &'static str)> {
+		ACTION_TYPE_IDS
+			.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty() && !line.starts_with('#'))
+			.filter_map(
+				|line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+					[line_scope, id, name] if line_scope == scope => {
+						Some((id.parse().unwrap(), name))
+					}
+					[_, _, _] => None,
+					_ => panic!("invalid line in action_type_ids.txt: {line}"),
+				},
+			)
+			.collect()
+	}
+
+	pub fn test_enum_action_type<T>()
+	where
+		T: 'static + ActionType + IntoEnumIterator + Serialize + DeserializeOwned,
+	{
 		let unique_ids_count = HashSet::<u32>::from_iter(T::iter().map(|item| item.id())).len();
 		assert_eq!(
 			&unique_ids_count,
@@ -39,23 +216,56 @@ pub mod tests {
 			"test if all the types ids are unique"
 		);
 
-		assert_eq!(
-			&id_action_map.keys().count(),
-			&T::iter().count(),
-			"test if there is only 1 id for each type"
-		);
+		for item in T::iter() {
+			assert_eq!(
+				T::from_id(item.id()),
+				Some(item),
+				"test if the type can be retrieved from its id"
+			);
+			assert_eq!(
+				T::from_name(&item.name()),
+				Some(item),
+				"test if the type can be retrieved from its name"
+			);
 
-		let count = T::iter()
-			.filter(|item| {
-				let mapped_item = T::from_id(item.id());
+			let id = serde_json::to_value(item).unwrap();
+			assert_eq!(
+				id,
+				json!(item.id()),
+				"test if the type is serialized as its id"
+			);
+			assert_eq!(serde_json::from_value::<T>(id).unwrap(), item);
+			assert_eq!(
+				serde_json::from_value::<T>(json!(item.name())).unwrap(),
+				item,
+				"test if the type can be deserialized from its name"
+			);
+		}
 
-				mapped_item == Some(item).copied()
-			})
-			.count();
-		assert_eq!(
-			&count,
-			&T::iter().count(),
-			"test if all the types can be retrieved from their ids"
-		);
+		let scope = T::scope().to_string();
+		let snapshot = snapshot(&scope);
+
+		for item in T::iter() {
+			let (id, name) = (item.id(), item.name());
+
+			for (snapshot_id, snapshot_name) in &snapshot {
+				assert!(
+					*snapshot_id != id || *snapshot_name == name,
+					"the id {id} of {scope}::{name} was assigned to {snapshot_name}, ids can't be reused"
+				);
+				assert!(
+					*snapshot_name != name || *snapshot_id == id,
+					"{scope}::{name} was renumbered from {snapshot_id} to {id}"
+				);
+			}
+
+			assert!(
+				snapshot.contains(&(id, &name)),
+				"add `{scope} {id} {name}` to action_type_ids.txt"
+			);
+		}
+
+		assert!(serde_json::from_value::<T>(json!(u32::MAX)).is_err());
+		assert!(serde_json::from_value::<T>(json!("Unknown")).is_err());
 	}
 }
@@ -1,53 +1,63 @@
-use super::general_action_type::{ActionScope, ActionType};
-use std::{collections::HashMap, iter::FromIterator};
-use strum::{EnumIter, IntoEnumIterator};
+use super::general_action_type::action_type;
+use crate::core::action::data::rate_limit_data::{RateLimit, RateLimitKey};
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, EnumIter)]
-pub enum UserActionType {
-	Test,
-	Register,
-	Login,
-	Logout,
-	Web,
-}
+// hashing the password is deliberately slow
+const PASSWORD_TIMEOUT: u32 = 10_000;
 
-#[ctor::ctor]
-static ID_ACTION_MAP: HashMap<u32, UserActionType> =
-	HashMap::from_iter(UserActionType::iter().map(|item| (item.id(), item)));
+const HOUR: u32 = 3600;
 
-impl ActionType for UserActionType {
-	fn scope() -> ActionScope {
-		ActionScope::User
+action_type! {
+	pub enum UserActionType(User) {
+		Test = 0,
+		Register = 1,
+		Login = 2,
+		Logout = 3,
+		Web = 4,
+		ConfirmEmail = 5,
+		ResendConfirmation = 6,
+		RequestPasswordReset = 7,
+		ResetPassword = 8,
+		DeleteUser = 9,
+		SelectUser = 10,
 	}
 
-	fn id(&self) -> u32 {
+	fn timeout(&self) -> Option<u32> {
 		match self {
-			UserActionType::Test => 0,
-			UserActionType::Register => 1,
-			UserActionType::Login => 2,
-			UserActionType::Logout => 3,
-			UserActionType::Web => 4,
+			UserActionType::Register => Some(PASSWORD_TIMEOUT),
+			UserActionType::Login => Some(PASSWORD_TIMEOUT),
+			UserActionType::ResetPassword => Some(PASSWORD_TIMEOUT),
+			_ => None,
 		}
 	}
 
-	fn from_id(id: u32) -> Option<Self> {
-		ID_ACTION_MAP.get(&id).copied()
-	}
-}
+	fn rate_limit(&self) -> Option<RateLimit> {
+		let limit = |key, capacity, window| {
+			Some(RateLimit {
+				key,
+				capacity,
+				window,
+			})
+		};
 
-#[cfg(test)]
-mod tests {
-	use super::ID_ACTION_MAP;
-	use crate::{
-		core::action::action_type::general_action_type::tests::test_enum_action_type,
-		tests::test_utils::tests::run_test,
-	};
+		match self {
+			UserActionType::Register => limit(RateLimitKey::Ip, 5, HOUR),
+			UserActionType::Login => limit(RateLimitKey::Ip, 10, 60),
+			UserActionType::ResendConfirmation => limit(RateLimitKey::User, 3, HOUR),
+			UserActionType::RequestPasswordReset => limit(RateLimitKey::Ip, 5, HOUR),
+			UserActionType::ResetPassword => limit(RateLimitKey::Ip, 10, HOUR),
+			_ => None,
+		}
+	}
 
-	#[tokio::test]
-	async fn main() {
-		run_test(|_| async {
-			test_enum_action_type(&ID_ACTION_MAP);
-		})
-		.await;
+	fn idempotent(&self) -> bool {
+		matches!(
+			self,
+			UserActionType::Register
+				| UserActionType::ConfirmEmail
+				| UserActionType::ResendConfirmation
+				| UserActionType::RequestPasswordReset
+				| UserActionType::ResetPassword
+				| UserActionType::DeleteUser
+		)
 	}
 }
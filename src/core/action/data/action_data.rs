@@ -6,7 +6,7 @@ use crate::{core::action::action_type::general_action_type::ActionType, lib::dat
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-pub trait RequestContext: Clone + Debug + Eq + PartialEq {}
+pub trait RequestContext: Clone + Debug + Eq + PartialEq + Send + Sync {}
 
 #[derive(Debug)]
 pub struct RequestInput<I, C: RequestContext> {
@@ -14,9 +14,14 @@ pub struct RequestInput<I, C: RequestContext> {
 	pub data: I,
 }
 
+/// The idempotency key is sent by the clients that retry the request, so
+/// that the action isn't executed again. The request id ties the logs of the
+/// request to the response the client got.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Request {
 	pub ip: Str,
+	pub request_id: Str,
+	pub idempotency_key: Option<Str>,
 }
 
 pub trait Session: Clone + Debug + Eq + PartialEq {}
@@ -26,8 +31,20 @@ pub struct Application {
 	pub request_timeout: u32,
 }
 
+pub const DEFAULT_REQUEST_TIMEOUT: u32 = 1000;
+
+impl Default for Application {
+	fn default() -> Self {
+		Self {
+			request_timeout: DEFAULT_REQUEST_TIMEOUT,
+		}
+	}
+}
+
 pub struct AuthBasicContext {
 	pub token: Option<String>,
+	pub application: Application,
+	pub request: Request,
 }
 
 pub struct RequestBasicData<I> {
@@ -126,12 +143,38 @@ impl DescriptiveError {
 	}
 }
 
-#[derive(Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCategory {
+	Validation,
+	Unauthenticated,
+	Forbidden,
+	NotFound,
+	Conflict,
+	RateLimited,
+	Internal,
+	Upstream,
+	Timeout,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct ErrorData {
+	pub code: Str,
 	pub msg: Str,
 	pub params: Option<HashMap<Str, Str>>,
 }
 
+/// The action didn't complete in the allowed time (in milliseconds).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TimeoutError {
+	pub timeout: u32,
+}
+
+/// The action panicked, with the message of the panic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PanicError {
+	pub msg: Str,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct ActionResultInfo<T: ActionType, C: RequestContext, D> {
 	pub action_context: ActionContext<T, C>,
@@ -156,9 +199,7 @@ pub mod tests {
 	#[allow(dead_code)]
 	impl ApplicationBuilder {
 		pub fn new() -> Self {
-			Self(Application {
-				request_timeout: 1000,
-			})
+			Self(Application::default())
 		}
 
 		pub fn request_timeout(mut self, request_timeout: u32) -> Self {
@@ -177,7 +218,11 @@ pub mod tests {
 	#[allow(dead_code)]
 	impl RequestBuilder {
 		pub fn new() -> Self {
-			Self(Request { ip: "".into() })
+			Self(Request {
+				ip: "".into(),
+				request_id: "request-01".into(),
+				idempotency_key: None,
+			})
 		}
 
 		pub fn ip(mut self, ip: Str) -> Self {
@@ -185,6 +230,16 @@ pub mod tests {
 			self
 		}
 
+		pub fn request_id(mut self, request_id: Str) -> Self {
+			self.0.request_id = request_id;
+			self
+		}
+
+		pub fn idempotency_key(mut self, idempotency_key: Str) -> Self {
+			self.0.idempotency_key = Some(idempotency_key);
+			self
+		}
+
 		pub fn build(self) -> Request {
 			self.0
 		}
@@ -0,0 +1,33 @@
+use crate::lib::data::str::Str;
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AuditActor {
+	Anonymous,
+	User(u64),
+	Moderator(u64),
+	Internal,
+	Hook,
+}
+
+impl AuditActor {
+	pub fn actor_type(&self) -> Str {
+		match self {
+			AuditActor::Anonymous => "anonymous".into(),
+			AuditActor::User(_) => "user".into(),
+			AuditActor::Moderator(_) => "moderator".into(),
+			AuditActor::Internal => "internal".into(),
+			AuditActor::Hook => "hook".into(),
+		}
+	}
+
+	pub fn actor_id(&self) -> Option<i64> {
+		match self {
+			AuditActor::User(id) | AuditActor::Moderator(id) => Some(*id as i64),
+			AuditActor::Anonymous | AuditActor::Internal | AuditActor::Hook => None,
+		}
+	}
+}
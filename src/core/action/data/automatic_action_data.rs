@@ -1,9 +1,14 @@
 use crate::core::action::{
 	action_type::automatic_action_type::AutomaticActionType,
 	data::action_data::{
-		ActionErrorInfo, ActionResultInfo, Application, Request, RequestContext, RequestInput,
+		ActionErrorInfo, ActionResultInfo, Application, PanicError, Request, RequestContext,
+		RequestInput, TimeoutError,
 	},
+	data::idempotency_data::IdempotencyError,
+	data::interceptor_data::InterceptorError,
+	data::validation_data::ValidationError,
 };
+use crate::core::external::data::external_exception::ExternalException;
 use std::fmt::Debug;
 
 ////////////////////////////////////////////////
@@ -63,6 +68,12 @@ pub type AutomaticErrorInfo<E> = ActionErrorInfo<AutomaticActionType, AutomaticR
 pub enum AutomaticActionError {
 	NotInternal,
 	NotHook,
+	InvalidInput(ValidationError),
+	Timeout(TimeoutError),
+	Panic(PanicError),
+	Intercepted(Box<InterceptorError>),
+	Outbox(ExternalException),
+	Idempotency(IdempotencyError),
 }
 
 ////////////////////////////////////////////////
@@ -0,0 +1,51 @@
+use futures::future::BoxFuture;
+
+use crate::{
+	core::action::{
+		action_type::automatic_action_type::AutomaticActionType, data::action_data::Application,
+	},
+	lib::data::str::Str,
+	shared::data::user_data::UserId,
+};
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// Published by the actions from `run_inner`; the subscribers only receive
+/// the events of the actions that succeeded.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEvent {
+	UserRegistered { user_id: UserId, email: String },
+	UserDeleted { user_id: UserId },
+	SessionCreated { user_id: UserId },
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EventType {
+	UserRegistered,
+	UserDeleted,
+	SessionCreated,
+}
+
+/// The inline subscribers run right after the action that published the
+/// event, the queued ones run from the outbox, so they are retried and
+/// survive a restart.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventDelivery {
+	Inline,
+	Queued,
+}
+
+pub type EventHandler = fn(Application, DomainEvent) -> BoxFuture<'static, Result<(), Str>>;
+
+/// An automatic action that runs, as an internal request, for each event of
+/// the type.
+#[derive(Clone, Copy)]
+pub struct EventSubscription {
+	pub event_type: EventType,
+	pub delivery: EventDelivery,
+	pub action_type: AutomaticActionType,
+	pub handler: EventHandler,
+}
@@ -0,0 +1,28 @@
+use crate::{core::external::data::external_exception::ExternalException, lib::data::str::Str};
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The request is identified by the actor, the action and the key; the
+/// fingerprint is the hash of the input, to detect a key reused for another
+/// request.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdempotentRequest {
+	pub actor: String,
+	pub action: String,
+	pub key: String,
+	pub fingerprint: String,
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum IdempotencyError {
+	Conflict,
+	InProgress,
+	StoreError(ExternalException),
+	ReplayError(Str),
+}
@@ -0,0 +1,65 @@
+use std::{
+	future::Future,
+	pin::Pin,
+	sync::{Arc, RwLock},
+	time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::core::action::{
+	action_type::general_action_type::ActionType,
+	data::action_data::{
+		ActionContext, DescriptiveError, ErrorCategory, ErrorData, RequestContext,
+	},
+	definition::action_interceptor::ActionInterceptor,
+};
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The input is the redacted summary of the action input.
+#[derive(Debug)]
+pub struct ActionCall<'a, T: ActionType, C: RequestContext> {
+	pub action_context: &'a ActionContext<T, C>,
+	pub created_at: DateTime<Utc>,
+	pub input: &'a str,
+}
+
+pub type ActionFuture<'a> = Pin<Box<dyn Future<Output = ActionOutcome> + Send + 'a>>;
+
+pub struct ActionNext<'a>(pub ActionFuture<'a>);
+
+pub struct InterceptorRegistry<T: ActionType, C: RequestContext>(
+	pub RwLock<Vec<Arc<dyn ActionInterceptor<T, C>>>>,
+);
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct ActionOutcome {
+	pub duration: Duration,
+	pub error: Option<OutcomeError>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutcomeError {
+	pub category: ErrorCategory,
+	pub public: Option<ErrorData>,
+	pub private: Option<DescriptiveError>,
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+/// Returned by an interceptor to stop the action before it runs.
+#[derive(Debug, Eq, PartialEq)]
+pub struct InterceptorError {
+	pub category: ErrorCategory,
+	pub public: Option<ErrorData>,
+	pub private: Option<DescriptiveError>,
+}
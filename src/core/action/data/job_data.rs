@@ -0,0 +1,60 @@
+use futures::future::BoxFuture;
+
+use crate::{
+	core::{
+		action::{
+			action_type::automatic_action_type::AutomaticActionType, data::action_data::Application,
+		},
+		external::data::external_exception::ExternalException,
+	},
+	lib::data::str::Str,
+};
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// Runs the job input (as json) with the automatic action.
+pub type JobRunner = fn(Application, String) -> BoxFuture<'static, Result<(), JobError>>;
+
+/// An automatic action that runs, as an internal request, the jobs enqueued
+/// with its id.
+#[derive(Clone, Copy)]
+pub struct JobDefinition {
+	pub action_type: AutomaticActionType,
+	pub max_attempts: i64,
+	pub runner: JobRunner,
+}
+
+/// The worker pool, with the intervals in seconds; a job that runs longer
+/// than the lease is claimed again by another worker.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct JobConfig {
+	pub workers: u32,
+	pub poll_interval: u64,
+	pub lease: i64,
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+/// A failed job runs again (after a backoff) only when it can be retried.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobError {
+	pub retry: bool,
+	pub msg: Str,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum EnqueueError {
+	NotRegistered(AutomaticActionType),
+	InvalidInput(Str),
+	ExternalError(ExternalException),
+}
+
+impl From<ExternalException> for EnqueueError {
+	fn from(error: ExternalException) -> Self {
+		Self::ExternalError(error)
+	}
+}
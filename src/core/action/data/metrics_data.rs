@@ -0,0 +1,48 @@
+use std::{
+	collections::HashMap,
+	sync::{atomic::AtomicU64, Arc, RwLock},
+};
+
+use crate::lib::data::str::Str;
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The upper bounds (in seconds) of the buckets of the latency histograms.
+pub const DURATION_BUCKETS: [f64; 11] = [
+	0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum MetricOutcome {
+	Ok,
+	PublicError,
+	PrivateError,
+	Error,
+}
+
+/// The label names and values of a series, in the order they are rendered.
+pub type MetricLabels = Vec<(&'static str, Str)>;
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+/// The calls of a series: the total, the sum of the durations (in
+/// microseconds) and the count of each bucket (not cumulative).
+#[derive(Debug, Default)]
+pub struct MetricSeries {
+	pub count: AtomicU64,
+	pub sum_micros: AtomicU64,
+	pub buckets: [AtomicU64; DURATION_BUCKETS.len()],
+}
+
+/// The series are only written when the labels are seen for the first time,
+/// the calls are counted with atomics under the read lock.
+#[derive(Debug)]
+pub struct MetricRegistry {
+	pub name: &'static str,
+	pub help: &'static str,
+	pub series: RwLock<HashMap<MetricLabels, Arc<MetricSeries>>>,
+}
@@ -1,4 +1,14 @@
 pub mod action_data;
+pub mod audit_data;
 pub mod automatic_action_data;
+pub mod event_data;
+pub mod idempotency_data;
+pub mod interceptor_data;
+pub mod job_data;
+pub mod metrics_data;
 pub mod moderator_action_data;
+pub mod rate_limit_data;
+pub mod registry_data;
+pub mod schedule_data;
 pub mod user_action_data;
+pub mod validation_data;
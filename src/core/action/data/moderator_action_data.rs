@@ -1,9 +1,19 @@
-use crate::core::action::{
-	action_type::moderator_action_type::ModeratorActionType,
-	data::action_data::{ActionErrorInfo, ActionResultInfo, RequestContext, RequestInput},
+use crate::core::{
+	action::{
+		action_type::moderator_action_type::ModeratorActionType,
+		data::action_data::{
+			ActionErrorInfo, ActionResultInfo, PanicError, RequestContext, RequestInput,
+			TimeoutError,
+		},
+	},
+	external::data::external_exception::ExternalException,
 };
 
 use super::action_data::{Application, Request, Session};
+use super::idempotency_data::IdempotencyError;
+use super::interceptor_data::InterceptorError;
+use super::user_action_data::UserActionError;
+use super::validation_data::ValidationError;
 
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
@@ -41,7 +51,16 @@ pub type ModeratorErrorInfo<E> = ActionErrorInfo<ModeratorActionType, ModeratorR
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum ModeratorActionError {
+	UserError(UserActionError),
+	NotModerator,
+	SessionError(ExternalException),
 	NotAllowed(ModeratorActionType),
+	InvalidInput(ValidationError),
+	Timeout(TimeoutError),
+	Panic(PanicError),
+	Intercepted(Box<InterceptorError>),
+	Outbox(ExternalException),
+	Idempotency(IdempotencyError),
 }
 
 ////////////////////////////////////////////////
@@ -0,0 +1,41 @@
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// What the calls are counted by. When the key isn't available (like the user
+/// of an anonymous request), the ip of the request is used.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateLimitKey {
+	User,
+	Ip,
+	#[allow(dead_code)]
+	Moderator,
+}
+
+/// The action can be called `capacity` times in a row, then the calls are
+/// refilled evenly over the `window` (in seconds).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimit {
+	pub key: RateLimitKey,
+	pub capacity: u32,
+	pub window: u32,
+}
+
+/// The state of the token bucket of a key (`updated_at` in unix
+/// milliseconds).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitBucket {
+	pub tokens: f64,
+	pub updated_at: i64,
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+/// The key exceeded the rate limit of the action; it can retry after the
+/// amount of seconds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimitError {
+	pub retry_after: u32,
+}
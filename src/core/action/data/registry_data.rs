@@ -0,0 +1,44 @@
+use crate::lib::data::str::Str;
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The kind of request context the action accepts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionAuth {
+	Any,
+	NoAuth,
+	Unconfirmed,
+	Auth,
+	Moderator,
+	Internal,
+	Hook,
+	Automatic,
+}
+
+/// The name of the type and of its fields, as known by serde (the types of
+/// the fields aren't available).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ActionSchema {
+	pub name: Str,
+	pub fields: Vec<Str>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ActionRoute {
+	pub method: Str,
+	pub path: Str,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ActionInfo {
+	pub scope: Str,
+	pub id: u32,
+	pub name: Str,
+	pub auth: ActionAuth,
+	pub input: ActionSchema,
+	pub output: ActionSchema,
+	pub route: Option<ActionRoute>,
+}
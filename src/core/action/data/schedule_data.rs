@@ -0,0 +1,78 @@
+use crate::{
+	core::action::action_type::automatic_action_type::AutomaticActionType, lib::data::str::Str,
+};
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// A schedule of the config: the automatic action runs, with the input, on
+/// the `cron` expression or every `interval` seconds.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ScheduleConfig {
+	pub name: String,
+	pub action_id: u32,
+	pub cron: Option<String>,
+	pub interval: Option<i64>,
+	#[serde(default)]
+	pub input: serde_json::Value,
+}
+
+/// The scheduler, with the intervals in seconds; a run that takes longer
+/// than the lease may overlap with the next one.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct SchedulerConfig {
+	pub poll_interval: u64,
+	pub lease: i64,
+	#[serde(default)]
+	pub schedules: Vec<ScheduleConfig>,
+}
+
+/// The allowed values of each field, as bits: minute (0-59), hour (0-23),
+/// day of the month (1-31), month (1-12) and day of the week (0-6, from
+/// sunday). The days match like in cron: when both are restricted, either of
+/// them matches.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CronExpression {
+	pub minutes: u64,
+	pub hours: u64,
+	pub days: u64,
+	pub months: u64,
+	pub weekdays: u64,
+	pub any_day: bool,
+	pub any_weekday: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScheduleTiming {
+	Cron(String, CronExpression),
+	Interval(i64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+	pub name: String,
+	pub action_type: AutomaticActionType,
+	pub timing: ScheduleTiming,
+	pub input: String,
+}
+
+/// The validated config, managed by the rocket.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scheduler {
+	pub poll_interval: u64,
+	pub lease: i64,
+	pub schedules: Vec<Schedule>,
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ScheduleError {
+	InvalidTiming(String),
+	InvalidCron(String, Str),
+	UnknownAction(String, u32),
+	DuplicateName(String),
+}
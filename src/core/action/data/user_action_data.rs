@@ -1,9 +1,18 @@
 use chrono::{DateTime, Utc};
 
 use super::action_data::{Application, Request, Session};
-use crate::core::action::{
-	action_type::user_action_type::UserActionType,
-	data::action_data::{ActionErrorInfo, ActionResultInfo, RequestContext, RequestInput},
+use super::idempotency_data::IdempotencyError;
+use super::interceptor_data::InterceptorError;
+use super::validation_data::ValidationError;
+use crate::core::{
+	action::{
+		action_type::user_action_type::UserActionType,
+		data::action_data::{
+			ActionErrorInfo, ActionResultInfo, PanicError, RequestContext, RequestInput,
+			TimeoutError,
+		},
+	},
+	external::data::external_exception::ExternalException,
 };
 
 ////////////////////////////////////////////////
@@ -21,12 +30,14 @@ impl Session for UserSession {}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UserAuthSession {
+	pub session_id: i64,
 	pub created_at: DateTime<Utc>,
 	pub user_id: u64,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UserUnconfirmedSession {
+	pub session_id: i64,
 	pub created_at: DateTime<Utc>,
 	pub user_id: u64,
 }
@@ -105,6 +116,16 @@ pub type UserErrorInfo<E> = ActionErrorInfo<UserActionType, UserRequestContext,
 pub enum UserActionError {
 	Authenticated,
 	Unauthenticated,
+	Forbidden,
+	InvalidToken,
+	ExpiredToken,
+	SessionError(ExternalException),
+	InvalidInput(ValidationError),
+	Timeout(TimeoutError),
+	Panic(PanicError),
+	Intercepted(Box<InterceptorError>),
+	Idempotency(IdempotencyError),
+	Outbox(ExternalException),
 }
 
 ////////////////////////////////////////////////
@@ -154,11 +175,17 @@ pub mod tests {
 	impl UserAuthSessionBuilder {
 		pub fn new() -> Self {
 			Self(UserAuthSession {
+				session_id: 0,
 				created_at: Utc::now(),
 				user_id: 0,
 			})
 		}
 
+		pub fn session_id(mut self, session_id: i64) -> Self {
+			self.0.session_id = session_id;
+			self
+		}
+
 		pub fn created_at(mut self, created_at: DateTime<Utc>) -> Self {
 			self.0.created_at = created_at;
 			self
@@ -181,11 +208,17 @@ pub mod tests {
 	impl UserUnconfirmedSessionBuilder {
 		pub fn new() -> Self {
 			Self(UserUnconfirmedSession {
+				session_id: 0,
 				created_at: Utc::now(),
 				user_id: 0,
 			})
 		}
 
+		pub fn session_id(mut self, session_id: i64) -> Self {
+			self.0.session_id = session_id;
+			self
+		}
+
 		pub fn created_at(mut self, created_at: DateTime<Utc>) -> Self {
 			self.0.created_at = created_at;
 			self
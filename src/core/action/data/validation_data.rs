@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+
+use crate::lib::data::str::Str;
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Clone, Copy, Debug)]
+pub enum TextRule {
+	Required,
+	Length {
+		min: usize,
+		max: usize,
+	},
+	Email,
+	Format {
+		code: &'static str,
+		check: fn(&str) -> bool,
+	},
+}
+
+#[derive(Debug, Default)]
+pub struct Validator {
+	pub errors: BTreeMap<Str, Str>,
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+pub type ValidationResult = Result<(), ValidationError>;
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError {
+	pub fields: BTreeMap<Str, Str>,
+}
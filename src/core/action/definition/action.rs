@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use serde::{de::DeserializeOwned, Serialize};
+
 use crate::{
 	core::action::{
 		action_type::{
@@ -7,7 +9,7 @@ use crate::{
 			user_action_type::UserActionType,
 		},
 		data::{
-			action_data::{DescriptiveError, ErrorData},
+			action_data::{DescriptiveError, ErrorCategory, ErrorData},
 			automatic_action_data::{
 				AutomaticActionError, AutomaticActionInput, AutomaticErrorInfo,
 				AutomaticOutputInfo, AutomaticRequestInput,
@@ -19,6 +21,7 @@ use crate::{
 			user_action_data::{
 				UserActionError, UserActionInput, UserErrorInfo, UserOutputInfo, UserRequestInput,
 			},
+			validation_data::ValidationResult,
 		},
 	},
 	lib::data::str::Str,
@@ -28,7 +31,12 @@ use crate::{
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-pub trait ActionInput: Debug + Send {}
+#[rocket::async_trait]
+pub trait ActionInput: Debug + Serialize + Send + Sync {
+	async fn validate(&self) -> ValidationResult {
+		Ok(())
+	}
+}
 
 impl ActionInput for () {}
 
@@ -36,7 +44,9 @@ impl ActionInput for () {}
 //////////////////// OUTPUT ////////////////////
 ////////////////////////////////////////////////
 
-pub trait ActionOutput: Debug {}
+/// The output is serializable, so that it can be replayed (see the
+/// idempotency keys).
+pub trait ActionOutput: Debug + Send + Serialize + DeserializeOwned {}
 
 impl ActionOutput for () {}
 
@@ -49,8 +59,14 @@ pub trait ActionError: Debug + Send {
 
 	fn public_error(&self) -> Option<ErrorData>;
 
-	fn error_msg(msg: Str) -> Option<ErrorData> {
-		Some(ErrorData { msg, params: None })
+	fn category(&self) -> ErrorCategory;
+
+	fn error_msg(code: &'static str, msg: Str) -> Option<ErrorData> {
+		Some(ErrorData {
+			code: code.into(),
+			msg,
+			params: None,
+		})
 	}
 }
 
@@ -1,9 +1,18 @@
 use std::{borrow::Cow, fmt::Debug};
 
 use crate::{
-	core::action::{
-		action_type::general_action_type::ActionType,
-		data::action_data::{ErrorData, RequestContext},
+	core::{
+		action::{
+			action_type::general_action_type::ActionType,
+			data::{
+				action_data::{Application, ErrorData, PanicError, RequestContext, TimeoutError},
+				audit_data::AuditActor,
+				idempotency_data::IdempotencyError,
+				interceptor_data::InterceptorError,
+				validation_data::ValidationError,
+			},
+		},
+		external::data::external_exception::ExternalException,
 	},
 	lib::data::str::Str,
 };
@@ -14,6 +23,12 @@ pub trait DescriptiveInfo {
 	fn description(&self) -> Cow<'_, str>;
 }
 
+pub trait AuditInfo {
+	fn actor(&self) -> AuditActor;
+
+	fn ip(&self) -> Option<Str>;
+}
+
 pub trait DescriptiveRequestContext:
 	Debug + Eq + PartialEq + Clone + DescriptiveInfo + RequestContext
 {
@@ -32,3 +47,26 @@ where
 
 	fn handle(self) -> Option<ErrorData>;
 }
+
+/// The parts of the request context used by the layers around the action.
+pub trait PipelineInfo {
+	fn application(&self) -> &Application;
+
+	/// Sent by the clients that retry the request, see `ActionType::idempotent`.
+	fn idempotency_key(&self) -> Option<&Str>;
+}
+
+/// The errors of the layers around the action, as the error of its scope.
+pub trait PipelineError {
+	fn invalid_input(error: ValidationError) -> Self;
+
+	fn timeout(error: TimeoutError) -> Self;
+
+	fn panic(error: PanicError) -> Self;
+
+	fn outbox(error: ExternalException) -> Self;
+
+	fn idempotency(error: IdempotencyError) -> Self;
+
+	fn intercepted(error: InterceptorError) -> Self;
+}
@@ -0,0 +1,25 @@
+use crate::core::action::{
+	action_type::general_action_type::ActionType,
+	data::{
+		action_data::RequestContext,
+		interceptor_data::{ActionCall, ActionNext, ActionOutcome, InterceptorError},
+	},
+};
+
+/// Cross-cutting logic around the execution of the actions of a scope. The
+/// `before` hooks run in the order of registration, then the `around` hooks
+/// wrap the action (the first registered is the outermost), and the `after`
+/// hooks run in the reverse order, also when the action failed or was
+/// stopped.
+#[rocket::async_trait]
+pub trait ActionInterceptor<T: ActionType, C: RequestContext>: Send + Sync {
+	async fn before(&self, _call: &ActionCall<'_, T, C>) -> Result<(), InterceptorError> {
+		Ok(())
+	}
+
+	async fn around(&self, _call: &ActionCall<'_, T, C>, next: ActionNext<'_>) -> ActionOutcome {
+		next.run().await
+	}
+
+	async fn after(&self, _call: &ActionCall<'_, T, C>, _outcome: &ActionOutcome) {}
+}
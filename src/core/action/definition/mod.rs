@@ -1,2 +1,3 @@
 pub mod action;
 pub mod action_helpers;
+pub mod action_interceptor;
@@ -1,14 +1,16 @@
 use core::fmt;
-use std::fmt::Debug;
+use std::{borrow::Cow, fmt::Debug};
 
 use crate::core::action::{
 	action_type::general_action_type::ActionType,
-	data::action_data::{ActionErrorInfo, ErrorData, ErrorInfo, RequestContext, RequestInput},
+	data::action_data::{
+		ActionErrorInfo, ErrorData, ErrorInfo, Request, RequestContext, RequestInput,
+	},
 };
 use crate::{
 	core::action::definition::{
 		action::ActionError,
-		action_helpers::{ActionErrorHelper, DescriptiveRequestContext},
+		action_helpers::{ActionErrorHelper, DescriptiveInfo, DescriptiveRequestContext},
 	},
 	lib::data::str::Str,
 };
@@ -23,6 +25,12 @@ impl<I, E, C: RequestContext> From<RequestInput<I, C>> for Result<RequestInput<I
 	}
 }
 
+impl DescriptiveInfo for Request {
+	fn description(&self) -> Cow<'_, str> {
+		format!("request({request_id})", request_id = self.request_id).into()
+	}
+}
+
 ////////////////////////////////////////////////
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
@@ -95,7 +103,7 @@ impl<T: ActionType, C: DescriptiveRequestContext, E: ActionError> ActionErrorHel
 //////////////////// ACTION ////////////////////
 ////////////////////////////////////////////////
 
-struct ActionTypeWrapper<T: ActionType>(T);
+pub struct ActionTypeWrapper<T: ActionType>(pub T);
 
 impl<T: ActionType> fmt::Display for ActionTypeWrapper<T> {
 	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
@@ -115,7 +123,7 @@ mod tests {
 
 	use crate::core::action::action_type::general_action_type::ActionType;
 	use crate::core::action::data::action_data::{
-		ActionContext, ActionErrorInfo, DescriptiveError, ErrorData,
+		ActionContext, ActionErrorInfo, DescriptiveError, ErrorCategory, ErrorData,
 	};
 	use crate::core::action::definition::action::ActionError;
 	use crate::core::action::definition::action_helpers::ActionErrorHelper;
@@ -169,13 +177,18 @@ mod tests {
 			let action_id = self.0.id();
 
 			match action_id {
-				1..=3 => {
-					Self::error_msg(format!("Test public error (action_id={action_id})").into())
-				}
+				1..=3 => Self::error_msg(
+					"test.error",
+					format!("Test public error (action_id={action_id})").into(),
+				),
 				4 | 5 => None,
 				_ => panic!(),
 			}
 		}
+
+		fn category(&self) -> ErrorCategory {
+			ErrorCategory::Internal
+		}
 	}
 
 	impl ActionType for TestActionType {
@@ -213,6 +226,7 @@ mod tests {
 			assert_eq!(
 				&public_error,
 				&Some(ErrorData {
+					code: "test.error".into(),
 					msg: "Test public error (action_id=1)".into(),
 					params: None
 				})
@@ -243,6 +257,7 @@ mod tests {
 			assert_eq!(
 				&public_error,
 				&Some(ErrorData {
+					code: "test.error".into(),
 					msg: "Test public error (action_id=2)".into(),
 					params: None
 				})
@@ -285,6 +300,7 @@ mod tests {
 			assert_eq!(
 				&public_error,
 				&Some(ErrorData {
+					code: "test.error".into(),
 					msg: "Test public error (action_id=3)".into(),
 					params: None
 				})
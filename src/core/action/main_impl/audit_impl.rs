@@ -0,0 +1,381 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+	core::action::{
+		action_type::general_action_type::ActionType,
+		data::{
+			action_data::RequestContext,
+			audit_data::AuditActor,
+			interceptor_data::{ActionCall, ActionOutcome, OutcomeError},
+		},
+		definition::{action_helpers::AuditInfo, action_interceptor::ActionInterceptor},
+	},
+	core::external::definition::external::ExternalAction,
+	external::dao::main::action_audit_dao,
+};
+
+const REDACTED: &str = "***";
+
+const SENSITIVE_FIELDS: [&str; 4] = ["pass", "password", "token", "secret"];
+
+const MAX_INPUT_LEN: usize = 1024;
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+// the name of the field as serialized, so it may be renamed or camel case
+fn is_sensitive(field: &str) -> bool {
+	let field = field.to_lowercase().replace(['_', '-'], "");
+	SENSITIVE_FIELDS.iter().any(|name| field.ends_with(name))
+}
+
+/// Replaces the values of the sensitive fields (passwords, tokens and
+/// secrets), at any depth, whatever their own structure.
+pub fn redact(value: Value) -> Value {
+	match value {
+		Value::Object(fields) => Value::Object(
+			fields
+				.into_iter()
+				.map(|(field, value)| match is_sensitive(&field) {
+					true => (field, Value::String(REDACTED.into())),
+					false => (field, redact(value)),
+				})
+				.collect(),
+		),
+		Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+		value => value,
+	}
+}
+
+/// The input as json, with the sensitive fields redacted.
+pub fn summary<I: Serialize>(input: &I) -> String {
+	let summary = match serde_json::to_value(input) {
+		Ok(value) => redact(value).to_string(),
+		Err(error) => format!("<not serializable: {error}>"),
+	};
+
+	match summary.char_indices().nth(MAX_INPUT_LEN) {
+		Some((index, _)) => format!("{}...", &summary[..index]),
+		None => summary,
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+fn error_code(error: &OutcomeError) -> String {
+	error
+		.public
+		.as_ref()
+		.map(|data| data.code.to_string())
+		.unwrap_or_else(|| format!("{:?}", error.category).to_lowercase())
+}
+
+pub fn entry<T, C>(
+	call: &ActionCall<'_, T, C>,
+	outcome: &ActionOutcome,
+) -> action_audit_dao::InsertInput
+where
+	T: ActionType,
+	C: RequestContext + AuditInfo,
+{
+	let action_context = call.action_context;
+	let context = action_context.context.as_ref();
+	let actor = context
+		.map(AuditInfo::actor)
+		.unwrap_or(AuditActor::Anonymous);
+
+	action_audit_dao::InsertInput {
+		scope: T::scope().to_string(),
+		action_id: action_context.action_type.id().into(),
+		actor_type: actor.actor_type().into_owned(),
+		actor_id: actor.actor_id(),
+		ip: context.and_then(AuditInfo::ip).map(String::from),
+		created_at: call.created_at.timestamp(),
+		duration_ms: outcome.duration.as_millis() as i64,
+		success: outcome.error.is_none(),
+		error_code: outcome.error.as_ref().map(error_code),
+		input: call.input.into(),
+	}
+}
+
+pub struct AuditInterceptor;
+
+#[rocket::async_trait]
+impl<T, C> ActionInterceptor<T, C> for AuditInterceptor
+where
+	T: ActionType,
+	C: RequestContext + AuditInfo,
+{
+	async fn after(&self, call: &ActionCall<'_, T, C>, outcome: &ActionOutcome) {
+		record(entry(call, outcome)).await;
+	}
+}
+
+// a failure to write the audit record doesn't change the result of the action
+pub async fn record(entry: action_audit_dao::InsertInput) {
+	if let Err(error) = action_audit_dao::Insert::run(entry).await {
+		error!("[audit] the action execution could not be recorded: {error:?}");
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::time::Duration;
+
+	use chrono::{TimeZone, Utc};
+	use mockito::{Matcher, Mock};
+	use serde::Serialize;
+
+	use crate::core::action::action_type::general_action_type::{ActionScope, ActionType};
+	use crate::core::action::action_type::user_action_type::UserActionType;
+	use crate::core::action::data::action_data::tests::RequestBuilder;
+	use crate::core::action::data::action_data::RequestInput;
+	use crate::core::action::data::action_data::{ActionContext, ErrorCategory, RequestContext};
+	use crate::core::action::data::audit_data::AuditActor;
+	use crate::core::action::data::interceptor_data::{ActionCall, ActionOutcome, OutcomeError};
+	use crate::core::action::data::user_action_data::tests::{
+		UserAuthSessionBuilder, UserRequestContextBuilder,
+	};
+	use crate::core::action::data::user_action_data::UserRequestInput;
+	use crate::core::action::data::user_action_data::{UserActionError, UserSession};
+	use crate::core::action::definition::action::{Action, UserAction};
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::action_audit_dao;
+	use crate::lib::data::str::Str;
+	use crate::tests::test_utils::tests::run_test;
+
+	/// The audit record of any action, mocked for the whole run; the tests that
+	/// check a record mock it again.
+	pub fn mock_any() -> Mock {
+		action_audit_dao::Insert::mock_fallback(action_audit_dao::InsertOutput { id: 1 })
+	}
+
+	#[derive(Clone, Debug, Eq, PartialEq)]
+	struct TestContext;
+
+	impl RequestContext for TestContext {}
+
+	impl super::AuditInfo for TestContext {
+		fn actor(&self) -> AuditActor {
+			AuditActor::Moderator(3)
+		}
+
+		fn ip(&self) -> Option<Str> {
+			Some("10.0.0.3".into())
+		}
+	}
+
+	#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+	struct TestActionType;
+
+	impl ActionType for TestActionType {
+		fn scope() -> ActionScope {
+			ActionScope::Moderator
+		}
+
+		fn id(&self) -> u32 {
+			9
+		}
+
+		fn from_id(_: u32) -> Option<Self> {
+			Some(Self)
+		}
+	}
+
+	#[derive(Debug)]
+	struct TestUserAction;
+
+	#[rocket::async_trait]
+	impl UserAction<(), (), UserActionError> for TestUserAction {
+		fn action_type() -> UserActionType {
+			UserActionType::Test
+		}
+
+		async fn new(_: UserRequestInput<()>) -> Result<Self, UserActionError> {
+			Ok(Self)
+		}
+
+		async fn run_inner(self) -> Result<(), UserActionError> {
+			Ok(())
+		}
+	}
+
+	#[tokio::test]
+	async fn test_redact() {
+		run_test(|_| async {
+			#[derive(Serialize)]
+			struct Nested {
+				login: String,
+				new_pass: String,
+			}
+
+			#[derive(Serialize)]
+			struct Token(String);
+
+			#[derive(Serialize)]
+			#[serde(rename_all = "camelCase")]
+			struct Renamed {
+				old_password: String,
+				#[serde(rename = "apiSecret")]
+				key: (u8, u8),
+			}
+
+			#[derive(Serialize)]
+			struct Input {
+				name: String,
+				pass: String,
+				token: Option<Token>,
+				nested: Vec<Nested>,
+				renamed: Renamed,
+				id: u64,
+			}
+
+			let input = Input {
+				name: "pass: \"x\"".into(),
+				pass: "p4$$, \"w0rd\" }".into(),
+				token: Some(Token("abc".into())),
+				nested: vec![Nested {
+					login: "user-01".into(),
+					new_pass: "n3w".into(),
+				}],
+				renamed: Renamed {
+					old_password: "0ld".into(),
+					key: (1, 2),
+				},
+				id: 7,
+			};
+
+			assert_eq!(
+				super::summary(&input),
+				concat!(
+					r#"{"id":7,"name":"pass: \"x\"","#,
+					r#""nested":[{"login":"user-01","new_pass":"***"}],"pass":"***","#,
+					r#""renamed":{"apiSecret":"***","oldPassword":"***"},"token":"***"}"#,
+				)
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_summary_max_len() {
+		run_test(|_| async {
+			let input = "a".repeat(2000);
+			let summary = super::summary(&input);
+
+			assert_eq!(summary.len(), super::MAX_INPUT_LEN + 3);
+			assert!(summary.ends_with("aaa..."));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_entry_error() {
+		run_test(|_| async {
+			let action_context = ActionContext {
+				action_type: TestActionType,
+				context: Some(TestContext),
+			};
+			let call = ActionCall {
+				action_context: &action_context,
+				created_at: Utc.timestamp(1_650_000_000, 0),
+				input: "()",
+			};
+			let outcome = ActionOutcome {
+				duration: Duration::from_millis(12),
+				error: Some(OutcomeError {
+					category: ErrorCategory::Upstream,
+					public: None,
+					private: None,
+				}),
+			};
+
+			let entry = super::entry(&call, &outcome);
+
+			assert_eq!(
+				entry,
+				action_audit_dao::InsertInput {
+					scope: "Moderator".into(),
+					action_id: 9,
+					actor_type: "moderator".into(),
+					actor_id: Some(3),
+					ip: Some("10.0.0.3".into()),
+					created_at: 1_650_000_000,
+					duration_ms: 12,
+					success: false,
+					error_code: Some("upstream".into()),
+					input: "()".into(),
+				}
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_record() {
+		run_test(|_| async {
+			let entry = action_audit_dao::InsertInput {
+				scope: "Moderator".into(),
+				action_id: 9,
+				actor_type: "moderator".into(),
+				actor_id: Some(3),
+				ip: Some("10.0.0.3".into()),
+				created_at: 1_650_000_000,
+				duration_ms: 12,
+				success: false,
+				error_code: Some("upstream".into()),
+				input: "()".into(),
+			};
+			let m = action_audit_dao::Insert::mock(
+				entry.clone(),
+				action_audit_dao::InsertOutput { id: 2 },
+			)
+			.expect(1);
+
+			super::record(entry).await;
+
+			m.assert();
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_record_user_action() {
+		run_test(|_| async {
+			// the time of the call and its duration aren't known
+			let m = action_audit_dao::Insert::mock_matching(
+				Matcher::Regex(
+					concat!(
+						r#"^\{"scope":"User","action_id":0,"actor_type":"user","actor_id":5,"#,
+						r#""ip":"10\.0\.5\.5","created_at":\d+,"duration_ms":\d+,"#,
+						r#""success":true,"error_code":null,"input":"null"\}$"#,
+					)
+					.into(),
+				),
+				action_audit_dao::InsertOutput { id: 3 },
+			)
+			.expect(1);
+
+			let context = UserRequestContextBuilder::new()
+				.request(RequestBuilder::new().ip("10.0.5.5".into()).build())
+				.session(UserSession::Auth(
+					UserAuthSessionBuilder::new().user_id(5).build(),
+				))
+				.build();
+
+			let result = TestUserAction::run(Ok(RequestInput { data: (), context })).await;
+			assert!(result.is_ok());
+
+			m.assert();
+		})
+		.await;
+	}
+}
@@ -1,35 +1,46 @@
 use std::borrow::Cow;
 
+use super::{interceptor_impl::AUTOMATIC_INTERCEPTORS, pipeline_impl};
+
 use crate::core::action::{
-	data::automatic_action_data::{
-		AutomaticActionInput, AutomaticRequestInput, HookInputResult, HookRequestInput,
-		InternalInputResult, InternalRequestInput,
-	},
-	definition::action::{Action, ActionError, AutomaticAction},
-	definition::action::{ActionInput, ActionOutput},
-};
-use crate::core::action::{
+	data::audit_data::AuditActor,
 	data::{
-		action_data::{ActionContext, DescriptiveError, ErrorData, RequestInput},
+		action_data::{
+			Application, DescriptiveError, ErrorCategory, ErrorData, PanicError, RequestInput,
+			TimeoutError,
+		},
 		automatic_action_data::{
 			AutomaticActionError, AutomaticErrorInfo, AutomaticOutputInfo, AutomaticRequest,
 			AutomaticRequestContext, HookRequestContext, InternalRequestContext,
 		},
+		idempotency_data::IdempotencyError,
+		interceptor_data::InterceptorError,
+		validation_data::ValidationError,
+	},
+	definition::action_helpers::{AuditInfo, DescriptiveInfo, PipelineError, PipelineInfo},
+};
+use crate::core::action::{
+	data::automatic_action_data::{
+		AutomaticActionInput, AutomaticRequestInput, HookInputResult, HookRequestInput,
+		InternalInputResult, InternalRequestInput,
 	},
-	definition::action_helpers::DescriptiveInfo,
+	definition::action::{Action, ActionError, AutomaticAction},
+	definition::action::{ActionInput, ActionOutput},
 };
+use crate::core::external::data::external_exception::ExternalException;
+use crate::lib::{data::str::Str, traits::async_from::AsyncFrom};
 
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-impl<I: ActionInput> ActionInput for RequestInput<I, AutomaticRequestContext> {}
-
 impl DescriptiveInfo for AutomaticRequest {
 	fn description(&self) -> Cow<'_, str> {
 		match self {
 			AutomaticRequest::Internal => "automatic(internal)".into(),
-			AutomaticRequest::Hook(_) => "automatic(hook)".into(),
+			AutomaticRequest::Hook(request) => {
+				format!("automatic(hook) {request}", request = request.description()).into()
+			}
 		}
 	}
 }
@@ -41,6 +52,35 @@ impl DescriptiveInfo for AutomaticRequestContext {
 	}
 }
 
+impl PipelineInfo for AutomaticRequestContext {
+	fn application(&self) -> &Application {
+		&self.application
+	}
+
+	fn idempotency_key(&self) -> Option<&Str> {
+		match &self.request {
+			AutomaticRequest::Internal => None,
+			AutomaticRequest::Hook(request) => request.idempotency_key.as_ref(),
+		}
+	}
+}
+
+impl AuditInfo for AutomaticRequestContext {
+	fn actor(&self) -> AuditActor {
+		match &self.request {
+			AutomaticRequest::Internal => AuditActor::Internal,
+			AutomaticRequest::Hook(_) => AuditActor::Hook,
+		}
+	}
+
+	fn ip(&self) -> Option<Str> {
+		match &self.request {
+			AutomaticRequest::Internal => None,
+			AutomaticRequest::Hook(request) => Some(request.ip.clone()),
+		}
+	}
+}
+
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
@@ -147,6 +187,14 @@ impl<T> From<HookRequestInput<T>> for AutomaticRequestInput<T> {
 	}
 }
 
+// the hooks are authenticated by the route, before the action is called
+#[rocket::async_trait]
+impl<I: Send> AsyncFrom<AutomaticRequestInput<I>> for AutomaticActionInput<I> {
+	async fn from(input: AutomaticRequestInput<I>) -> Self {
+		Ok(input)
+	}
+}
+
 ////////////////////////////////////////////////
 //////////////////// ERROR /////////////////////
 ////////////////////////////////////////////////
@@ -156,19 +204,73 @@ impl ActionError for AutomaticActionError {
 		match self {
 			AutomaticActionError::NotInternal => None,
 			AutomaticActionError::NotHook => None,
+			AutomaticActionError::InvalidInput(error) => error.private_error(),
+			AutomaticActionError::Timeout(error) => error.private_error(),
+			AutomaticActionError::Panic(error) => error.private_error(),
+			AutomaticActionError::Intercepted(error) => error.private_error(),
+			AutomaticActionError::Outbox(error) => error.private_error(),
+			AutomaticActionError::Idempotency(error) => error.private_error(),
 		}
 	}
 
 	fn public_error(&self) -> Option<ErrorData> {
 		match self {
-			AutomaticActionError::NotInternal => {
-				Self::error_msg("This is not an internal action.".into())
+			AutomaticActionError::NotInternal => Self::error_msg(
+				"automatic.not_internal",
+				"This is not an internal action.".into(),
+			),
+			AutomaticActionError::NotHook => {
+				Self::error_msg("automatic.not_hook", "This is not a hook action.".into())
 			}
-			AutomaticActionError::NotHook => Self::error_msg("This is not a hook action.".into()),
+			AutomaticActionError::InvalidInput(error) => error.public_error(),
+			AutomaticActionError::Timeout(error) => error.public_error(),
+			AutomaticActionError::Panic(error) => error.public_error(),
+			AutomaticActionError::Intercepted(error) => error.public_error(),
+			AutomaticActionError::Outbox(error) => error.public_error(),
+			AutomaticActionError::Idempotency(error) => error.public_error(),
+		}
+	}
+
+	fn category(&self) -> ErrorCategory {
+		match self {
+			AutomaticActionError::NotInternal => ErrorCategory::Forbidden,
+			AutomaticActionError::NotHook => ErrorCategory::Forbidden,
+			AutomaticActionError::InvalidInput(error) => error.category(),
+			AutomaticActionError::Timeout(error) => error.category(),
+			AutomaticActionError::Panic(error) => error.category(),
+			AutomaticActionError::Intercepted(error) => error.category(),
+			AutomaticActionError::Outbox(error) => error.category(),
+			AutomaticActionError::Idempotency(error) => error.category(),
 		}
 	}
 }
 
+impl PipelineError for AutomaticActionError {
+	fn invalid_input(error: ValidationError) -> Self {
+		AutomaticActionError::InvalidInput(error)
+	}
+
+	fn timeout(error: TimeoutError) -> Self {
+		AutomaticActionError::Timeout(error)
+	}
+
+	fn panic(error: PanicError) -> Self {
+		AutomaticActionError::Panic(error)
+	}
+
+	fn outbox(error: ExternalException) -> Self {
+		AutomaticActionError::Outbox(error)
+	}
+
+	fn idempotency(error: IdempotencyError) -> Self {
+		AutomaticActionError::Idempotency(error)
+	}
+
+	fn intercepted(error: InterceptorError) -> Self {
+		AutomaticActionError::Intercepted(Box::new(error))
+	}
+}
+
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
@@ -185,46 +287,14 @@ where
 	async fn run(
 		input: AutomaticActionInput<I>,
 	) -> Result<AutomaticOutputInfo<O>, AutomaticErrorInfo<E>> {
-		let context = input
-			.as_ref()
-			.map(|ok_input| Some(ok_input.context.clone()))
-			.unwrap_or(None);
-
-		let action_context = ActionContext {
-			action_type: Self::action_type(),
-			context,
-		};
-
-		match input {
-			Ok(ok_input) => {
-				let action_result = Self::new(ok_input).await;
-
-				match action_result {
-					Ok(action) => {
-						let result = action.run_inner().await;
-
-						match result {
-							Ok(data) => Ok(AutomaticOutputInfo {
-								action_context,
-								data,
-							}),
-							Err(error) => Err(AutomaticErrorInfo {
-								action_context,
-								error,
-							}),
-						}
-					}
-					Err(error) => Err(AutomaticErrorInfo {
-						action_context,
-						error,
-					}),
-				}
-			}
-			Err(error) => Err(AutomaticErrorInfo {
-				action_context,
-				error: E::from(error),
-			}),
-		}
+		pipeline_impl::run(
+			&AUTOMATIC_INTERCEPTORS.list(),
+			Self::action_type(),
+			input,
+			Self::new,
+			Self::run_inner,
+		)
+		.await
 	}
 }
 
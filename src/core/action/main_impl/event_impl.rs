@@ -0,0 +1,508 @@
+use std::{cell::RefCell, future::Future, sync::Mutex};
+
+use futures::future::BoxFuture;
+use rocket::fairing::AdHoc;
+
+use crate::{
+	core::{
+		action::{
+			action_type::general_action_type::ActionType,
+			data::{
+				action_data::{Application, RequestInput},
+				automatic_action_data::{
+					AutomaticActionError, AutomaticRequest, AutomaticRequestContext,
+				},
+				event_data::{
+					DomainEvent, EventDelivery, EventHandler, EventSubscription, EventType,
+				},
+			},
+			definition::action::{ActionError, ActionInput, ActionOutput, AutomaticAction},
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::event_outbox_dao,
+	lib::data::str::Str,
+};
+
+/// The queued event is dropped after this many failed deliveries.
+pub const MAX_ATTEMPTS: i64 = 5;
+
+const BATCH_SIZE: i64 = 50;
+
+const DEFAULT_POLL_INTERVAL: u64 = 5;
+
+// const initialized, so the subscribers can subscribe from their ctors in any
+// order
+static SUBSCRIPTIONS: Mutex<Vec<EventSubscription>> = Mutex::new(Vec::new());
+
+tokio::task_local! {
+	static EVENTS: RefCell<Vec<DomainEvent>>;
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+impl DomainEvent {
+	pub fn event_type(&self) -> EventType {
+		match self {
+			DomainEvent::UserRegistered { .. } => EventType::UserRegistered,
+			DomainEvent::UserDeleted { .. } => EventType::UserDeleted,
+			DomainEvent::SessionCreated { .. } => EventType::SessionCreated,
+		}
+	}
+}
+
+impl ActionInput for DomainEvent {}
+
+////////////////////////////////////////////////
+/////////////////// REGISTRY ///////////////////
+////////////////////////////////////////////////
+
+fn handle<A, O, E>(
+	application: Application,
+	event: DomainEvent,
+) -> BoxFuture<'static, Result<(), Str>>
+where
+	A: AutomaticAction<DomainEvent, O, E> + Send + 'static,
+	O: ActionOutput,
+	E: ActionError + From<AutomaticActionError> + Send,
+{
+	Box::pin(async move {
+		let input = RequestInput {
+			context: AutomaticRequestContext {
+				application,
+				request: AutomaticRequest::Internal,
+			},
+			data: event,
+		};
+
+		A::run(Ok(input))
+			.await
+			.map(|_| ())
+			.map_err(|error_info| format!("{:?}", error_info.error).into())
+	})
+}
+
+/// Runs the automatic action for each event of the type, once the action
+/// that published it succeeds.
+pub fn subscribe<A, O, E>(event_type: EventType, delivery: EventDelivery)
+where
+	A: AutomaticAction<DomainEvent, O, E> + Send + 'static,
+	O: ActionOutput,
+	E: ActionError + From<AutomaticActionError> + Send,
+{
+	let handler: EventHandler = handle::<A, O, E>;
+	SUBSCRIPTIONS.lock().unwrap().push(EventSubscription {
+		event_type,
+		delivery,
+		action_type: A::action_type(),
+		handler,
+	});
+}
+
+pub fn subscriptions() -> Vec<EventSubscription> {
+	SUBSCRIPTIONS.lock().unwrap().clone()
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+/// Publishes the event from the running action.
+pub fn publish(event: DomainEvent) {
+	let published = EVENTS.try_with(|events| events.borrow_mut().push(event.clone()));
+
+	if published.is_err() {
+		error!("[event] {event:?} was published outside of an action");
+	}
+}
+
+// runs the action, returning the events it published
+async fn collect<O, E, F>(future: F) -> (Result<O, E>, Vec<DomainEvent>)
+where
+	F: Future<Output = Result<O, E>>,
+{
+	EVENTS
+		.scope(RefCell::new(vec![]), async {
+			let result = future.await;
+			let events = EVENTS.with(RefCell::take);
+			(result, events)
+		})
+		.await
+}
+
+/// Runs the action and delivers the events it published, when it succeeds.
+/// The action fails when its queued events can't be stored, as their
+/// subscribers would never receive them.
+pub async fn run<O, E, F>(
+	application: &Application,
+	future: F,
+	error: fn(ExternalException) -> E,
+) -> Result<O, E>
+where
+	F: Future<Output = Result<O, E>>,
+{
+	let (result, events) = collect(future).await;
+
+	if result.is_ok() && !events.is_empty() {
+		dispatch(application, events, &subscriptions())
+			.await
+			.map_err(error)?;
+	}
+
+	result
+}
+
+async fn dispatch(
+	application: &Application,
+	events: Vec<DomainEvent>,
+	subscriptions: &[EventSubscription],
+) -> Result<(), ExternalException> {
+	let mut queued = vec![];
+
+	for event in events {
+		let event_type = event.event_type();
+
+		for subscription in subscriptions
+			.iter()
+			.filter(|subscription| subscription.event_type == event_type)
+		{
+			match subscription.delivery {
+				EventDelivery::Inline => {
+					let result = (subscription.handler)(application.clone(), event.clone()).await;
+
+					if let Err(error) = result {
+						error!(
+							"[event] {event_type:?} could not be delivered to {action_type:?}: \
+							 {error}",
+							action_type = subscription.action_type,
+						);
+					}
+				}
+				EventDelivery::Queued => match serde_json::to_string(&event) {
+					Ok(json) => queued.push(event_outbox_dao::NewEvent {
+						action_id: subscription.action_type.id().into(),
+						event: json,
+					}),
+					Err(error) => error!(
+						"[event] {event_type:?} could not be queued for {action_type:?}: {error}",
+						action_type = subscription.action_type,
+					),
+				},
+			}
+		}
+	}
+
+	if queued.is_empty() {
+		return Ok(());
+	}
+
+	event_outbox_dao::Insert::run(event_outbox_dao::InsertInput(queued)).await
+}
+
+////////////////////////////////////////////////
+/////////////////// WORKER /////////////////////
+////////////////////////////////////////////////
+
+async fn deliver(
+	application: &Application,
+	subscriptions: &[EventSubscription],
+	item: &event_outbox_dao::OutboxEvent,
+) -> Result<(), Str> {
+	let event = serde_json::from_str::<DomainEvent>(&item.event)
+		.map_err(|error| Str::from(error.to_string()))?;
+	let event_type = event.event_type();
+
+	let subscription = subscriptions
+		.iter()
+		.find(|subscription| {
+			subscription.event_type == event_type
+				&& subscription.delivery == EventDelivery::Queued
+				&& i64::from(subscription.action_type.id()) == item.action_id
+		})
+		.ok_or_else(|| Str::from(format!("no queued subscriber for {event_type:?}")))?;
+
+	(subscription.handler)(application.clone(), event).await
+}
+
+/// Delivers a batch of the queued events, returning how many were delivered.
+pub async fn deliver_queued(
+	application: &Application,
+	subscriptions: &[EventSubscription],
+) -> Result<usize, ExternalException> {
+	let event_outbox_dao::SelectPendingOutput(items) =
+		event_outbox_dao::SelectPending::run(event_outbox_dao::SelectPendingInput {
+			max_attempts: MAX_ATTEMPTS,
+			limit: BATCH_SIZE,
+		})
+		.await?;
+
+	let mut delivered = 0;
+
+	for item in items {
+		let input = match deliver(application, subscriptions, &item).await {
+			Ok(()) => {
+				delivered += 1;
+				event_outbox_dao::UpdateInput::Delivered { id: item.id }
+			}
+			Err(error) => {
+				error!(
+					"[event] the queued event {id} could not be delivered (attempt {attempt}): \
+					 {error}",
+					id = item.id,
+					attempt = item.attempts + 1,
+				);
+				event_outbox_dao::UpdateInput::Failed {
+					id: item.id,
+					error: error.into(),
+				}
+			}
+		};
+
+		event_outbox_dao::Update::run(input).await?;
+	}
+
+	Ok(delivered)
+}
+
+#[derive(Debug, Deserialize)]
+struct EventConfig {
+	poll_interval: u64,
+}
+
+#[cfg(not(test))]
+fn spawn_worker(application: Application, poll_interval: u64) {
+	let has_queued = subscriptions()
+		.iter()
+		.any(|subscription| subscription.delivery == EventDelivery::Queued);
+
+	if !has_queued {
+		return;
+	}
+
+	tokio::spawn(async move {
+		let mut interval = tokio::time::interval(std::time::Duration::from_secs(poll_interval));
+
+		loop {
+			interval.tick().await;
+
+			if let Err(error) = deliver_queued(&application, &subscriptions()).await {
+				error!("[event] the queued events could not be delivered: {error:?}");
+			}
+		}
+	});
+}
+
+// the tests deliver the queued events themselves
+#[cfg(test)]
+fn spawn_worker(_application: Application, _poll_interval: u64) {}
+
+pub fn stage() -> AdHoc {
+	AdHoc::on_liftoff("Event Worker", |rocket| {
+		Box::pin(async move {
+			let poll_interval = rocket
+				.figment()
+				.extract_inner::<EventConfig>("events")
+				.map(|config| config.poll_interval)
+				.unwrap_or(DEFAULT_POLL_INTERVAL)
+				.max(1);
+			let application = rocket.state::<Application>().cloned().unwrap_or_default();
+
+			spawn_worker(application, poll_interval);
+		})
+	})
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use crate::core::action::action_type::automatic_action_type::AutomaticActionType;
+	use crate::core::action::data::action_data::Application;
+	use crate::core::action::data::automatic_action_data::{
+		AutomaticActionError, AutomaticRequestInput, InternalInputResult, InternalRequestInput,
+	};
+	use crate::core::action::data::event_data::{
+		DomainEvent, EventDelivery, EventSubscription, EventType,
+	};
+	use crate::core::action::definition::action::AutomaticAction;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::event_outbox_dao;
+	use crate::shared::data::user_data::UserId;
+	use crate::tests::test_utils::tests::{run_test, Recorder, RecorderKey};
+
+	thread_local! {
+		static HANDLED: Recorder<DomainEvent> = const { Recorder::new() };
+	}
+
+	#[derive(Debug)]
+	struct TestHandler(InternalRequestInput<DomainEvent>);
+
+	#[rocket::async_trait]
+	impl AutomaticAction<DomainEvent, (), AutomaticActionError> for TestHandler {
+		fn action_type() -> AutomaticActionType {
+			AutomaticActionType::Test
+		}
+
+		async fn new(
+			input: AutomaticRequestInput<DomainEvent>,
+		) -> Result<Self, AutomaticActionError> {
+			InternalInputResult::from(input).map(Self)
+		}
+
+		// the user 0 can't be handled
+		async fn run_inner(self) -> Result<(), AutomaticActionError> {
+			let Self(input) = self;
+
+			match input.data {
+				DomainEvent::UserDeleted { user_id: UserId(0) } => {
+					Err(AutomaticActionError::NotHook)
+				}
+				event => {
+					HANDLED.record(event);
+					Ok(())
+				}
+			}
+		}
+	}
+
+	fn subscription(event_type: EventType, delivery: EventDelivery) -> EventSubscription {
+		EventSubscription {
+			event_type,
+			delivery,
+			action_type: AutomaticActionType::Test,
+			handler: super::handle::<TestHandler, (), AutomaticActionError>,
+		}
+	}
+
+	fn deleted(id: i64) -> DomainEvent {
+		DomainEvent::UserDeleted {
+			user_id: UserId(id),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_collect() {
+		run_test(|helper| async move {
+			let (result, events) = super::collect(async {
+				super::publish(deleted(1));
+				super::publish(deleted(2));
+				Ok::<_, ()>(3)
+			})
+			.await;
+			assert_eq!(result, Ok(3));
+			assert_eq!(events, vec![deleted(1), deleted(2)]);
+
+			super::publish(deleted(4));
+			assert_eq!(
+				helper.pop_log(),
+				Some("ERROR - [event] UserDeleted { user_id: UserId(4) } was published outside of an action".into()),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_dispatch_inline() {
+		run_test(|helper| async move {
+			let subscriptions = [
+				subscription(EventType::UserDeleted, EventDelivery::Inline),
+				subscription(EventType::SessionCreated, EventDelivery::Inline),
+			];
+			let created = DomainEvent::SessionCreated { user_id: UserId(1) };
+			let registered = DomainEvent::UserRegistered {
+				user_id: UserId(2),
+				email: "user-02@domain.test".into(),
+			};
+
+			let result = super::dispatch(
+				&Application::default(),
+				vec![deleted(1), created.clone(), registered, deleted(0)],
+				&subscriptions,
+			)
+			.await;
+
+			assert_eq!(result, Ok(()));
+			assert_eq!(HANDLED.take(), vec![deleted(1), created]);
+			assert_eq!(
+				helper.pop_log(),
+				Some("ERROR - [event] UserDeleted could not be delivered to Test: NotHook".into()),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_dispatch_queued() {
+		run_test(|_| async {
+			let subscriptions = [
+				subscription(EventType::UserDeleted, EventDelivery::Queued),
+				subscription(EventType::SessionCreated, EventDelivery::Inline),
+			];
+
+			let _m = event_outbox_dao::Insert::mock(
+				event_outbox_dao::InsertInput(vec![event_outbox_dao::NewEvent {
+					action_id: 0,
+					event: r#"{"type":"user_deleted","user_id":1}"#.into(),
+				}]),
+				(),
+			);
+
+			let result =
+				super::dispatch(&Application::default(), vec![deleted(1)], &subscriptions).await;
+
+			assert_eq!(result, Ok(()));
+			assert_eq!(HANDLED.take(), vec![]);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_deliver_queued() {
+		run_test(|helper| async move {
+			let subscriptions = [subscription(EventType::UserDeleted, EventDelivery::Queued)];
+			let item = |id, action_id, event: &str| event_outbox_dao::OutboxEvent {
+				id,
+				action_id,
+				event: event.into(),
+				created_at: 1_650_000_000,
+				attempts: 1,
+			};
+
+			let _m1 = event_outbox_dao::SelectPending::mock(
+				event_outbox_dao::SelectPendingInput {
+					max_attempts: super::MAX_ATTEMPTS,
+					limit: super::BATCH_SIZE,
+				},
+				event_outbox_dao::SelectPendingOutput(vec![
+					item(11, 0, r#"{"type":"user_deleted","user_id":5}"#),
+					item(12, 3, r#"{"type":"user_deleted","user_id":6}"#),
+				]),
+			);
+			let _m2 =
+				event_outbox_dao::Update::mock(event_outbox_dao::UpdateInput::Delivered { id: 11 }, ());
+			let _m3 = event_outbox_dao::Update::mock(
+				event_outbox_dao::UpdateInput::Failed {
+					id: 12,
+					error: "no queued subscriber for UserDeleted".into(),
+				},
+				(),
+			);
+
+			let delivered = super::deliver_queued(&Application::default(), &subscriptions).await;
+
+			assert_eq!(delivered, Ok(1));
+			assert_eq!(HANDLED.take(), vec![deleted(5)]);
+			assert_eq!(
+				helper.pop_log(),
+				Some("ERROR - [event] the queued event 12 could not be delivered (attempt 2): no queued subscriber for UserDeleted".into()),
+			);
+		})
+		.await;
+	}
+}
@@ -0,0 +1,402 @@
+use std::future::Future;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+	core::{
+		action::{
+			action_type::general_action_type::ActionType,
+			data::{
+				action_data::{DescriptiveError, ErrorCategory, ErrorData},
+				audit_data::AuditActor,
+				idempotency_data::{IdempotencyError, IdempotentRequest},
+			},
+			definition::{
+				action::{ActionError, ActionOutput},
+				action_helpers::AuditInfo,
+			},
+			main_impl::audit_impl,
+		},
+		external::definition::external::ExternalAction,
+	},
+	external::dao::main::idempotency_dao,
+	lib::data::str::Str,
+};
+
+// the window in which a request with the same key is replayed
+const IDEMPOTENCY_TTL: i64 = 24 * 3600;
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+// the anonymous requests are told apart by their ip
+fn actor<C: AuditInfo>(context: &C) -> String {
+	match context.actor() {
+		AuditActor::User(user_id) => format!("user:{user_id}"),
+		AuditActor::Moderator(moderator_id) => format!("moderator:{moderator_id}"),
+		actor => format!(
+			"{actor_type}:{ip}",
+			actor_type = actor.actor_type(),
+			ip = context.ip().unwrap_or_default(),
+		),
+	}
+}
+
+// the sensitive fields are redacted, so that the stored fingerprint doesn't
+// expose them (a retry that only changes them is still the same request)
+fn fingerprint<I: Serialize>(data: &I) -> String {
+	let input = serde_json::to_value(data)
+		.map(audit_impl::redact)
+		.unwrap_or_default();
+	hex::encode(Sha256::digest(input.to_string().as_bytes()))
+}
+
+/// The idempotent request, when the action type is idempotent and the
+/// request has a key.
+pub fn request<T, C, I>(
+	action_type: T,
+	context: &C,
+	key: Option<&Str>,
+	data: &I,
+) -> Option<IdempotentRequest>
+where
+	T: ActionType,
+	C: AuditInfo,
+	I: Serialize,
+{
+	if !action_type.idempotent() {
+		return None;
+	}
+
+	key.map(|key| IdempotentRequest {
+		actor: actor(context),
+		action: format!("{}:{}", T::scope(), action_type.id()),
+		key: key.to_string(),
+		fingerprint: fingerprint(data),
+	})
+}
+
+impl From<&IdempotentRequest> for idempotency_dao::IdempotencyId {
+	fn from(request: &IdempotentRequest) -> Self {
+		Self {
+			actor: request.actor.clone(),
+			action: request.action.clone(),
+			key: request.key.clone(),
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ACTION ////////////////////
+////////////////////////////////////////////////
+
+/// Runs the action once for the request: a retry with the same input gets
+/// the output of the first execution, while a retry with another input (or
+/// before the first one completed) is rejected. Only the successful
+/// executions are kept, the failed ones can be retried.
+pub async fn run<O, E, F>(
+	request: Option<IdempotentRequest>,
+	action: F,
+	error: fn(IdempotencyError) -> E,
+) -> Result<O, E>
+where
+	O: ActionOutput,
+	F: Future<Output = Result<O, E>>,
+{
+	let request = match request {
+		Some(request) => request,
+		None => return action.await,
+	};
+
+	let begin_input = idempotency_dao::BeginInput {
+		id: (&request).into(),
+		fingerprint: request.fingerprint.clone(),
+		ttl_seconds: IDEMPOTENCY_TTL,
+	};
+
+	match idempotency_dao::Begin::run(begin_input).await {
+		Ok(idempotency_dao::BeginOutput::Started) => {}
+		Ok(idempotency_dao::BeginOutput::Replay(response)) => {
+			return serde_json::from_str(&response)
+				.map_err(|e| error(IdempotencyError::ReplayError(e.to_string().into())));
+		}
+		Ok(idempotency_dao::BeginOutput::InProgress) => {
+			return Err(error(IdempotencyError::InProgress))
+		}
+		Ok(idempotency_dao::BeginOutput::Conflict) => {
+			return Err(error(IdempotencyError::Conflict))
+		}
+		Err(e) => return Err(error(IdempotencyError::StoreError(e))),
+	}
+
+	let result = action.await;
+	let response = result
+		.as_ref()
+		.ok()
+		.and_then(|output| serde_json::to_string(output).ok());
+
+	let stored = match response {
+		Some(response) => {
+			let input = idempotency_dao::CompleteInput {
+				id: (&request).into(),
+				response,
+			};
+			idempotency_dao::Complete::run(input).await
+		}
+		None => {
+			let input = idempotency_dao::ReleaseInput {
+				id: (&request).into(),
+			};
+			idempotency_dao::Release::run(input).await
+		}
+	};
+
+	// the result of the action doesn't change, the key just stays in progress
+	// until it expires
+	if let Err(e) = stored {
+		error!(
+			"[idempotency] the request {action} - {key} could not be stored: {e:?}",
+			action = request.action,
+			key = request.key,
+		);
+	}
+
+	result
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+impl ActionError for IdempotencyError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		match self {
+			IdempotencyError::Conflict => None,
+			IdempotencyError::InProgress => None,
+			IdempotencyError::StoreError(error) => error.private_error(),
+			IdempotencyError::ReplayError(msg) => Some(DescriptiveError {
+				msg: Some(format!("the stored response could not be replayed: {msg}").into()),
+				data: None,
+				source: None,
+			}),
+		}
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		match self {
+			IdempotencyError::Conflict => Self::error_msg(
+				"action.idempotency_conflict",
+				"The idempotency key was already used for a different request.".into(),
+			),
+			IdempotencyError::InProgress => Self::error_msg(
+				"action.idempotency_in_progress",
+				"A request with the same idempotency key is still in progress.".into(),
+			),
+			IdempotencyError::StoreError(error) => error.public_error(),
+			IdempotencyError::ReplayError(_) => None,
+		}
+	}
+
+	fn category(&self) -> ErrorCategory {
+		match self {
+			IdempotencyError::Conflict => ErrorCategory::Conflict,
+			IdempotencyError::InProgress => ErrorCategory::Conflict,
+			IdempotencyError::StoreError(error) => error.category(),
+			IdempotencyError::ReplayError(_) => ErrorCategory::Internal,
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use crate::business::action::user::register_user_action;
+	use crate::core::action::action_type::user_action_type::UserActionType;
+	use crate::core::action::data::action_data::tests::RequestBuilder;
+	use crate::core::action::data::idempotency_data::{IdempotencyError, IdempotentRequest};
+	use crate::core::action::data::user_action_data::tests::{
+		UserAuthSessionBuilder, UserRequestContextBuilder,
+	};
+	use crate::core::action::data::user_action_data::UserSession;
+	use crate::core::action::definition::action::ActionOutput;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::idempotency_dao;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct TestOutput(u64);
+
+	impl ActionOutput for TestOutput {}
+
+	#[derive(Debug, PartialEq)]
+	enum TestError {
+		Idempotency(IdempotencyError),
+		Failed,
+	}
+
+	fn test_request() -> IdempotentRequest {
+		IdempotentRequest {
+			actor: "user:5".into(),
+			action: "User:1".into(),
+			key: "key-01".into(),
+			fingerprint: "fingerprint-01".into(),
+		}
+	}
+
+	fn mock_begin(output: idempotency_dao::BeginOutput) -> mockito::Mock {
+		idempotency_dao::Begin::mock(
+			idempotency_dao::BeginInput {
+				id: (&test_request()).into(),
+				fingerprint: "fingerprint-01".into(),
+				ttl_seconds: super::IDEMPOTENCY_TTL,
+			},
+			output,
+		)
+	}
+
+	async fn run(result: Result<TestOutput, TestError>) -> Result<TestOutput, TestError> {
+		super::run(
+			Some(test_request()),
+			async { result },
+			TestError::Idempotency,
+		)
+		.await
+	}
+
+	#[tokio::test]
+	async fn test_request_key() {
+		run_test(|_| async {
+			let anonymous = UserRequestContextBuilder::new()
+				.request(RequestBuilder::new().ip("10.0.0.5".into()).build())
+				.build();
+			let user = UserRequestContextBuilder::new()
+				.session(UserSession::Auth(
+					UserAuthSessionBuilder::new().user_id(5).build(),
+				))
+				.build();
+
+			let key = Some("key-01".into());
+			let register = UserActionType::Register;
+
+			assert_eq!(super::request(register, &user, None, &3), None);
+			assert_eq!(
+				super::request(UserActionType::Login, &user, key.as_ref(), &3),
+				None
+			);
+
+			let request = super::request(register, &user, key.as_ref(), &3).unwrap();
+			assert_eq!(&request.actor, "user:5");
+			assert_eq!(&request.action, "User:1");
+			assert_eq!(&request.key, "key-01");
+
+			let other = super::request(register, &user, key.as_ref(), &4).unwrap();
+			assert_ne!(&request.fingerprint, &other.fingerprint);
+
+			let request = super::request(register, &anonymous, key.as_ref(), &3).unwrap();
+			assert_eq!(&request.actor, "anonymous:10.0.0.5");
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_request_fingerprint() {
+		run_test(|_| async {
+			let user = UserRequestContextBuilder::new().build();
+			let key = Some("key-01".into());
+
+			let request = |name: &str, pass: &str| {
+				let input = register_user_action::Input {
+					name: name.into(),
+					email: "a@b.com".into(),
+					pass: pass.into(),
+				};
+				super::request(UserActionType::Register, &user, key.as_ref(), &input).unwrap()
+			};
+
+			let fingerprint = request("User 01", "p4$$w0rd").fingerprint;
+			assert_eq!(&request("User 01", "0th3r-p4$$").fingerprint, &fingerprint);
+			assert_ne!(&request("User 02", "p4$$w0rd").fingerprint, &fingerprint);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_no_request() {
+		run_test(|_| async {
+			let result = super::run(
+				None,
+				async { Ok::<_, TestError>(TestOutput(7)) },
+				TestError::Idempotency,
+			)
+			.await;
+			assert_eq!(result, Ok(TestOutput(7)));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_started() {
+		run_test(|_| async {
+			let _m1 = mock_begin(idempotency_dao::BeginOutput::Started);
+			let _m2 = idempotency_dao::Complete::mock(
+				idempotency_dao::CompleteInput {
+					id: (&test_request()).into(),
+					response: "7".into(),
+				},
+				(),
+			);
+
+			assert_eq!(run(Ok(TestOutput(7))).await, Ok(TestOutput(7)));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_failed() {
+		run_test(|_| async {
+			let _m1 = mock_begin(idempotency_dao::BeginOutput::Started);
+			let _m2 = idempotency_dao::Release::mock(
+				idempotency_dao::ReleaseInput {
+					id: (&test_request()).into(),
+				},
+				(),
+			);
+
+			assert_eq!(run(Err(TestError::Failed)).await, Err(TestError::Failed));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_replay() {
+		run_test(|_| async {
+			let _m = mock_begin(idempotency_dao::BeginOutput::Replay("3".into()));
+
+			assert_eq!(run(Ok(TestOutput(7))).await, Ok(TestOutput(3)));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_rejected() {
+		run_test(|_| async {
+			let _m = mock_begin(idempotency_dao::BeginOutput::Conflict);
+			assert_eq!(
+				run(Ok(TestOutput(7))).await,
+				Err(TestError::Idempotency(IdempotencyError::Conflict))
+			);
+
+			let _m = mock_begin(idempotency_dao::BeginOutput::InProgress);
+			assert_eq!(
+				run(Ok(TestOutput(7))).await,
+				Err(TestError::Idempotency(IdempotencyError::InProgress))
+			);
+		})
+		.await;
+	}
+}
@@ -0,0 +1,493 @@
+use std::{
+	future::Future,
+	sync::{Arc, RwLock},
+	time::Instant,
+};
+
+use chrono::Utc;
+
+use super::{
+	audit_impl::AuditInterceptor, metrics_impl::MetricsInterceptor,
+	rate_limit_impl::RateLimitInterceptor,
+};
+
+use crate::core::action::{
+	action_type::{
+		automatic_action_type::AutomaticActionType, general_action_type::ActionType,
+		moderator_action_type::ModeratorActionType, user_action_type::UserActionType,
+	},
+	data::{
+		action_data::{
+			ActionContext, ActionErrorInfo, ActionResultInfo, DescriptiveError, ErrorCategory,
+			ErrorData, RequestContext,
+		},
+		automatic_action_data::AutomaticRequestContext,
+		interceptor_data::{
+			ActionCall, ActionFuture, ActionNext, ActionOutcome, InterceptorError,
+			InterceptorRegistry, OutcomeError,
+		},
+		moderator_action_data::ModeratorRequestContext,
+		user_action_data::UserRequestContext,
+	},
+	definition::{action::ActionError, action_interceptor::ActionInterceptor},
+};
+
+////////////////////////////////////////////////
+/////////////////// REGISTRY ///////////////////
+////////////////////////////////////////////////
+
+// const initialized, so the interceptors can register from their ctors in any
+// order
+pub static USER_INTERCEPTORS: InterceptorRegistry<UserActionType, UserRequestContext> =
+	InterceptorRegistry::new();
+
+pub static MODERATOR_INTERCEPTORS: InterceptorRegistry<
+	ModeratorActionType,
+	ModeratorRequestContext,
+> = InterceptorRegistry::new();
+
+pub static AUTOMATIC_INTERCEPTORS: InterceptorRegistry<
+	AutomaticActionType,
+	AutomaticRequestContext,
+> = InterceptorRegistry::new();
+
+// the metrics are the outermost, so they include the time spent by the others
+#[ctor::ctor]
+fn register() {
+	register_global(MetricsInterceptor);
+	register_global(AuditInterceptor);
+	register_global(RateLimitInterceptor);
+}
+
+/// Registers the interceptor for the actions of all the scopes.
+pub fn register_global<I>(interceptor: I)
+where
+	I: ActionInterceptor<UserActionType, UserRequestContext>
+		+ ActionInterceptor<ModeratorActionType, ModeratorRequestContext>
+		+ ActionInterceptor<AutomaticActionType, AutomaticRequestContext>
+		+ 'static,
+{
+	let interceptor = Arc::new(interceptor);
+	USER_INTERCEPTORS.register(interceptor.clone());
+	MODERATOR_INTERCEPTORS.register(interceptor.clone());
+	AUTOMATIC_INTERCEPTORS.register(interceptor);
+}
+
+impl<T: ActionType, C: RequestContext> InterceptorRegistry<T, C> {
+	pub const fn new() -> Self {
+		Self(RwLock::new(Vec::new()))
+	}
+
+	/// Registers the interceptor for the actions of the scope.
+	pub fn register(&self, interceptor: Arc<dyn ActionInterceptor<T, C>>) {
+		self.0.write().unwrap().push(interceptor);
+	}
+
+	pub fn list(&self) -> Vec<Arc<dyn ActionInterceptor<T, C>>> {
+		self.0.read().unwrap().clone()
+	}
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+impl<'a> ActionNext<'a> {
+	pub async fn run(self) -> ActionOutcome {
+		self.0.await
+	}
+}
+
+fn outcome<O, E: ActionError>(started: Instant, result: &Result<O, E>) -> ActionOutcome {
+	ActionOutcome {
+		duration: started.elapsed(),
+		error: result.as_ref().err().map(|error| OutcomeError {
+			category: error.category(),
+			public: error.public_error(),
+			private: error.private_error(),
+		}),
+	}
+}
+
+fn not_run() -> InterceptorError {
+	InterceptorError {
+		category: ErrorCategory::Internal,
+		public: None,
+		private: Some(DescriptiveError {
+			msg: Some("the action was not run by an interceptor".into()),
+			data: None,
+			source: None,
+		}),
+	}
+}
+
+/// Runs the action through the interceptors; `intercepted` converts the error
+/// of an interceptor that stopped the action into the action error.
+pub async fn run<T, C, O, E, F>(
+	interceptors: &[Arc<dyn ActionInterceptor<T, C>>],
+	action_context: ActionContext<T, C>,
+	input: String,
+	action: F,
+	intercepted: fn(InterceptorError) -> E,
+) -> Result<ActionResultInfo<T, C, O>, ActionErrorInfo<T, C, E>>
+where
+	T: ActionType,
+	C: RequestContext,
+	O: Send,
+	E: ActionError,
+	F: Future<Output = Result<O, E>> + Send,
+{
+	let started = Instant::now();
+	let call = ActionCall {
+		action_context: &action_context,
+		created_at: Utc::now(),
+		input: &input,
+	};
+
+	let mut stopped = None;
+
+	for interceptor in interceptors {
+		if let Err(error) = interceptor.before(&call).await {
+			stopped = Some(error);
+			break;
+		}
+	}
+
+	let (result, action_outcome) = match stopped {
+		Some(error) => {
+			let result = Err(intercepted(error));
+			let action_outcome = outcome(started, &result);
+			(result, action_outcome)
+		}
+		None => {
+			let mut slot = None;
+			let inner: ActionFuture = Box::pin(async {
+				let result = action.await;
+				let action_outcome = outcome(started, &result);
+				slot = Some(result);
+				action_outcome
+			});
+			let chain = interceptors.iter().rev().fold(inner, |next, interceptor| {
+				interceptor.around(&call, ActionNext(next))
+			});
+			let action_outcome = chain.await;
+
+			match slot {
+				Some(result) => (result, action_outcome),
+				None => {
+					let result = Err(intercepted(not_run()));
+					let action_outcome = outcome(started, &result);
+					(result, action_outcome)
+				}
+			}
+		}
+	};
+
+	for interceptor in interceptors.iter().rev() {
+		interceptor.after(&call, &action_outcome).await;
+	}
+
+	match result {
+		Ok(data) => Ok(ActionResultInfo {
+			action_context,
+			data,
+		}),
+		Err(error) => Err(ActionErrorInfo {
+			action_context,
+			error,
+		}),
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+impl ActionError for InterceptorError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		self.private.clone()
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		self.public.clone()
+	}
+
+	fn category(&self) -> ErrorCategory {
+		self.category
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::sync::{Arc, Mutex};
+
+	use crate::core::action::action_type::general_action_type::{ActionScope, ActionType};
+	use crate::core::action::data::action_data::{ActionContext, ActionErrorInfo, ErrorCategory};
+	use crate::core::action::data::action_data::{ActionResultInfo, RequestContext};
+	use crate::core::action::data::interceptor_data::{
+		ActionCall, ActionNext, ActionOutcome, InterceptorError, InterceptorRegistry,
+	};
+	use crate::core::action::definition::action_interceptor::ActionInterceptor;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[derive(Clone, Debug, Eq, PartialEq)]
+	struct TestContext;
+
+	impl RequestContext for TestContext {}
+
+	#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+	struct TestActionType;
+
+	impl ActionType for TestActionType {
+		fn scope() -> ActionScope {
+			ActionScope::User
+		}
+
+		fn id(&self) -> u32 {
+			1
+		}
+
+		fn from_id(_: u32) -> Option<Self> {
+			Some(Self)
+		}
+	}
+
+	type Events = Arc<Mutex<Vec<String>>>;
+
+	struct TestInterceptor {
+		name: &'static str,
+		events: Events,
+		stop: bool,
+		skip: bool,
+	}
+
+	impl TestInterceptor {
+		fn new(name: &'static str, events: &Events) -> Self {
+			Self {
+				name,
+				events: events.clone(),
+				stop: false,
+				skip: false,
+			}
+		}
+
+		fn push(&self, event: &str) {
+			let name = self.name;
+			self.events.lock().unwrap().push(format!("{name}:{event}"));
+		}
+	}
+
+	fn stop_error() -> InterceptorError {
+		InterceptorError {
+			category: ErrorCategory::RateLimited,
+			public: None,
+			private: None,
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ActionInterceptor<TestActionType, TestContext> for TestInterceptor {
+		async fn before(
+			&self,
+			call: &ActionCall<'_, TestActionType, TestContext>,
+		) -> Result<(), InterceptorError> {
+			self.push(&format!("before({})", call.input));
+
+			if self.stop {
+				Err(stop_error())
+			} else {
+				Ok(())
+			}
+		}
+
+		async fn around(
+			&self,
+			_call: &ActionCall<'_, TestActionType, TestContext>,
+			next: ActionNext<'_>,
+		) -> ActionOutcome {
+			self.push("around(start)");
+
+			let outcome = if self.skip {
+				ActionOutcome {
+					duration: Default::default(),
+					error: None,
+				}
+			} else {
+				next.run().await
+			};
+
+			self.push("around(end)");
+			outcome
+		}
+
+		async fn after(
+			&self,
+			_call: &ActionCall<'_, TestActionType, TestContext>,
+			outcome: &ActionOutcome,
+		) {
+			let category = outcome.error.as_ref().map(|error| error.category);
+			self.push(&format!("after({category:?})"));
+		}
+	}
+
+	fn action_context() -> ActionContext<TestActionType, TestContext> {
+		ActionContext {
+			action_type: TestActionType,
+			context: Some(TestContext),
+		}
+	}
+
+	async fn run(
+		interceptors: &[Arc<dyn ActionInterceptor<TestActionType, TestContext>>],
+		events: &Events,
+	) -> Result<
+		ActionResultInfo<TestActionType, TestContext, u32>,
+		ActionErrorInfo<TestActionType, TestContext, InterceptorError>,
+	> {
+		let events = events.clone();
+
+		super::run(
+			interceptors,
+			action_context(),
+			"input".into(),
+			async move {
+				events.lock().unwrap().push("action".into());
+				Ok(7)
+			},
+			|error| error,
+		)
+		.await
+	}
+
+	#[tokio::test]
+	async fn test_run_ok() {
+		run_test(|_| async {
+			let events = Events::default();
+			let interceptors: Vec<Arc<dyn ActionInterceptor<_, _>>> = vec![
+				Arc::new(TestInterceptor::new("a", &events)),
+				Arc::new(TestInterceptor::new("b", &events)),
+			];
+
+			let result = run(&interceptors, &events).await;
+
+			assert_eq!(
+				result,
+				Ok(ActionResultInfo {
+					action_context: action_context(),
+					data: 7,
+				})
+			);
+			assert_eq!(
+				*events.lock().unwrap(),
+				[
+					"a:before(input)",
+					"b:before(input)",
+					"a:around(start)",
+					"b:around(start)",
+					"action",
+					"b:around(end)",
+					"a:around(end)",
+					"b:after(None)",
+					"a:after(None)",
+				]
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_stopped() {
+		run_test(|_| async {
+			let events = Events::default();
+			let interceptors: Vec<Arc<dyn ActionInterceptor<_, _>>> = vec![
+				Arc::new(TestInterceptor {
+					stop: true,
+					..TestInterceptor::new("a", &events)
+				}),
+				Arc::new(TestInterceptor::new("b", &events)),
+			];
+
+			let result = run(&interceptors, &events).await;
+
+			assert_eq!(
+				result,
+				Err(ActionErrorInfo {
+					action_context: action_context(),
+					error: stop_error(),
+				})
+			);
+			assert_eq!(
+				*events.lock().unwrap(),
+				[
+					"a:before(input)",
+					"b:after(Some(RateLimited))",
+					"a:after(Some(RateLimited))",
+				]
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_not_run() {
+		run_test(|_| async {
+			let events = Events::default();
+			let interceptors: Vec<Arc<dyn ActionInterceptor<_, _>>> =
+				vec![Arc::new(TestInterceptor {
+					skip: true,
+					..TestInterceptor::new("a", &events)
+				})];
+
+			let result = run(&interceptors, &events).await;
+
+			assert_eq!(
+				result,
+				Err(ActionErrorInfo {
+					action_context: action_context(),
+					error: super::not_run(),
+				})
+			);
+			assert_eq!(
+				*events.lock().unwrap(),
+				[
+					"a:before(input)",
+					"a:around(start)",
+					"a:around(end)",
+					"a:after(Some(Internal))",
+				]
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_registry() {
+		run_test(|_| async {
+			let events = Events::default();
+			let registry = InterceptorRegistry::new();
+			registry.register(Arc::new(TestInterceptor::new("a", &events)));
+
+			let result = run(&registry.list(), &events).await;
+
+			assert!(result.is_ok());
+			assert_eq!(registry.list().len(), 1);
+			assert_eq!(events.lock().unwrap().len(), 5);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_registry_global() {
+		run_test(|_| async {
+			// the metrics, audit and rate limit interceptors
+			assert_eq!(super::USER_INTERCEPTORS.list().len(), 3);
+			assert_eq!(super::MODERATOR_INTERCEPTORS.list().len(), 3);
+			assert_eq!(super::AUTOMATIC_INTERCEPTORS.list().len(), 3);
+		})
+		.await;
+	}
+}
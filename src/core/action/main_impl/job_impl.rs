@@ -0,0 +1,558 @@
+use std::sync::Mutex;
+
+use futures::future::BoxFuture;
+use rocket::fairing::AdHoc;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+	core::{
+		action::{
+			action_type::general_action_type::ActionType,
+			data::{
+				action_data::{
+					Application, DescriptiveError, ErrorCategory, ErrorData, RequestInput,
+				},
+				automatic_action_data::{
+					AutomaticActionError, AutomaticRequest, AutomaticRequestContext,
+				},
+				job_data::{EnqueueError, JobConfig, JobDefinition, JobError, JobRunner},
+			},
+			definition::action::{ActionError, ActionInput, ActionOutput, AutomaticAction},
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::job_dao,
+};
+
+pub const DEFAULT_MAX_ATTEMPTS: i64 = 5;
+
+/// The delay (in seconds) before the first retry, doubled on each attempt.
+const BASE_BACKOFF: i64 = 10;
+
+const MAX_BACKOFF: i64 = 3600;
+
+const DEFAULT_CONFIG: JobConfig = JobConfig {
+	workers: 2,
+	poll_interval: 1,
+	lease: 60,
+};
+
+// const initialized, so the actions can register from their ctors in any
+// order
+static JOBS: Mutex<Vec<JobDefinition>> = Mutex::new(Vec::new());
+
+////////////////////////////////////////////////
+/////////////////// REGISTRY ///////////////////
+////////////////////////////////////////////////
+
+// only the failures that may not happen again are retried
+fn retry(category: ErrorCategory) -> bool {
+	matches!(
+		category,
+		ErrorCategory::RateLimited
+			| ErrorCategory::Internal
+			| ErrorCategory::Upstream
+			| ErrorCategory::Timeout
+	)
+}
+
+fn handle<A, I, O, E>(
+	application: Application,
+	input: String,
+) -> BoxFuture<'static, Result<(), JobError>>
+where
+	A: AutomaticAction<I, O, E> + Send + 'static,
+	I: ActionInput + DeserializeOwned + Send + 'static,
+	O: ActionOutput,
+	E: ActionError + From<AutomaticActionError> + Send,
+{
+	Box::pin(async move {
+		let data = serde_json::from_str::<I>(&input).map_err(|error| JobError {
+			retry: false,
+			msg: error.to_string().into(),
+		})?;
+		let input = RequestInput {
+			context: AutomaticRequestContext {
+				application,
+				request: AutomaticRequest::Internal,
+			},
+			data,
+		};
+
+		A::run(Ok(input))
+			.await
+			.map(|_| ())
+			.map_err(|error_info| JobError {
+				retry: retry(error_info.error.category()),
+				msg: format!("{:?}", error_info.error).into(),
+			})
+	})
+}
+
+/// Runs the jobs enqueued for the automatic action, as internal requests.
+pub fn register<A, I, O, E>(max_attempts: i64)
+where
+	A: AutomaticAction<I, O, E> + Send + 'static,
+	I: ActionInput + DeserializeOwned + Send + 'static,
+	O: ActionOutput,
+	E: ActionError + From<AutomaticActionError> + Send,
+{
+	let runner: JobRunner = handle::<A, I, O, E>;
+	JOBS.lock().unwrap().push(JobDefinition {
+		action_type: A::action_type(),
+		max_attempts,
+		runner,
+	});
+}
+
+pub fn definitions() -> Vec<JobDefinition> {
+	JOBS.lock().unwrap().clone()
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+/// Stores the input for the automatic action, to run in `delay` seconds,
+/// returning the id of the job.
+pub async fn enqueue<A, I, O, E>(input: &I, delay: i64) -> Result<i64, EnqueueError>
+where
+	A: AutomaticAction<I, O, E>,
+	I: ActionInput + Serialize,
+	O: ActionOutput,
+	E: ActionError + From<AutomaticActionError>,
+{
+	let action_type = A::action_type();
+	let definition = definitions()
+		.into_iter()
+		.find(|definition| definition.action_type == action_type)
+		.ok_or(EnqueueError::NotRegistered(action_type))?;
+	let json = serde_json::to_string(input)
+		.map_err(|error| EnqueueError::InvalidInput(error.to_string().into()))?;
+
+	let job_dao::InsertOutput { id } = job_dao::Insert::run(job_dao::InsertInput {
+		action_id: action_type.id().into(),
+		input: json,
+		delay,
+		max_attempts: definition.max_attempts,
+	})
+	.await?;
+
+	Ok(id)
+}
+
+/// The delay (in seconds) before running again the job that failed after
+/// the attempts.
+pub fn backoff(attempts: i64) -> i64 {
+	let exponent = (attempts - 1).clamp(0, 16) as u32;
+	BASE_BACKOFF
+		.saturating_mul(2_i64.pow(exponent))
+		.min(MAX_BACKOFF)
+}
+
+////////////////////////////////////////////////
+/////////////////// WORKER /////////////////////
+////////////////////////////////////////////////
+
+/// Runs the input (as json) with the automatic action of the id, as an
+/// internal request.
+pub async fn execute(
+	application: &Application,
+	definitions: &[JobDefinition],
+	action_id: i64,
+	input: String,
+) -> Result<(), JobError> {
+	let definition = definitions
+		.iter()
+		.find(|definition| i64::from(definition.action_type.id()) == action_id);
+
+	match definition {
+		Some(definition) => (definition.runner)(application.clone(), input).await,
+		None => Err(JobError {
+			retry: false,
+			msg: format!("no job runner for the automatic action {action_id}").into(),
+		}),
+	}
+}
+
+/// Claims and runs the next job that is due, returning whether there was
+/// one.
+pub async fn run_next(
+	application: &Application,
+	definitions: &[JobDefinition],
+	worker: &str,
+	lease: i64,
+) -> Result<bool, ExternalException> {
+	let job_dao::ClaimOutput(job) = job_dao::Claim::run(job_dao::ClaimInput {
+		worker: worker.into(),
+		lease,
+	})
+	.await?;
+
+	let job = match job {
+		Some(job) => job,
+		None => return Ok(false),
+	};
+
+	let result = execute(application, definitions, job.action_id, job.input.clone()).await;
+
+	match result {
+		Ok(()) => {
+			job_dao::Complete::run(job_dao::CompleteInput {
+				id: job.id,
+				worker: worker.into(),
+			})
+			.await?;
+		}
+		Err(JobError { retry, msg }) => {
+			let retry_in =
+				(retry && job.attempts < job.max_attempts).then(|| backoff(job.attempts));
+
+			match retry_in {
+				Some(retry_in) => warn!(
+					"[job] the job {id} failed (attempt {attempts}), retrying in {retry_in}s: {msg}",
+					id = job.id,
+					attempts = job.attempts,
+				),
+				None => error!(
+					"[job] the job {id} is dead (attempt {attempts}): {msg}",
+					id = job.id,
+					attempts = job.attempts,
+				),
+			}
+
+			job_dao::Fail::run(job_dao::FailInput {
+				id: job.id,
+				worker: worker.into(),
+				error: msg.into(),
+				retry_in,
+			})
+			.await?;
+		}
+	}
+
+	Ok(true)
+}
+
+#[cfg(not(test))]
+fn spawn_workers(application: Application, config: JobConfig) {
+	if definitions().is_empty() {
+		return;
+	}
+
+	for index in 0..config.workers {
+		let application = application.clone();
+		let worker = format!("{}-{index}", std::process::id());
+		let poll_interval = std::time::Duration::from_secs(config.poll_interval);
+		let lease = config.lease;
+
+		tokio::spawn(async move {
+			loop {
+				match run_next(&application, &definitions(), &worker, lease).await {
+					Ok(true) => continue,
+					Ok(false) => {}
+					Err(error) => error!("[job] the next job could not be run: {error:?}"),
+				}
+
+				tokio::time::sleep(poll_interval).await;
+			}
+		});
+	}
+}
+
+// the tests run the jobs themselves
+#[cfg(test)]
+fn spawn_workers(_application: Application, _config: JobConfig) {}
+
+pub fn stage() -> AdHoc {
+	AdHoc::on_liftoff("Job Workers", |rocket| {
+		Box::pin(async move {
+			let config = rocket
+				.figment()
+				.extract_inner::<JobConfig>("jobs")
+				.unwrap_or(DEFAULT_CONFIG);
+			let config = JobConfig {
+				poll_interval: config.poll_interval.max(1),
+				lease: config.lease.max(1),
+				..config
+			};
+			let application = rocket.state::<Application>().cloned().unwrap_or_default();
+
+			spawn_workers(application, config);
+		})
+	})
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+impl ActionError for EnqueueError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		match self {
+			EnqueueError::NotRegistered(action_type) => Some(DescriptiveError {
+				msg: Some(format!("no job runner for the automatic action {action_type:?}").into()),
+				data: None,
+				source: None,
+			}),
+			EnqueueError::InvalidInput(msg) => Some(DescriptiveError {
+				msg: Some(format!("the job input could not be stored: {msg}").into()),
+				data: None,
+				source: None,
+			}),
+			EnqueueError::ExternalError(error) => error.private_error(),
+		}
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		match self {
+			EnqueueError::ExternalError(error) => error.public_error(),
+			_ => None,
+		}
+	}
+
+	fn category(&self) -> ErrorCategory {
+		match self {
+			EnqueueError::ExternalError(error) => error.category(),
+			_ => ErrorCategory::Internal,
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use crate::core::action::action_type::automatic_action_type::AutomaticActionType;
+	use crate::core::action::action_type::general_action_type::ActionType;
+	use crate::core::action::data::action_data::{Application, TimeoutError};
+	use crate::core::action::data::automatic_action_data::{
+		AutomaticActionError, AutomaticRequestInput, InternalInputResult, InternalRequestInput,
+	};
+	use crate::core::action::data::job_data::{EnqueueError, JobDefinition};
+	use crate::core::action::definition::action::{ActionInput, AutomaticAction};
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::job_dao;
+	use crate::tests::test_utils::tests::{run_test, Recorder, RecorderKey};
+
+	thread_local! {
+		static RAN: Recorder<u64> = const { Recorder::new() };
+	}
+
+	pub fn ran() -> Vec<u64> {
+		RAN.take()
+	}
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct TestInput {
+		id: u64,
+		error: Option<String>,
+	}
+
+	impl ActionInput for TestInput {}
+
+	#[derive(Debug)]
+	struct TestJob(InternalRequestInput<TestInput>);
+
+	#[rocket::async_trait]
+	impl AutomaticAction<TestInput, (), AutomaticActionError> for TestJob {
+		fn action_type() -> AutomaticActionType {
+			AutomaticActionType::Test
+		}
+
+		async fn new(
+			input: AutomaticRequestInput<TestInput>,
+		) -> Result<Self, AutomaticActionError> {
+			InternalInputResult::from(input).map(Self)
+		}
+
+		async fn run_inner(self) -> Result<(), AutomaticActionError> {
+			let Self(input) = self;
+
+			match input.data.error.as_deref() {
+				Some("timeout") => Err(AutomaticActionError::Timeout(TimeoutError { timeout: 1 })),
+				Some(_) => Err(AutomaticActionError::NotHook),
+				None => {
+					RAN.record(input.data.id);
+					Ok(())
+				}
+			}
+		}
+	}
+
+	pub fn definitions() -> [JobDefinition; 1] {
+		[JobDefinition {
+			action_type: AutomaticActionType::Test,
+			max_attempts: 3,
+			runner: super::handle::<TestJob, TestInput, (), AutomaticActionError>,
+		}]
+	}
+
+	fn job(action_id: i64, input: &str, attempts: i64) -> job_dao::Job {
+		job_dao::Job {
+			id: 21,
+			action_id,
+			input: input.into(),
+			state: job_dao::STATE_RUNNING.into(),
+			run_at: 1_650_000_000,
+			attempts,
+			max_attempts: 3,
+			last_error: None,
+			created_at: 1_650_000_000,
+			updated_at: 1_650_000_000,
+		}
+	}
+
+	fn mock_claim(job: Option<job_dao::Job>) -> mockito::Mock {
+		job_dao::Claim::mock(
+			job_dao::ClaimInput {
+				worker: "worker-1".into(),
+				lease: 60,
+			},
+			job_dao::ClaimOutput(job),
+		)
+	}
+
+	fn mock_fail(error: &str, retry_in: Option<i64>) -> mockito::Mock {
+		job_dao::Fail::mock(
+			job_dao::FailInput {
+				id: 21,
+				worker: "worker-1".into(),
+				error: error.into(),
+				retry_in,
+			},
+			(),
+		)
+	}
+
+	#[test]
+	fn test_backoff() {
+		assert_eq!(super::backoff(1), 10);
+		assert_eq!(super::backoff(2), 20);
+		assert_eq!(super::backoff(4), 80);
+		assert_eq!(super::backoff(9), 2560);
+		assert_eq!(super::backoff(10), 3600);
+		assert_eq!(super::backoff(100), 3600);
+	}
+
+	#[test]
+	fn test_timeout_below_lease() {
+		for definition in super::definitions() {
+			let timeout = definition.action_type.timeout();
+
+			assert!(
+				timeout
+					.is_some_and(|timeout| i64::from(timeout) < super::DEFAULT_CONFIG.lease * 1000),
+				"{:?} must end before its lease",
+				definition.action_type,
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_enqueue_not_registered() {
+		run_test(|_| async {
+			let input = TestInput { id: 1, error: None };
+			let result =
+				super::enqueue::<TestJob, TestInput, (), AutomaticActionError>(&input, 0).await;
+
+			assert_eq!(
+				result,
+				Err(EnqueueError::NotRegistered(AutomaticActionType::Test))
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_next_empty() {
+		run_test(|_| async {
+			let _m = mock_claim(None);
+
+			let result =
+				super::run_next(&Application::default(), &definitions(), "worker-1", 60).await;
+
+			assert_eq!(result, Ok(false));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_next_ok() {
+		run_test(|_| async {
+			let _m1 = mock_claim(Some(job(0, r#"{"id":5,"error":null}"#, 1)));
+			let _m2 = job_dao::Complete::mock(
+				job_dao::CompleteInput {
+					id: 21,
+					worker: "worker-1".into(),
+				},
+				(),
+			);
+
+			let result =
+				super::run_next(&Application::default(), &definitions(), "worker-1", 60).await;
+
+			assert_eq!(result, Ok(true));
+			assert_eq!(ran(), vec![5]);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_next_retry() {
+		run_test(|helper| async move {
+			let _m1 = mock_claim(Some(job(0, r#"{"id":6,"error":"timeout"}"#, 2)));
+			let _m2 = mock_fail("Timeout(TimeoutError { timeout: 1 })", Some(20));
+
+			let result =
+				super::run_next(&Application::default(), &definitions(), "worker-1", 60).await;
+
+			assert_eq!(result, Ok(true));
+			assert_eq!(
+				helper.pop_log(),
+				Some("WARN - [job] the job 21 failed (attempt 2), retrying in 20s: Timeout(TimeoutError { timeout: 1 })".into()),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_next_exhausted() {
+		run_test(|helper| async move {
+			let _m1 = mock_claim(Some(job(0, r#"{"id":7,"error":"timeout"}"#, 3)));
+			let _m2 = mock_fail("Timeout(TimeoutError { timeout: 1 })", None);
+
+			let result =
+				super::run_next(&Application::default(), &definitions(), "worker-1", 60).await;
+
+			assert_eq!(result, Ok(true));
+			assert_eq!(
+				helper.pop_log(),
+				Some("ERROR - [job] the job 21 is dead (attempt 3): Timeout(TimeoutError { timeout: 1 })".into()),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_next_not_retried() {
+		run_test(|helper| async move {
+			let _m1 = mock_claim(Some(job(3, r#"{"id":8,"error":null}"#, 1)));
+			let _m2 = mock_fail("no job runner for the automatic action 3", None);
+
+			let result =
+				super::run_next(&Application::default(), &definitions(), "worker-1", 60).await;
+
+			assert_eq!(result, Ok(true));
+			assert_eq!(
+				helper.pop_log(),
+				Some("ERROR - [job] the job 21 is dead (attempt 1): no job runner for the automatic action 3".into()),
+			);
+		})
+		.await;
+	}
+}
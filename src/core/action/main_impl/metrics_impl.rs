@@ -0,0 +1,336 @@
+use std::{
+	collections::HashMap,
+	fmt::Write,
+	future::Future,
+	sync::{atomic::Ordering, Arc, RwLock},
+	time::{Duration, Instant},
+};
+
+use super::action_impl::ActionTypeWrapper;
+
+use crate::{
+	core::action::{
+		action_type::general_action_type::ActionType,
+		data::{
+			action_data::RequestContext,
+			interceptor_data::{ActionCall, ActionOutcome},
+			metrics_data::{
+				MetricLabels, MetricOutcome, MetricRegistry, MetricSeries, DURATION_BUCKETS,
+			},
+		},
+		definition::action_interceptor::ActionInterceptor,
+	},
+	lib::data::str::Str,
+};
+
+////////////////////////////////////////////////
+/////////////////// REGISTRY ///////////////////
+////////////////////////////////////////////////
+
+#[ctor::ctor]
+pub static ACTION_METRICS: MetricRegistry = MetricRegistry::new("action", "The executed actions.");
+
+#[ctor::ctor]
+pub static EXTERNAL_METRICS: MetricRegistry =
+	MetricRegistry::new("external", "The calls of the external actions.");
+
+impl MetricOutcome {
+	pub fn label(&self) -> &'static str {
+		match self {
+			MetricOutcome::Ok => "ok",
+			MetricOutcome::PublicError => "public_error",
+			MetricOutcome::PrivateError => "private_error",
+			MetricOutcome::Error => "error",
+		}
+	}
+}
+
+impl MetricSeries {
+	fn observe(&self, duration: Duration) {
+		let seconds = duration.as_secs_f64();
+		if let Some(bucket) = DURATION_BUCKETS.iter().position(|le| seconds <= *le) {
+			self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+		}
+		self.sum_micros
+			.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+		self.count.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+impl MetricRegistry {
+	pub fn new(name: &'static str, help: &'static str) -> Self {
+		Self {
+			name,
+			help,
+			series: RwLock::new(HashMap::new()),
+		}
+	}
+
+	pub fn observe(&self, labels: MetricLabels, duration: Duration) {
+		let series = self.series.read().unwrap().get(&labels).cloned();
+		let series = match series {
+			Some(series) => series,
+			None => self
+				.series
+				.write()
+				.unwrap()
+				.entry(labels)
+				.or_insert_with(|| Arc::new(MetricSeries::default()))
+				.clone(),
+		};
+
+		series.observe(duration);
+	}
+
+	/// Renders the counter and the latency histogram of the series, in the
+	/// Prometheus text exposition format.
+	pub fn render(&self, out: &mut String) {
+		let mut series = self
+			.series
+			.read()
+			.unwrap()
+			.iter()
+			.map(|(labels, series)| (labels.clone(), series.clone()))
+			.collect::<Vec<_>>();
+		series.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+		let name = self.name;
+		let help = self.help;
+
+		let _ = writeln!(out, "# HELP {name}_calls_total {help}");
+		let _ = writeln!(out, "# TYPE {name}_calls_total counter");
+		for (labels, series) in &series {
+			let count = series.count.load(Ordering::Relaxed);
+			let _ = writeln!(
+				out,
+				"{name}_calls_total{{{}}} {count}",
+				render_labels(labels)
+			);
+		}
+
+		let _ = writeln!(out, "# HELP {name}_duration_seconds {help}");
+		let _ = writeln!(out, "# TYPE {name}_duration_seconds histogram");
+		for (labels, series) in &series {
+			let labels = render_labels(labels);
+			let mut cumulative = 0;
+			for (le, bucket) in DURATION_BUCKETS.iter().zip(&series.buckets) {
+				cumulative += bucket.load(Ordering::Relaxed);
+				let _ = writeln!(
+					out,
+					"{name}_duration_seconds_bucket{{{labels},le=\"{le}\"}} {cumulative}"
+				);
+			}
+			let count = series.count.load(Ordering::Relaxed);
+			let sum = series.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+			let _ = writeln!(
+				out,
+				"{name}_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {count}"
+			);
+			let _ = writeln!(out, "{name}_duration_seconds_sum{{{labels}}} {sum}");
+			let _ = writeln!(out, "{name}_duration_seconds_count{{{labels}}} {count}");
+		}
+	}
+}
+
+fn render_labels(labels: &MetricLabels) -> String {
+	labels
+		.iter()
+		.map(|(name, value)| {
+			let value = value
+				.replace('\\', "\\\\")
+				.replace('"', "\\\"")
+				.replace('\n', "\\n");
+			format!("{name}=\"{value}\"")
+		})
+		.collect::<Vec<_>>()
+		.join(",")
+}
+
+/// The metrics of the actions and the external calls.
+pub fn render() -> String {
+	let mut out = String::new();
+	ACTION_METRICS.render(&mut out);
+	EXTERNAL_METRICS.render(&mut out);
+	out
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+fn outcome(action_outcome: &ActionOutcome) -> MetricOutcome {
+	match &action_outcome.error {
+		None => MetricOutcome::Ok,
+		Some(error) if error.private.is_some() => MetricOutcome::PrivateError,
+		Some(_) => MetricOutcome::PublicError,
+	}
+}
+
+pub struct MetricsInterceptor;
+
+#[rocket::async_trait]
+impl<T: ActionType, C: RequestContext> ActionInterceptor<T, C> for MetricsInterceptor {
+	async fn after(&self, call: &ActionCall<'_, T, C>, action_outcome: &ActionOutcome) {
+		let labels = vec![
+			("scope", T::scope().to_string().into()),
+			(
+				"action",
+				ActionTypeWrapper(call.action_context.action_type)
+					.to_string()
+					.into(),
+			),
+			("outcome", outcome(action_outcome).label().into()),
+		];
+
+		ACTION_METRICS.observe(labels, action_outcome.duration);
+	}
+}
+
+/// Runs the call of the external action, measuring it with the name.
+pub async fn external<O, E, F>(name: &'static str, future: F) -> Result<O, E>
+where
+	F: Future<Output = Result<O, E>>,
+{
+	let started = Instant::now();
+	let result = future.await;
+	let outcome = match result {
+		Ok(_) => MetricOutcome::Ok,
+		Err(_) => MetricOutcome::Error,
+	};
+
+	let labels: MetricLabels = vec![
+		("external", Str::from(name)),
+		("outcome", outcome.label().into()),
+	];
+	EXTERNAL_METRICS.observe(labels, started.elapsed());
+
+	result
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::time::Duration;
+
+	use chrono::Utc;
+
+	use crate::core::action::action_type::user_action_type::UserActionType;
+	use crate::core::action::data::action_data::{ActionContext, DescriptiveError, ErrorCategory};
+	use crate::core::action::data::interceptor_data::{ActionCall, ActionOutcome, OutcomeError};
+	use crate::core::action::data::metrics_data::{MetricOutcome, MetricRegistry};
+	use crate::core::action::data::user_action_data::tests::UserRequestContextBuilder;
+	use crate::core::action::definition::action_interceptor::ActionInterceptor;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_render() {
+		run_test(|_| async {
+			let registry = MetricRegistry::new("test", "The test calls.");
+			let labels = || vec![("name", "a\"b".into()), ("outcome", "ok".into())];
+
+			registry.observe(labels(), Duration::from_millis(20));
+			registry.observe(labels(), Duration::from_millis(300));
+			registry.observe(labels(), Duration::from_secs(20));
+
+			let mut out = String::new();
+			registry.render(&mut out);
+
+			let series = r#"name="a\"b",outcome="ok""#;
+			let expected = [
+				"# HELP test_calls_total The test calls.".to_string(),
+				"# TYPE test_calls_total counter".into(),
+				format!("test_calls_total{{{series}}} 3"),
+				"# HELP test_duration_seconds The test calls.".into(),
+				"# TYPE test_duration_seconds histogram".into(),
+				format!("test_duration_seconds_bucket{{{series},le=\"0.005\"}} 0"),
+				format!("test_duration_seconds_bucket{{{series},le=\"0.01\"}} 0"),
+				format!("test_duration_seconds_bucket{{{series},le=\"0.025\"}} 1"),
+				format!("test_duration_seconds_bucket{{{series},le=\"0.05\"}} 1"),
+				format!("test_duration_seconds_bucket{{{series},le=\"0.1\"}} 1"),
+				format!("test_duration_seconds_bucket{{{series},le=\"0.25\"}} 1"),
+				format!("test_duration_seconds_bucket{{{series},le=\"0.5\"}} 2"),
+				format!("test_duration_seconds_bucket{{{series},le=\"1\"}} 2"),
+				format!("test_duration_seconds_bucket{{{series},le=\"2.5\"}} 2"),
+				format!("test_duration_seconds_bucket{{{series},le=\"5\"}} 2"),
+				format!("test_duration_seconds_bucket{{{series},le=\"10\"}} 2"),
+				format!("test_duration_seconds_bucket{{{series},le=\"+Inf\"}} 3"),
+				format!("test_duration_seconds_sum{{{series}}} 20.32"),
+				format!("test_duration_seconds_count{{{series}}} 3"),
+			];
+
+			assert_eq!(out.lines().collect::<Vec<_>>(), expected);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_interceptor() {
+		run_test(|_| async {
+			let action_context = ActionContext {
+				action_type: UserActionType::Login,
+				context: Some(UserRequestContextBuilder::new().build()),
+			};
+			let call = ActionCall {
+				action_context: &action_context,
+				created_at: Utc::now(),
+				input: "()",
+			};
+			let error = |private: Option<DescriptiveError>| {
+				Some(OutcomeError {
+					category: ErrorCategory::Internal,
+					public: None,
+					private,
+				})
+			};
+
+			let cases = [
+				(None, MetricOutcome::Ok),
+				(error(None), MetricOutcome::PublicError),
+				(
+					error(Some(DescriptiveError::empty())),
+					MetricOutcome::PrivateError,
+				),
+			];
+
+			for (error, expected) in cases {
+				let action_outcome = ActionOutcome {
+					duration: Duration::from_millis(1),
+					error,
+				};
+				assert_eq!(super::outcome(&action_outcome), expected);
+
+				super::MetricsInterceptor
+					.after(&call, &action_outcome)
+					.await;
+			}
+
+			let out = super::render();
+			for outcome in ["ok", "public_error", "private_error"] {
+				let series = format!(r#"scope="User",action="Login",outcome="{outcome}""#);
+				assert!(out.contains(&format!("action_calls_total{{{series}}} ")));
+				assert!(out.contains(&format!("action_duration_seconds_count{{{series}}} ")));
+			}
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_external() {
+		run_test(|_| async {
+			let result = super::external("test_dao::Ok", async { Ok::<_, ()>(3) }).await;
+			assert_eq!(result, Ok(3));
+
+			let result = super::external("test_dao::Error", async { Err::<(), _>(4) }).await;
+			assert_eq!(result, Err(4));
+
+			let out = super::render();
+			assert!(out.contains(r#"external_calls_total{external="test_dao::Ok",outcome="ok"} 1"#));
+			assert!(out
+				.contains(r#"external_calls_total{external="test_dao::Error",outcome="error"} 1"#));
+		})
+		.await;
+	}
+}
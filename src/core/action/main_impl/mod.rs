@@ -1,4 +1,17 @@
 pub mod action_impl;
+pub mod audit_impl;
 pub mod automatic_action_impl;
+pub mod event_impl;
+pub mod idempotency_impl;
+pub mod interceptor_impl;
+pub mod job_impl;
+pub mod metrics_impl;
 pub mod moderator_action_impl;
+pub mod panic_impl;
+pub mod pipeline_impl;
+pub mod rate_limit_impl;
+pub mod registry_impl;
+pub mod schedule_impl;
+pub mod timeout_impl;
 pub mod user_action_impl;
+pub mod validation_impl;
@@ -1,37 +1,141 @@
+use super::user_action_impl::session_from_token;
+use super::{interceptor_impl::MODERATOR_INTERCEPTORS, pipeline_impl};
 use crate::core::action::{
 	action_type::general_action_type::ActionType,
 	data::{
-		action_data::{ActionContext, DescriptiveError, ErrorData, RequestInput},
+		action_data::{
+			Application, DescriptiveError, ErrorCategory, ErrorData, PanicError, TimeoutError,
+		},
+		idempotency_data::IdempotencyError,
+		interceptor_data::InterceptorError,
 		moderator_action_data::{
 			ModeratorActionError, ModeratorErrorInfo, ModeratorOutputInfo, ModeratorRequestContext,
 			ModeratorSession,
 		},
+		validation_data::ValidationError,
 	},
 };
 use crate::{
-	core::action::{
-		data::moderator_action_data::ModeratorActionInput,
-		definition::{
-			action::{Action, ActionError, ActionInput, ActionOutput, ModeratorAction},
-			action_helpers::DescriptiveInfo,
+	core::{
+		action::{
+			action_type::moderator_action_type::ModeratorActionType,
+			data::{
+				action_data::{AuthBasicContext, RequestBasicData},
+				audit_data::AuditActor,
+				moderator_action_data::{ModeratorActionInput, ModeratorRequestInput},
+				user_action_data::{UserActionError, UserAuthSession, UserSession},
+			},
+			definition::{
+				action::{Action, ActionError, ActionInput, ActionOutput, ModeratorAction},
+				action_helpers::{AuditInfo, DescriptiveInfo, PipelineError, PipelineInfo},
+			},
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
 		},
 	},
-	lib::data::str::Str,
+	external::dao::main::moderator_dao,
+	lib::{data::str::Str, traits::async_from::AsyncFrom},
+	shared::data::user_data::UserId,
 };
 
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-impl<I: ActionInput> ActionInput for RequestInput<I, ModeratorRequestContext> {}
-
 impl DescriptiveInfo for ModeratorRequestContext {
 	fn description(&self) -> Str {
 		let ModeratorRequestContext {
 			session: ModeratorSession { user_id, .. },
+			request,
 			..
 		} = &self;
-		format!("moderator({user_id:?})").into()
+		format!(
+			"moderator({user_id:?}) {request}",
+			request = request.description()
+		)
+		.into()
+	}
+}
+
+impl PipelineInfo for ModeratorRequestContext {
+	fn application(&self) -> &Application {
+		&self.application
+	}
+
+	fn idempotency_key(&self) -> Option<&Str> {
+		self.request.idempotency_key.as_ref()
+	}
+}
+
+impl AuditInfo for ModeratorRequestContext {
+	fn actor(&self) -> AuditActor {
+		AuditActor::Moderator(self.session.user_id)
+	}
+
+	fn ip(&self) -> Option<Str> {
+		Some(self.request.ip.clone())
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+// the moderator is authenticated with the session of the (confirmed) user
+#[rocket::async_trait]
+impl<I: Send> AsyncFrom<RequestBasicData<I>>
+	for Result<ModeratorRequestInput<I>, ModeratorActionError>
+{
+	async fn from(input: RequestBasicData<I>) -> Self {
+		let RequestBasicData { data, context } = input;
+		let AuthBasicContext {
+			token,
+			application,
+			request,
+		} = context;
+
+		let token = token.ok_or(ModeratorActionError::UserError(
+			UserActionError::Unauthenticated,
+		))?;
+		let session = session_from_token(token)
+			.await
+			.map_err(ModeratorActionError::UserError)?;
+
+		let user_id = match session {
+			UserSession::Auth(UserAuthSession { user_id, .. }) => user_id,
+			_ => {
+				return Err(ModeratorActionError::UserError(
+					UserActionError::Unauthenticated,
+				))
+			}
+		};
+
+		let moderator_dao::SelectOutput(moderator) =
+			moderator_dao::Select::run(moderator_dao::SelectInput::ByUser(UserId(user_id as i64)))
+				.await
+				.map_err(ModeratorActionError::SessionError)?;
+		let moderator_dao::Moderator {
+			admin,
+			allowed_actions,
+			..
+		} = moderator.ok_or(ModeratorActionError::NotModerator)?;
+
+		Ok(ModeratorRequestInput {
+			data,
+			context: ModeratorRequestContext {
+				application,
+				session: ModeratorSession {
+					admin,
+					user_id,
+					allowed_actions: allowed_actions
+						.into_iter()
+						.filter_map(ModeratorActionType::from_id)
+						.collect(),
+				},
+				request,
+			},
+		})
 	}
 }
 
@@ -42,21 +146,84 @@ impl DescriptiveInfo for ModeratorRequestContext {
 impl ActionError for ModeratorActionError {
 	fn private_error(&self) -> Option<DescriptiveError> {
 		match self {
+			ModeratorActionError::UserError(error) => error.private_error(),
+			ModeratorActionError::NotModerator => None,
+			ModeratorActionError::SessionError(error) => error.private_error(),
 			ModeratorActionError::NotAllowed(data) => Some(DescriptiveError::data(data)),
+			ModeratorActionError::InvalidInput(error) => error.private_error(),
+			ModeratorActionError::Timeout(error) => error.private_error(),
+			ModeratorActionError::Panic(error) => error.private_error(),
+			ModeratorActionError::Intercepted(error) => error.private_error(),
+			ModeratorActionError::Outbox(error) => error.private_error(),
+			ModeratorActionError::Idempotency(error) => error.private_error(),
 		}
 	}
 
 	fn public_error(&self) -> Option<ErrorData> {
 		match self {
+			ModeratorActionError::UserError(error) => error.public_error(),
+			ModeratorActionError::NotModerator => Self::error_msg(
+				"moderator.not_moderator",
+				"You must be a moderator to execute this action.".into(),
+			),
+			ModeratorActionError::SessionError(error) => error.public_error(),
 			ModeratorActionError::NotAllowed(action_type) => Self::error_msg(
+				"moderator.not_allowed",
 				format!(
 					"You are not allowed to execute this action ({action_id}).",
 					action_id = action_type.id()
 				)
 				.into(),
 			),
+			ModeratorActionError::InvalidInput(error) => error.public_error(),
+			ModeratorActionError::Timeout(error) => error.public_error(),
+			ModeratorActionError::Panic(error) => error.public_error(),
+			ModeratorActionError::Intercepted(error) => error.public_error(),
+			ModeratorActionError::Outbox(error) => error.public_error(),
+			ModeratorActionError::Idempotency(error) => error.public_error(),
 		}
 	}
+
+	fn category(&self) -> ErrorCategory {
+		match self {
+			ModeratorActionError::UserError(error) => error.category(),
+			ModeratorActionError::NotModerator => ErrorCategory::Forbidden,
+			ModeratorActionError::SessionError(error) => error.category(),
+			ModeratorActionError::NotAllowed(_) => ErrorCategory::Forbidden,
+			ModeratorActionError::InvalidInput(error) => error.category(),
+			ModeratorActionError::Timeout(error) => error.category(),
+			ModeratorActionError::Panic(error) => error.category(),
+			ModeratorActionError::Intercepted(error) => error.category(),
+			ModeratorActionError::Outbox(error) => error.category(),
+			ModeratorActionError::Idempotency(error) => error.category(),
+		}
+	}
+}
+
+impl PipelineError for ModeratorActionError {
+	fn invalid_input(error: ValidationError) -> Self {
+		ModeratorActionError::InvalidInput(error)
+	}
+
+	fn timeout(error: TimeoutError) -> Self {
+		ModeratorActionError::Timeout(error)
+	}
+
+	fn panic(error: PanicError) -> Self {
+		ModeratorActionError::Panic(error)
+	}
+
+	fn outbox(error: ExternalException) -> Self {
+		ModeratorActionError::Outbox(error)
+	}
+
+	fn idempotency(error: IdempotencyError) -> Self {
+		ModeratorActionError::Idempotency(error)
+	}
+
+	fn intercepted(error: InterceptorError) -> Self {
+		ModeratorActionError::Intercepted(Box::new(error))
+	}
 }
 
 ////////////////////////////////////////////////
@@ -78,60 +245,24 @@ where
 	) -> Result<ModeratorOutputInfo<O>, ModeratorErrorInfo<E>> {
 		let action_type = Self::action_type();
 
-		let context = input
-			.as_ref()
-			.map(|ok_input| Some(ok_input.context.clone()))
-			.unwrap_or(None);
+		// the session is checked before the action is created
+		let new = |input: ModeratorRequestInput<I>| async move {
+			let session = &input.context.session;
+			if !(session.admin || session.allowed_actions.contains(&action_type)) {
+				return Err(E::from(ModeratorActionError::NotAllowed(action_type)));
+			}
 
-		let action_context = ActionContext {
-			action_type,
-			context,
+			Self::new(input).await
 		};
 
-		match input {
-			Ok(ok_input) => {
-				let allowed = ok_input.context.session.admin
-					|| ok_input
-						.context
-						.session
-						.allowed_actions
-						.contains(&action_type);
-
-				if !allowed {
-					Err(ModeratorErrorInfo {
-						action_context,
-						error: E::from(ModeratorActionError::NotAllowed(action_type)),
-					})
-				} else {
-					let action_result = Self::new(ok_input).await;
-
-					match action_result {
-						Ok(action) => {
-							let result = action.run_inner().await;
-
-							match result {
-								Ok(data) => Ok(ModeratorOutputInfo {
-									action_context,
-									data,
-								}),
-								Err(error) => Err(ModeratorErrorInfo {
-									action_context,
-									error,
-								}),
-							}
-						}
-						Err(error) => Err(ModeratorErrorInfo {
-							action_context,
-							error,
-						}),
-					}
-				}
-			}
-			Err(error) => Err(ModeratorErrorInfo {
-				action_context,
-				error: E::from(error),
-			}),
-		}
+		pipeline_impl::run(
+			&MODERATOR_INTERCEPTORS.list(),
+			action_type,
+			input,
+			new,
+			Self::run_inner,
+		)
+		.await
 	}
 }
 
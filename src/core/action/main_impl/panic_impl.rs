@@ -0,0 +1,120 @@
+use std::{any::Any, future::Future, panic::AssertUnwindSafe};
+
+use futures::FutureExt;
+
+use crate::core::action::{
+	data::action_data::{DescriptiveError, ErrorCategory, ErrorData, PanicError},
+	definition::action::ActionError,
+};
+
+////////////////////////////////////////////////
+//////////////////// ACTION ////////////////////
+////////////////////////////////////////////////
+
+/// Runs the future, returning the panic as an error instead of unwinding
+/// through the caller.
+pub async fn run<F: Future>(future: F) -> Result<F::Output, PanicError> {
+	AssertUnwindSafe(future)
+		.catch_unwind()
+		.await
+		.map_err(|payload| PanicError {
+			msg: message(payload.as_ref()).into(),
+		})
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+	if let Some(msg) = payload.downcast_ref::<&str>() {
+		msg.to_string()
+	} else if let Some(msg) = payload.downcast_ref::<String>() {
+		msg.clone()
+	} else {
+		"unknown panic".into()
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+impl ActionError for PanicError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		Some(DescriptiveError {
+			msg: Some(format!("the action panicked: {msg}", msg = self.msg).into()),
+			data: None,
+			source: None,
+		})
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		None
+	}
+
+	fn category(&self) -> ErrorCategory {
+		ErrorCategory::Internal
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use crate::core::action::data::action_data::{DescriptiveError, ErrorCategory, PanicError};
+	use crate::core::action::definition::action::ActionError;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_run() {
+		run_test(|_| async {
+			assert_eq!(super::run(async { 3 }).await, Ok(3));
+
+			let result = super::run(async { panic!("static message") }).await;
+			assert_eq!(
+				result,
+				Err::<(), _>(PanicError {
+					msg: "static message".into()
+				})
+			);
+
+			let id = 7;
+			let result = super::run(async move { panic!("formatted message {id}") }).await;
+			assert_eq!(
+				result,
+				Err::<(), _>(PanicError {
+					msg: "formatted message 7".into()
+				})
+			);
+
+			let result = super::run(async { std::panic::panic_any(7) }).await;
+			assert_eq!(
+				result,
+				Err::<(), _>(PanicError {
+					msg: "unknown panic".into()
+				})
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_error() {
+		run_test(|_| async {
+			let error = PanicError {
+				msg: "not yet implemented".into(),
+			};
+
+			assert_eq!(
+				error.private_error(),
+				Some(DescriptiveError {
+					msg: Some("the action panicked: not yet implemented".into()),
+					data: None,
+					source: None,
+				})
+			);
+			assert_eq!(error.public_error(), None);
+			assert_eq!(error.category(), ErrorCategory::Internal);
+		})
+		.await;
+	}
+}
@@ -0,0 +1,97 @@
+use std::{future::Future, sync::Arc};
+
+use super::{audit_impl, event_impl, idempotency_impl, interceptor_impl, panic_impl, timeout_impl};
+
+use crate::core::action::{
+	action_type::general_action_type::ActionType,
+	data::action_data::{ActionContext, ActionErrorInfo, ActionResultInfo, RequestInput},
+	definition::{
+		action::{ActionError, ActionInput, ActionOutput},
+		action_helpers::{AuditInfo, DescriptiveRequestContext, PipelineError, PipelineInfo},
+		action_interceptor::ActionInterceptor,
+	},
+};
+
+////////////////////////////////////////////////
+//////////////////// ACTION ////////////////////
+////////////////////////////////////////////////
+
+/// Runs the action of any scope through the layers around it, from the
+/// outermost: the interceptors, the idempotency, the delivery of its events,
+/// the timeout and the panics. `new` receives the input of the scope (and may
+/// reject the caller), `run_inner` runs the action it returned.
+pub async fn run<T, C, I, O, E, S, A, N, NF, R, RF>(
+	interceptors: &[Arc<dyn ActionInterceptor<T, C>>],
+	action_type: T,
+	input: Result<RequestInput<I, C>, S>,
+	new: N,
+	run_inner: R,
+) -> Result<ActionResultInfo<T, C, O>, ActionErrorInfo<T, C, E>>
+where
+	T: ActionType,
+	C: DescriptiveRequestContext + AuditInfo + PipelineInfo,
+	I: ActionInput,
+	O: ActionOutput,
+	E: ActionError + From<S>,
+	S: PipelineError + Send,
+	N: FnOnce(RequestInput<I, C>) -> NF + Send,
+	NF: Future<Output = Result<A, E>> + Send,
+	R: FnOnce(A) -> RF + Send,
+	RF: Future<Output = Result<O, E>> + Send,
+{
+	let action_context = ActionContext {
+		action_type,
+		context: input.as_ref().ok().map(|ok_input| ok_input.context.clone()),
+	};
+
+	let input_summary = input
+		.as_ref()
+		.map(|ok_input| audit_impl::summary(&ok_input.data))
+		.unwrap_or_default();
+	let timeout_context = action_context.clone();
+
+	let action = async move {
+		let ok_input = input?;
+
+		let context = &ok_input.context;
+		let idempotent_request = idempotency_impl::request(
+			action_type,
+			context,
+			context.idempotency_key(),
+			&ok_input.data,
+		);
+		let timeout = timeout_impl::timeout(action_type, context.application());
+		let application = context.application().clone();
+
+		let action = async {
+			// the input is only reported as invalid once it passed the
+			// authentication, so that its fields don't leak to anyone
+			let action = panic_impl::run(async {
+				let validation = ok_input.data.validate().await;
+				let action = new(ok_input).await?;
+				validation.map_err(S::invalid_input)?;
+				run_inner(action).await
+			});
+			timeout_impl::run(&timeout_context, timeout, action)
+				.await
+				.map_err(S::timeout)?
+				.map_err(S::panic)?
+		};
+
+		let action = event_impl::run(&application, action, |error| E::from(S::outbox(error)));
+
+		idempotency_impl::run(idempotent_request, action, |error| {
+			E::from(S::idempotency(error))
+		})
+		.await
+	};
+
+	interceptor_impl::run(
+		interceptors,
+		action_context,
+		input_summary,
+		action,
+		|error| E::from(S::intercepted(error)),
+	)
+	.await
+}
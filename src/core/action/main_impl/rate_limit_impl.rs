@@ -0,0 +1,651 @@
+use std::{
+	collections::{BTreeSet, HashMap},
+	sync::OnceLock,
+};
+
+use chrono::Utc;
+use rocket::fairing::AdHoc;
+
+use crate::{
+	core::action::{
+		action_type::general_action_type::ActionType,
+		data::{
+			action_data::{DescriptiveError, ErrorCategory, ErrorData, RequestContext},
+			audit_data::AuditActor,
+			interceptor_data::{ActionCall, InterceptorError},
+			rate_limit_data::{RateLimit, RateLimitBucket, RateLimitError, RateLimitKey},
+		},
+		definition::{
+			action::ActionError, action_helpers::AuditInfo, action_interceptor::ActionInterceptor,
+		},
+	},
+	core::external::definition::external::ExternalAction,
+	external::dao::main::rate_limit_dao,
+	lib::data::str::Str,
+};
+
+// the memory buckets are dropped when they are full again; past this amount of
+// keys, the ones closest to be full are dropped too
+const MAX_MEMORY_KEYS: usize = 10_000;
+
+////////////////////////////////////////////////
+/////////////////// BACKEND ////////////////////
+////////////////////////////////////////////////
+
+// The buckets are kept in memory by default; with the `sqlite` backend they
+// are stored in the main database, so that the limits survive restarts.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum RateLimitConfig {
+	Memory,
+	Sqlite,
+}
+
+static CONFIG: OnceLock<RateLimitConfig> = OnceLock::new();
+
+#[cfg(not(test))]
+#[ctor::ctor]
+static MEMORY_BUCKETS: std::sync::Mutex<MemoryBuckets> =
+	std::sync::Mutex::new(MemoryBuckets::new(MAX_MEMORY_KEYS));
+
+pub fn stage() -> AdHoc {
+	AdHoc::on_ignite("Rate Limit", |rocket| async {
+		let config = rocket
+			.figment()
+			.extract_inner::<RateLimitConfig>("rate_limit")
+			.unwrap_or(RateLimitConfig::Memory);
+
+		let _ = CONFIG.set(config);
+		rocket
+	})
+}
+
+////////////////////////////////////////////////
+//////////////////// BUCKET ////////////////////
+////////////////////////////////////////////////
+
+/// Refills the bucket for the time elapsed since its last update (a missing
+/// bucket is full) and takes a call from it.
+pub fn take(
+	bucket: Option<RateLimitBucket>,
+	capacity: u32,
+	window: u32,
+	now: i64,
+) -> (RateLimitBucket, Result<(), RateLimitError>) {
+	let capacity = f64::from(capacity.max(1));
+	let window_ms = f64::from(window.max(1)) * 1000.0;
+	let tokens = bucket
+		.map(|RateLimitBucket { tokens, updated_at }| {
+			let elapsed = (now - updated_at).max(0) as f64;
+			(tokens + elapsed * capacity / window_ms).min(capacity)
+		})
+		.unwrap_or(capacity);
+
+	if tokens >= 1.0 {
+		let bucket = RateLimitBucket {
+			tokens: tokens - 1.0,
+			updated_at: now,
+		};
+		(bucket, Ok(()))
+	} else {
+		let retry_after_ms = (1.0 - tokens) * window_ms / capacity;
+		let retry_after = (retry_after_ms / 1000.0).ceil().max(1.0) as u32;
+		let bucket = RateLimitBucket {
+			tokens,
+			updated_at: now,
+		};
+		(bucket, Err(RateLimitError { retry_after }))
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+struct MemoryBucket {
+	bucket: RateLimitBucket,
+	capacity: u32,
+	window: u32,
+}
+
+impl MemoryBucket {
+	// the unix milliseconds from which the bucket is full again, with its own
+	// limit
+	fn full_at(&self) -> i64 {
+		let capacity = f64::from(self.capacity.max(1));
+		let window_ms = f64::from(self.window.max(1)) * 1000.0;
+		let missing = (capacity - self.bucket.tokens).max(0.0);
+		self.bucket.updated_at + (missing * window_ms / capacity).ceil() as i64
+	}
+}
+
+/// The buckets of the memory backend, indexed by the time at which they are
+/// full again, so that they can be dropped (like a missing bucket) without
+/// going through all of them.
+pub struct MemoryBuckets {
+	max_keys: usize,
+	buckets: HashMap<String, MemoryBucket>,
+	full_at: BTreeSet<(i64, String)>,
+}
+
+impl MemoryBuckets {
+	pub fn new(max_keys: usize) -> Self {
+		Self {
+			max_keys,
+			buckets: HashMap::new(),
+			full_at: BTreeSet::new(),
+		}
+	}
+
+	fn remove(&mut self, key: &str) -> Option<MemoryBucket> {
+		let bucket = self.buckets.remove(key)?;
+		self.full_at.remove(&(bucket.full_at(), key.to_string()));
+		Some(bucket)
+	}
+
+	fn take(&mut self, key: String, limit: &RateLimit, now: i64) -> Result<(), RateLimitError> {
+		let RateLimit {
+			capacity, window, ..
+		} = *limit;
+
+		while let Some((full_at, full_key)) = self.full_at.first() {
+			if *full_at > now {
+				break;
+			}
+			let full_key = full_key.clone();
+			self.remove(&full_key);
+		}
+
+		let previous = self.remove(&key);
+
+		// the limit of the dropped key is reset, so it's the one that loses
+		// the fewest calls
+		if previous.is_none() && self.buckets.len() >= self.max_keys {
+			if let Some((_, full_key)) = self.full_at.first() {
+				let full_key = full_key.clone();
+				self.remove(&full_key);
+			}
+		}
+
+		let (bucket, result) = take(
+			previous.map(|previous| previous.bucket),
+			capacity,
+			window,
+			now,
+		);
+		let bucket = MemoryBucket {
+			bucket,
+			capacity,
+			window,
+		};
+		self.full_at.insert((bucket.full_at(), key.clone()));
+		self.buckets.insert(key, bucket);
+		result
+	}
+}
+
+async fn check(
+	config: RateLimitConfig,
+	key: String,
+	limit: &RateLimit,
+	now: i64,
+) -> Result<(), RateLimitError> {
+	match config {
+		RateLimitConfig::Memory => with_memory_buckets(|buckets| buckets.take(key, limit, now)),
+		RateLimitConfig::Sqlite => {
+			let input = rate_limit_dao::TakeInput {
+				key,
+				capacity: limit.capacity,
+				window: limit.window,
+				now,
+			};
+
+			// the action isn't blocked when the limit can't be checked
+			match rate_limit_dao::Take::run(input).await {
+				Ok(rate_limit_dao::TakeOutput { retry_after }) => match retry_after {
+					Some(retry_after) => Err(RateLimitError { retry_after }),
+					None => Ok(()),
+				},
+				Err(error) => {
+					error!("[rate limit] the limit could not be checked: {error:?}");
+					Ok(())
+				}
+			}
+		}
+	}
+}
+
+#[cfg(not(test))]
+fn config() -> RateLimitConfig {
+	CONFIG.get().copied().unwrap_or(RateLimitConfig::Memory)
+}
+
+#[cfg(not(test))]
+fn now() -> i64 {
+	Utc::now().timestamp_millis()
+}
+
+#[cfg(not(test))]
+fn with_memory_buckets<R>(f: impl FnOnce(&mut MemoryBuckets) -> R) -> R {
+	let mut buckets = MEMORY_BUCKETS
+		.lock()
+		.unwrap_or_else(|error| error.into_inner());
+	f(&mut buckets)
+}
+
+// the tests have their own backend, clock and buckets, so that they don't
+// share their limits
+#[cfg(test)]
+fn config() -> RateLimitConfig {
+	tests::CONFIG.with(std::cell::Cell::get)
+}
+
+#[cfg(test)]
+fn now() -> i64 {
+	tests::NOW
+		.with(std::cell::Cell::get)
+		.unwrap_or_else(|| Utc::now().timestamp_millis())
+}
+
+#[cfg(test)]
+fn with_memory_buckets<R>(f: impl FnOnce(&mut MemoryBuckets) -> R) -> R {
+	tests::BUCKETS.with(|buckets| f(&mut buckets.borrow_mut()))
+}
+
+////////////////////////////////////////////////
+//////////////////// ACTION ////////////////////
+////////////////////////////////////////////////
+
+/// The key of the calls; the user and moderator keys fall back to the ip
+/// when the actor isn't one.
+fn key<C: AuditInfo>(limit: &RateLimit, context: &C) -> Option<String> {
+	let ip = || {
+		context
+			.ip()
+			.filter(|ip| !ip.is_empty())
+			.map(|ip| format!("ip:{ip}"))
+	};
+
+	match (limit.key, context.actor()) {
+		(RateLimitKey::User, AuditActor::User(user_id)) => Some(format!("user:{user_id}")),
+		(RateLimitKey::Moderator, AuditActor::Moderator(moderator_id)) => {
+			Some(format!("moderator:{moderator_id}"))
+		}
+		_ => ip(),
+	}
+}
+
+pub struct RateLimitInterceptor;
+
+#[rocket::async_trait]
+impl<T, C> ActionInterceptor<T, C> for RateLimitInterceptor
+where
+	T: ActionType,
+	C: RequestContext + AuditInfo,
+{
+	async fn before(&self, call: &ActionCall<'_, T, C>) -> Result<(), InterceptorError> {
+		let action_type = call.action_context.action_type;
+		let limit = match action_type.rate_limit() {
+			Some(limit) => limit,
+			None => return Ok(()),
+		};
+		let key = call
+			.action_context
+			.context
+			.as_ref()
+			.and_then(|context| key(&limit, context));
+
+		match key {
+			Some(key) => {
+				let key = format!("{}:{}:{key}", T::scope(), action_type.id());
+				check(config(), key, &limit, now())
+					.await
+					.map_err(InterceptorError::from)
+			}
+			None => Ok(()),
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+impl ActionError for RateLimitError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		None
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		let retry_after = self.retry_after;
+
+		Some(ErrorData {
+			code: "action.rate_limited".into(),
+			msg: format!("Too many requests. Please try again in {retry_after} seconds.").into(),
+			params: Some(HashMap::from([(
+				Str::from("retry_after"),
+				Str::from(retry_after.to_string()),
+			)])),
+		})
+	}
+
+	fn category(&self) -> ErrorCategory {
+		ErrorCategory::RateLimited
+	}
+}
+
+impl From<RateLimitError> for InterceptorError {
+	fn from(error: RateLimitError) -> Self {
+		Self {
+			category: error.category(),
+			public: error.public_error(),
+			private: error.private_error(),
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::{
+		cell::{Cell, RefCell},
+		collections::HashMap,
+	};
+
+	use chrono::Utc;
+
+	use crate::core::action::action_type::general_action_type::{ActionScope, ActionType};
+	use crate::core::action::data::action_data::tests::RequestBuilder;
+	use crate::core::action::data::action_data::{ActionContext, ErrorCategory, ErrorData};
+	use crate::core::action::data::interceptor_data::{ActionCall, InterceptorError};
+	use crate::core::action::data::rate_limit_data::{
+		RateLimit, RateLimitBucket, RateLimitError, RateLimitKey,
+	};
+	use crate::core::action::data::user_action_data::tests::{
+		UserAuthSessionBuilder, UserRequestContextBuilder,
+	};
+	use crate::core::action::data::user_action_data::{UserRequestContext, UserSession};
+	use crate::core::action::definition::action_interceptor::ActionInterceptor;
+	use crate::core::action::main_impl::rate_limit_impl::{MemoryBuckets, RateLimitConfig};
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::rate_limit_dao;
+	use crate::lib::data::str::Str;
+	use crate::tests::test_utils::tests::run_test;
+
+	thread_local! {
+		pub static BUCKETS: RefCell<MemoryBuckets> =
+			RefCell::new(MemoryBuckets::new(super::MAX_MEMORY_KEYS));
+		pub static CONFIG: Cell<RateLimitConfig> = const { Cell::new(RateLimitConfig::Memory) };
+		pub static NOW: Cell<Option<i64>> = const { Cell::new(None) };
+	}
+
+	fn set_config(config: RateLimitConfig) {
+		CONFIG.with(|cell| cell.set(config));
+	}
+
+	fn set_now(now: i64) {
+		NOW.with(|cell| cell.set(Some(now)));
+	}
+
+	#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+	enum TestActionType {
+		Unlimited,
+		ByIp,
+		ByUser,
+	}
+
+	impl ActionType for TestActionType {
+		fn scope() -> ActionScope {
+			ActionScope::User
+		}
+
+		fn id(&self) -> u32 {
+			match self {
+				TestActionType::Unlimited => 0,
+				TestActionType::ByIp => 1,
+				TestActionType::ByUser => 2,
+			}
+		}
+
+		fn from_id(_: u32) -> Option<Self> {
+			None
+		}
+
+		fn rate_limit(&self) -> Option<RateLimit> {
+			let limit = |key| {
+				Some(RateLimit {
+					key,
+					capacity: 2,
+					window: 10,
+				})
+			};
+
+			match self {
+				TestActionType::Unlimited => None,
+				TestActionType::ByIp => limit(RateLimitKey::Ip),
+				TestActionType::ByUser => limit(RateLimitKey::User),
+			}
+		}
+	}
+
+	fn context(ip: &'static str, user_id: Option<u64>) -> UserRequestContext {
+		let builder =
+			UserRequestContextBuilder::new().request(RequestBuilder::new().ip(ip.into()).build());
+		match user_id {
+			Some(user_id) => builder
+				.session(UserSession::Auth(
+					UserAuthSessionBuilder::new().user_id(user_id).build(),
+				))
+				.build(),
+			None => builder.build(),
+		}
+	}
+
+	async fn before(
+		action_type: TestActionType,
+		context: UserRequestContext,
+	) -> Result<(), InterceptorError> {
+		let action_context = ActionContext {
+			action_type,
+			context: Some(context),
+		};
+		let call = ActionCall {
+			action_context: &action_context,
+			created_at: Utc::now(),
+			input: "()",
+		};
+		super::RateLimitInterceptor.before(&call).await
+	}
+
+	fn limited(retry_after: u32) -> InterceptorError {
+		RateLimitError { retry_after }.into()
+	}
+
+	#[tokio::test]
+	async fn test_take() {
+		run_test(|_| async {
+			let (bucket, result) = super::take(None, 2, 10, 1_000);
+			assert_eq!(
+				bucket,
+				RateLimitBucket {
+					tokens: 1.0,
+					updated_at: 1_000,
+				}
+			);
+			assert_eq!(result, Ok(()));
+
+			let (bucket, result) = super::take(Some(bucket), 2, 10, 1_000);
+			assert_eq!(bucket.tokens, 0.0);
+			assert_eq!(result, Ok(()));
+
+			let (bucket, result) = super::take(Some(bucket), 2, 10, 3_500);
+			assert_eq!(bucket.tokens, 0.5);
+			assert_eq!(result, Err(RateLimitError { retry_after: 3 }));
+
+			let (bucket, result) = super::take(Some(bucket), 2, 10, 6_000);
+			assert_eq!(bucket.tokens, 0.0);
+			assert_eq!(result, Ok(()));
+
+			let (bucket, result) = super::take(Some(bucket), 2, 10, 100_000);
+			assert_eq!(bucket.tokens, 1.0);
+			assert_eq!(result, Ok(()));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_memory_buckets() {
+		run_test(|_| async {
+			let keys = |buckets: &MemoryBuckets| {
+				assert_eq!(buckets.buckets.len(), buckets.full_at.len());
+				let mut keys: Vec<_> = buckets.buckets.keys().cloned().collect();
+				keys.sort();
+				keys
+			};
+			let short = TestActionType::ByIp.rate_limit().unwrap();
+			let long = RateLimit {
+				key: RateLimitKey::Ip,
+				capacity: 1,
+				window: 100,
+			};
+
+			let mut buckets = MemoryBuckets::new(3);
+			assert_eq!(buckets.take("a".into(), &short, 0), Ok(()));
+			assert_eq!(buckets.take("b".into(), &long, 0), Ok(()));
+
+			// only the full buckets are dropped, each with its own limit
+			assert_eq!(buckets.take("c".into(), &short, 6_000), Ok(()));
+			assert_eq!(keys(&buckets), ["b", "c"]);
+			assert_eq!(
+				buckets.take("b".into(), &long, 6_000),
+				Err(RateLimitError { retry_after: 94 })
+			);
+
+			// past the maximum, the bucket closest to be full is dropped
+			assert_eq!(buckets.take("d".into(), &short, 6_000), Ok(()));
+			assert_eq!(buckets.take("e".into(), &short, 7_000), Ok(()));
+			assert_eq!(keys(&buckets), ["b", "d", "e"]);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_interceptor_memory() {
+		run_test(|_| async {
+			set_config(RateLimitConfig::Memory);
+			set_now(1_000);
+
+			for _ in 0..3 {
+				assert_eq!(
+					before(TestActionType::Unlimited, context("10.0.0.1", None)).await,
+					Ok(())
+				);
+			}
+
+			assert_eq!(
+				before(TestActionType::ByIp, context("10.0.0.1", None)).await,
+				Ok(())
+			);
+			assert_eq!(
+				before(TestActionType::ByIp, context("10.0.0.1", Some(3))).await,
+				Ok(())
+			);
+			assert_eq!(
+				before(TestActionType::ByIp, context("10.0.0.1", None)).await,
+				Err(limited(5))
+			);
+			assert_eq!(
+				before(TestActionType::ByIp, context("10.0.0.2", None)).await,
+				Ok(())
+			);
+
+			// without a user, the user limit is applied to the ip
+			assert_eq!(
+				before(TestActionType::ByUser, context("10.0.0.1", Some(3))).await,
+				Ok(())
+			);
+			assert_eq!(
+				before(TestActionType::ByUser, context("10.0.0.2", Some(3))).await,
+				Ok(())
+			);
+			assert_eq!(
+				before(TestActionType::ByUser, context("10.0.0.3", Some(3))).await,
+				Err(limited(5))
+			);
+			assert_eq!(
+				before(TestActionType::ByUser, context("10.0.0.1", None)).await,
+				Ok(())
+			);
+
+			set_now(6_000);
+			assert_eq!(
+				before(TestActionType::ByIp, context("10.0.0.1", None)).await,
+				Ok(())
+			);
+			assert_eq!(
+				before(TestActionType::ByUser, context("10.0.0.1", Some(3))).await,
+				Ok(())
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_interceptor_sqlite() {
+		run_test(|_| async {
+			set_config(RateLimitConfig::Sqlite);
+			set_now(1_000);
+
+			let mock = |ip: &str, retry_after| {
+				rate_limit_dao::Take::mock(
+					rate_limit_dao::TakeInput {
+						key: format!("{}:1:ip:{ip}", ActionScope::User),
+						capacity: 2,
+						window: 10,
+						now: 1_000,
+					},
+					rate_limit_dao::TakeOutput { retry_after },
+				)
+			};
+			let _m = [mock("10.0.0.1", None), mock("10.0.0.2", Some(5))];
+
+			assert_eq!(
+				before(TestActionType::Unlimited, context("10.0.0.1", None)).await,
+				Ok(())
+			);
+			assert_eq!(
+				before(TestActionType::ByIp, context("10.0.0.1", None)).await,
+				Ok(())
+			);
+			assert_eq!(
+				before(TestActionType::ByIp, context("10.0.0.2", None)).await,
+				Err(limited(5))
+			);
+			// the memory buckets aren't used
+			assert!(BUCKETS.with(|buckets| buckets.borrow().buckets.is_empty()));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_error() {
+		run_test(|_| async {
+			let error = InterceptorError::from(RateLimitError { retry_after: 42 });
+			assert_eq!(
+				error,
+				InterceptorError {
+					category: ErrorCategory::RateLimited,
+					public: Some(ErrorData {
+						code: "action.rate_limited".into(),
+						msg: "Too many requests. Please try again in 42 seconds.".into(),
+						params: Some(HashMap::from([
+							(Str::from("retry_after"), Str::from("42"),)
+						])),
+					}),
+					private: None,
+				}
+			);
+		})
+		.await;
+	}
+}
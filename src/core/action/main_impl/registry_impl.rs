@@ -0,0 +1,271 @@
+use std::sync::Mutex;
+
+use serde::{
+	de::{self, DeserializeOwned, Visitor},
+	forward_to_deserialize_any, Deserializer,
+};
+
+use crate::{
+	core::action::{
+		action_type::general_action_type::ActionType,
+		data::registry_data::{ActionAuth, ActionInfo, ActionRoute, ActionSchema},
+	},
+	lib::data::str::Str,
+};
+
+// const initialized, so the actions can register from their ctors in any order
+static ACTIONS: Mutex<Vec<ActionInfo>> = Mutex::new(Vec::new());
+
+////////////////////////////////////////////////
+//////////////////// SCHEMA ////////////////////
+////////////////////////////////////////////////
+
+// records the name and the fields the type asks for, then stops the
+// deserialization
+struct SchemaDeserializer<'a>(&'a mut Option<ActionSchema>);
+
+impl<'a> SchemaDeserializer<'a> {
+	fn record<T>(
+		self,
+		name: &'static str,
+		fields: &'static [&'static str],
+	) -> Result<T, de::value::Error> {
+		*self.0 = Some(ActionSchema {
+			name: name.into(),
+			fields: fields.iter().map(|field| Str::from(*field)).collect(),
+		});
+		Err(de::Error::custom("schema recorded"))
+	}
+}
+
+impl<'de, 'a> Deserializer<'de> for SchemaDeserializer<'a> {
+	type Error = de::value::Error;
+
+	fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+		Err(de::Error::custom("schema not available"))
+	}
+
+	fn deserialize_unit<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+		self.record("()", &[])
+	}
+
+	fn deserialize_unit_struct<V: Visitor<'de>>(
+		self,
+		name: &'static str,
+		_visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self.record(name, &[])
+	}
+
+	fn deserialize_newtype_struct<V: Visitor<'de>>(
+		self,
+		name: &'static str,
+		_visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self.record(name, &[])
+	}
+
+	fn deserialize_struct<V: Visitor<'de>>(
+		self,
+		name: &'static str,
+		fields: &'static [&'static str],
+		_visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self.record(name, fields)
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf option seq tuple tuple_struct map enum identifier
+		ignored_any
+	}
+}
+
+impl ActionSchema {
+	/// The schema of the type, falling back to its type name (without the
+	/// fields) when serde doesn't describe it as a struct.
+	pub fn of<T: DeserializeOwned>() -> Self {
+		let mut schema = None;
+		let _ = T::deserialize(SchemaDeserializer(&mut schema));
+		schema.unwrap_or_else(|| {
+			let name = std::any::type_name::<T>();
+			Self {
+				name: name.rsplit("::").next().unwrap_or(name).to_string().into(),
+				fields: vec![],
+			}
+		})
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// BUILDER ///////////////////
+////////////////////////////////////////////////
+
+impl ActionInfo {
+	pub fn new<T: ActionType>(action_type: T, auth: ActionAuth) -> Self {
+		Self {
+			scope: T::scope().to_string().into(),
+			id: action_type.id(),
+			name: action_type.name(),
+			auth,
+			input: ActionSchema::of::<()>(),
+			output: ActionSchema::of::<()>(),
+			route: None,
+		}
+	}
+
+	pub fn input<I: DeserializeOwned>(mut self) -> Self {
+		self.input = ActionSchema::of::<I>();
+		self
+	}
+
+	pub fn output<O: DeserializeOwned>(mut self) -> Self {
+		self.output = ActionSchema::of::<O>();
+		self
+	}
+
+	pub fn route(mut self, method: &'static str, path: &'static str) -> Self {
+		self.route = Some(ActionRoute {
+			method: method.into(),
+			path: path.into(),
+		});
+		self
+	}
+}
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+pub fn register(info: ActionInfo) {
+	ACTIONS
+		.lock()
+		.unwrap_or_else(|error| error.into_inner())
+		.push(info);
+}
+
+/// The registered actions, sorted by scope and id.
+pub fn list() -> Vec<ActionInfo> {
+	let mut actions = ACTIONS
+		.lock()
+		.unwrap_or_else(|error| error.into_inner())
+		.clone();
+	actions.sort_by(|a, b| (&a.scope, a.id).cmp(&(&b.scope, b.id)));
+	actions
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::collections::HashSet;
+
+	use strum::IntoEnumIterator;
+
+	use crate::{
+		core::action::{
+			action_type::{
+				automatic_action_type::AutomaticActionType, general_action_type::ActionType,
+				moderator_action_type::ModeratorActionType, user_action_type::UserActionType,
+			},
+			data::registry_data::{ActionAuth, ActionInfo, ActionRoute, ActionSchema},
+		},
+		lib::data::str::Str,
+		shared::data::user_data::UserId,
+		tests::test_utils::tests::run_test,
+	};
+
+	#[derive(Deserialize)]
+	#[allow(dead_code)]
+	struct TestInput {
+		name: String,
+		#[serde(rename = "limit_value")]
+		limit: Option<u32>,
+	}
+
+	#[derive(Deserialize)]
+	struct TestUnit;
+
+	fn missing<T: 'static + ActionType + IntoEnumIterator>(
+		keys: &HashSet<(Str, u32)>,
+		test_type: T,
+	) -> Vec<T> {
+		T::iter()
+			.filter(|action_type| *action_type != test_type)
+			.filter(|action_type| {
+				!keys.contains(&(T::scope().to_string().into(), action_type.id()))
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn test_schema() {
+		run_test(|_| async {
+			assert_eq!(
+				ActionSchema::of::<TestInput>(),
+				ActionSchema {
+					name: "TestInput".into(),
+					fields: vec!["name".into(), "limit_value".into()],
+				},
+			);
+			assert_eq!(
+				ActionSchema::of::<UserId>(),
+				ActionSchema {
+					name: "UserId".into(),
+					fields: vec![],
+				},
+			);
+			assert_eq!(ActionSchema::of::<TestUnit>().name, "TestUnit");
+			assert_eq!(ActionSchema::of::<()>().name, "()");
+			assert_eq!(ActionSchema::of::<Vec<u32>>().name, "Vec<u32>");
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_info() {
+		run_test(|_| async {
+			let info = ActionInfo::new(UserActionType::Login, ActionAuth::NoAuth)
+				.input::<TestInput>()
+				.route("POST", "/user/session");
+
+			assert_eq!(&info.scope, "User");
+			assert_eq!(&info.name, "Login");
+			assert_eq!(info.id, UserActionType::Login.id());
+			assert_eq!(&info.input.name, "TestInput");
+			assert_eq!(&info.output.name, "()");
+			assert_eq!(
+				info.route,
+				Some(ActionRoute {
+					method: "POST".into(),
+					path: "/user/session".into(),
+				}),
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_all_registered() {
+		run_test(|_| async {
+			let actions = super::list();
+			let keys = actions
+				.iter()
+				.map(|info| (info.scope.clone(), info.id))
+				.collect::<HashSet<_>>();
+
+			assert_eq!(keys.len(), actions.len(), "test if there are no duplicates");
+			assert_eq!(missing(&keys, UserActionType::Test), vec![]);
+			assert_eq!(missing(&keys, ModeratorActionType::Test), vec![]);
+			assert_eq!(missing(&keys, AutomaticActionType::Test), vec![]);
+
+			let sorted = actions
+				.windows(2)
+				.all(|pair| (&pair[0].scope, pair[0].id) < (&pair[1].scope, pair[1].id));
+			assert!(sorted, "test if the actions are sorted");
+		})
+		.await;
+	}
+}
@@ -0,0 +1,749 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use rocket::{fairing::AdHoc, Build, Rocket};
+
+use crate::{
+	core::{
+		action::{
+			action_type::{
+				automatic_action_type::AutomaticActionType, general_action_type::ActionType,
+			},
+			data::{
+				action_data::Application,
+				job_data::{JobDefinition, JobError},
+				schedule_data::{
+					CronExpression, Schedule, ScheduleConfig, ScheduleError, ScheduleTiming,
+					Scheduler, SchedulerConfig,
+				},
+			},
+			main_impl::job_impl,
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
+		},
+	},
+	external::dao::main::schedule_dao,
+	lib::data::str::Str,
+};
+
+const DEFAULT_POLL_INTERVAL: u64 = 1;
+
+const DEFAULT_LEASE: i64 = 60;
+
+// the next run is searched in the next 4 years, so that the 29th of february
+// is found
+const CRON_SEARCH_DAYS: i64 = 4 * 366;
+
+////////////////////////////////////////////////
+//////////////////// TIMING ////////////////////
+////////////////////////////////////////////////
+
+fn parse_number(value: &str, min: u32, max: u32) -> Option<u32> {
+	value
+		.parse::<u32>()
+		.ok()
+		.filter(|value| (min..=max).contains(value))
+}
+
+// the bits of the values of the field: `*`, `5`, `1-5`, `*/15`, `10-50/20`
+// and their lists (`1,15,30`)
+fn parse_field(field: &str, min: u32, max: u32) -> Option<u64> {
+	let mut bits = 0;
+
+	for part in field.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, step)) => (range, Some(parse_number(step, 1, max)?)),
+			None => (part, None),
+		};
+
+		let (start, end) = match range.split_once('-') {
+			_ if range == "*" => (min, max),
+			Some((start, end)) => (parse_number(start, min, max)?, parse_number(end, min, max)?),
+			None => {
+				let start = parse_number(range, min, max)?;
+				(start, if step.is_some() { max } else { start })
+			}
+		};
+
+		if start > end {
+			return None;
+		}
+
+		for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+			bits |= 1 << value;
+		}
+	}
+
+	Some(bits)
+}
+
+fn has(bits: u64, value: u32) -> bool {
+	bits & (1 << value) != 0
+}
+
+impl CronExpression {
+	/// Parses the 5 fields: minute, hour, day of the month, month and day of
+	/// the week (where 7 is also sunday).
+	pub fn parse(expression: &str) -> Result<Self, Str> {
+		let fields = expression.split_whitespace().collect::<Vec<_>>();
+
+		let (minutes, hours, days, months, weekdays) = match fields.as_slice() {
+			[minutes, hours, days, months, weekdays] => {
+				(*minutes, *hours, *days, *months, *weekdays)
+			}
+			_ => return Err("expected 5 fields".into()),
+		};
+
+		let field = |field: &str, min, max| {
+			parse_field(field, min, max)
+				.ok_or_else(|| Str::from(format!("invalid field `{field}`")))
+		};
+
+		let weekdays_bits = field(weekdays, 0, 7)?;
+
+		Ok(Self {
+			minutes: field(minutes, 0, 59)?,
+			hours: field(hours, 0, 23)?,
+			days: field(days, 1, 31)?,
+			months: field(months, 1, 12)?,
+			weekdays: (weekdays_bits | weekdays_bits >> 7) & 0x7f,
+			any_day: days.starts_with('*'),
+			any_weekday: weekdays.starts_with('*'),
+		})
+	}
+
+	fn matches_date(&self, date: NaiveDate) -> bool {
+		let day = has(self.days, date.day());
+		let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+
+		has(self.months, date.month())
+			&& match (self.any_day, self.any_weekday) {
+				(true, true) => true,
+				(true, false) => weekday,
+				(false, true) => day,
+				(false, false) => day || weekday,
+			}
+	}
+
+	/// The first matching minute after the time (in unix seconds, UTC).
+	pub fn next_after(&self, timestamp: i64) -> Option<i64> {
+		let start = NaiveDateTime::from_timestamp_opt(timestamp.div_euclid(60) * 60 + 60, 0)?;
+		let mut date = start.date();
+
+		for _ in 0..CRON_SEARCH_DAYS {
+			if self.matches_date(date) {
+				let first_hour = if date == start.date() {
+					start.hour()
+				} else {
+					0
+				};
+
+				for hour in (first_hour..24).filter(|hour| has(self.hours, *hour)) {
+					let first_minute = if date == start.date() && hour == start.hour() {
+						start.minute()
+					} else {
+						0
+					};
+
+					if let Some(minute) =
+						(first_minute..60).find(|minute| has(self.minutes, *minute))
+					{
+						return Some(date.and_hms(hour, minute, 0).timestamp());
+					}
+				}
+			}
+
+			date = date.succ_opt()?;
+		}
+
+		None
+	}
+}
+
+impl ScheduleTiming {
+	/// The time (in unix seconds) of the run after the one at the time.
+	pub fn next_run(&self, timestamp: i64) -> i64 {
+		match self {
+			ScheduleTiming::Cron(_, expression) => {
+				expression.next_after(timestamp).unwrap_or(i64::MAX)
+			}
+			ScheduleTiming::Interval(seconds) => timestamp.saturating_add(*seconds),
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// CONFIG ////////////////////
+////////////////////////////////////////////////
+
+fn schedule(config: ScheduleConfig) -> Result<Schedule, ScheduleError> {
+	let ScheduleConfig {
+		name,
+		action_id,
+		cron,
+		interval,
+		input,
+	} = config;
+
+	let action_type = match AutomaticActionType::from_id(action_id) {
+		Some(action_type) => action_type,
+		None => return Err(ScheduleError::UnknownAction(name, action_id)),
+	};
+
+	let timing = match (cron, interval) {
+		(Some(cron), None) => match CronExpression::parse(&cron) {
+			Ok(expression) if expression.next_after(0).is_some() => {
+				ScheduleTiming::Cron(cron, expression)
+			}
+			Ok(_) => return Err(ScheduleError::InvalidCron(name, "never runs".into())),
+			Err(error) => return Err(ScheduleError::InvalidCron(name, error)),
+		},
+		(None, Some(interval)) if interval > 0 => ScheduleTiming::Interval(interval),
+		_ => return Err(ScheduleError::InvalidTiming(name)),
+	};
+
+	Ok(Schedule {
+		name,
+		action_type,
+		timing,
+		input: input.to_string(),
+	})
+}
+
+/// Validates the schedules of the config, whose names must be unique.
+pub fn scheduler(config: SchedulerConfig) -> Result<Scheduler, ScheduleError> {
+	let mut schedules: Vec<Schedule> = vec![];
+
+	for config in config.schedules {
+		let schedule = schedule(config)?;
+
+		if schedules.iter().any(|item| item.name == schedule.name) {
+			return Err(ScheduleError::DuplicateName(schedule.name));
+		}
+
+		schedules.push(schedule);
+	}
+
+	Ok(Scheduler {
+		poll_interval: config.poll_interval.max(1),
+		lease: config.lease.max(1),
+		schedules,
+	})
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+/// Stores the schedules, removing the ones that aren't configured anymore.
+pub async fn sync(schedules: &[Schedule], now: i64) -> Result<(), ExternalException> {
+	let items = schedules
+		.iter()
+		.map(|schedule| {
+			let (cron, interval_seconds) = match &schedule.timing {
+				ScheduleTiming::Cron(cron, _) => (Some(cron.clone()), None),
+				ScheduleTiming::Interval(seconds) => (None, Some(*seconds)),
+			};
+
+			schedule_dao::SyncItem {
+				name: schedule.name.clone(),
+				action_id: schedule.action_type.id().into(),
+				cron,
+				interval_seconds,
+				next_run_at: schedule.timing.next_run(now),
+			}
+		})
+		.collect();
+
+	schedule_dao::Sync::run(schedule_dao::SyncInput(items)).await
+}
+
+// the run of the schedules started at the time by this instance
+fn run_id(started_at: i64) -> String {
+	format!("{}-{started_at}", std::process::id())
+}
+
+/// Leases the schedules that are due (or triggered) and not running,
+/// returning them.
+pub async fn start_due(
+	schedules: &[Schedule],
+	now: i64,
+	lease: i64,
+) -> Result<Vec<Schedule>, ExternalException> {
+	let schedule_dao::SelectOutput(states) = schedule_dao::Select::run(()).await?;
+	let mut started = vec![];
+
+	for schedule in schedules {
+		let due = states.iter().any(|state| {
+			state.name == schedule.name
+				&& (state.triggered || (!state.paused && state.next_run_at <= now))
+				&& state
+					.locked_until
+					.is_none_or(|locked_until| locked_until <= now)
+		});
+
+		if !due {
+			continue;
+		}
+
+		// another instance may have started it since the select
+		let schedule_dao::StartOutput { started: leased } =
+			schedule_dao::Start::run(schedule_dao::StartInput {
+				name: schedule.name.clone(),
+				run_id: run_id(now),
+				now,
+				lease,
+			})
+			.await?;
+
+		if leased {
+			started.push(schedule.clone());
+		}
+	}
+
+	Ok(started)
+}
+
+/// Runs the started schedule, storing its result and its next run.
+pub async fn run(
+	application: &Application,
+	definitions: &[JobDefinition],
+	schedule: &Schedule,
+	started_at: i64,
+) -> Result<(), ExternalException> {
+	let result = job_impl::execute(
+		application,
+		definitions,
+		schedule.action_type.id().into(),
+		schedule.input.clone(),
+	)
+	.await;
+
+	let error = match result {
+		Ok(()) => None,
+		Err(JobError { msg, .. }) => {
+			error!("[schedule] {name} failed: {msg}", name = schedule.name);
+			Some(msg.into())
+		}
+	};
+
+	schedule_dao::Finish::run(schedule_dao::FinishInput {
+		name: schedule.name.clone(),
+		run_id: run_id(started_at),
+		next_run_at: schedule.timing.next_run(started_at),
+		error,
+	})
+	.await
+}
+
+////////////////////////////////////////////////
+/////////////////// WORKER /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(not(test))]
+fn spawn_scheduler(application: Application, scheduler: Scheduler) {
+	use chrono::Utc;
+
+	tokio::spawn(async move {
+		let mut interval =
+			tokio::time::interval(std::time::Duration::from_secs(scheduler.poll_interval));
+		let mut synced = false;
+
+		loop {
+			interval.tick().await;
+			let now = Utc::now().timestamp();
+
+			if !synced {
+				match sync(&scheduler.schedules, now).await {
+					Ok(()) => synced = true,
+					Err(error) => {
+						error!("[schedule] the schedules could not be stored: {error:?}");
+						continue;
+					}
+				}
+			}
+
+			if scheduler.schedules.is_empty() {
+				return;
+			}
+
+			let started = match start_due(&scheduler.schedules, now, scheduler.lease).await {
+				Ok(started) => started,
+				Err(error) => {
+					error!("[schedule] the due schedules could not be started: {error:?}");
+					continue;
+				}
+			};
+
+			for schedule in started {
+				let application = application.clone();
+
+				tokio::spawn(async move {
+					let definitions = job_impl::definitions();
+
+					if let Err(error) = run(&application, &definitions, &schedule, now).await {
+						error!(
+							"[schedule] the run of {name} could not be stored: {error:?}",
+							name = schedule.name,
+						);
+					}
+				});
+			}
+		}
+	});
+}
+
+// the tests run the schedules themselves
+#[cfg(test)]
+fn spawn_scheduler(_application: Application, _scheduler: Scheduler) {}
+
+async fn load_config(rocket: Rocket<Build>) -> Result<Rocket<Build>, Rocket<Build>> {
+	// a missing section has no schedules, an invalid one stops the launch
+	let config = if rocket.figment().find_value("scheduler").is_ok() {
+		rocket
+			.figment()
+			.extract_inner::<SchedulerConfig>("scheduler")
+	} else {
+		Ok(SchedulerConfig {
+			poll_interval: DEFAULT_POLL_INTERVAL,
+			lease: DEFAULT_LEASE,
+			schedules: vec![],
+		})
+	};
+
+	let config = match config {
+		Ok(config) => config,
+		Err(error) => {
+			error!("[schedule] the scheduler config is invalid: {error}");
+			return Err(rocket);
+		}
+	};
+
+	match scheduler(config) {
+		Ok(scheduler) => Ok(rocket.manage(scheduler)),
+		Err(error) => {
+			error!("[schedule] the scheduler config is invalid: {error:?}");
+			Err(rocket)
+		}
+	}
+}
+
+pub fn stage() -> AdHoc {
+	AdHoc::on_ignite("Scheduler Stage", |rocket| async {
+		rocket
+			.attach(AdHoc::try_on_ignite("Scheduler Config", load_config))
+			.attach(AdHoc::on_liftoff("Scheduler", |rocket| {
+				Box::pin(async move {
+					let scheduler = rocket.state::<Scheduler>().cloned().unwrap_or_default();
+					let application = rocket.state::<Application>().cloned().unwrap_or_default();
+
+					spawn_scheduler(application, scheduler);
+				})
+			}))
+	})
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use chrono::NaiveDate;
+
+	use crate::core::action::action_type::automatic_action_type::AutomaticActionType;
+	use crate::core::action::data::action_data::Application;
+	use crate::core::action::data::schedule_data::{
+		CronExpression, Schedule, ScheduleConfig, ScheduleError, ScheduleTiming, SchedulerConfig,
+	};
+	use crate::core::action::main_impl::job_impl;
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::schedule_dao;
+	use crate::tests::test_utils::tests::run_test;
+
+	fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> i64 {
+		NaiveDate::from_ymd(year, month, day)
+			.and_hms(hour, minute, 0)
+			.timestamp()
+	}
+
+	fn next(expression: &str, timestamp: i64) -> Option<i64> {
+		CronExpression::parse(expression)
+			.unwrap()
+			.next_after(timestamp)
+	}
+
+	fn config(name: &str, cron: Option<&str>, interval: Option<i64>) -> ScheduleConfig {
+		ScheduleConfig {
+			name: name.into(),
+			action_id: 0,
+			cron: cron.map(String::from),
+			interval,
+			input: serde_json::Value::Null,
+		}
+	}
+
+	fn schedule(name: &str, input: &str) -> Schedule {
+		Schedule {
+			name: name.into(),
+			action_type: AutomaticActionType::Test,
+			timing: ScheduleTiming::Interval(600),
+			input: input.into(),
+		}
+	}
+
+	fn state(
+		name: &str,
+		paused: bool,
+		triggered: bool,
+		next_run_at: i64,
+	) -> schedule_dao::Schedule {
+		schedule_dao::Schedule {
+			name: name.into(),
+			action_id: 0,
+			cron: None,
+			interval_seconds: Some(600),
+			paused,
+			triggered,
+			locked_until: None,
+			last_run_at: None,
+			last_error: None,
+			next_run_at,
+		}
+	}
+
+	#[test]
+	fn test_cron_parse() {
+		let expression = CronExpression::parse("*/20 9-17 1,15 * 7").unwrap();
+		assert_eq!(expression.minutes, 1 | 1 << 20 | 1 << 40);
+		assert_eq!(expression.hours, 0x3fe00);
+		assert_eq!(expression.days, 1 << 1 | 1 << 15);
+		assert_eq!(expression.months, 0x1ffe);
+		assert_eq!(expression.weekdays, 1);
+		assert!(!expression.any_day);
+		assert!(!expression.any_weekday);
+
+		let errors = [
+			("* * * *", "expected 5 fields"),
+			("60 * * * *", "invalid field `60`"),
+			("* * 0 * *", "invalid field `0`"),
+			("* 5-1 * * *", "invalid field `5-1`"),
+			("*/0 * * * *", "invalid field `*/0`"),
+			("* * * jan *", "invalid field `jan`"),
+		];
+
+		for (expression, error) in errors {
+			assert_eq!(CronExpression::parse(expression), Err(error.into()));
+		}
+	}
+
+	#[test]
+	fn test_cron_next() {
+		let now = at(2022, 8, 20, 10, 7) + 30;
+
+		assert_eq!(next("* * * * *", now), Some(at(2022, 8, 20, 10, 8)));
+		assert_eq!(next("*/15 * * * *", now), Some(at(2022, 8, 20, 10, 15)));
+		assert_eq!(next("5 * * * *", now), Some(at(2022, 8, 20, 11, 5)));
+		assert_eq!(next("30 3 * * *", now), Some(at(2022, 8, 21, 3, 30)));
+		// the 20th is a saturday
+		assert_eq!(next("0 9 * * 1-5", now), Some(at(2022, 8, 22, 9, 0)));
+		assert_eq!(next("0 0 1 * *", now), Some(at(2022, 9, 1, 0, 0)));
+		assert_eq!(next("0 0 25 * 0", now), Some(at(2022, 8, 21, 0, 0)));
+		assert_eq!(next("0 0 29 2 *", now), Some(at(2024, 2, 29, 0, 0)));
+		assert_eq!(next("0 0 31 2 *", now), None);
+	}
+
+	#[test]
+	fn test_scheduler() {
+		let scheduler = super::scheduler(SchedulerConfig {
+			poll_interval: 0,
+			lease: 30,
+			schedules: vec![
+				config("hourly", Some("0 * * * *"), None),
+				config("often", None, Some(20)),
+			],
+		})
+		.unwrap();
+
+		assert_eq!(scheduler.poll_interval, 1);
+		assert_eq!(
+			scheduler.schedules[1],
+			Schedule {
+				timing: ScheduleTiming::Interval(20),
+				..schedule("often", "null")
+			},
+		);
+
+		let errors = [
+			(
+				config("both", Some("0 * * * *"), Some(20)),
+				ScheduleError::InvalidTiming("both".into()),
+			),
+			(
+				config("none", None, None),
+				ScheduleError::InvalidTiming("none".into()),
+			),
+			(
+				config("never", Some("0 0 31 2 *"), None),
+				ScheduleError::InvalidCron("never".into(), "never runs".into()),
+			),
+			(
+				ScheduleConfig {
+					action_id: 1000,
+					..config("unknown", None, Some(20))
+				},
+				ScheduleError::UnknownAction("unknown".into(), 1000),
+			),
+		];
+
+		for (schedule, error) in errors {
+			let result = super::scheduler(SchedulerConfig {
+				poll_interval: 1,
+				lease: 30,
+				schedules: vec![schedule],
+			});
+			assert_eq!(result, Err(error));
+		}
+
+		let result = super::scheduler(SchedulerConfig {
+			poll_interval: 1,
+			lease: 30,
+			schedules: vec![
+				config("same", None, Some(20)),
+				config("same", None, Some(30)),
+			],
+		});
+		assert_eq!(result, Err(ScheduleError::DuplicateName("same".into())));
+	}
+
+	#[tokio::test]
+	async fn test_sync() {
+		run_test(|_| async {
+			let now = at(2022, 8, 20, 10, 7);
+			let hourly = Schedule {
+				timing: ScheduleTiming::Cron(
+					"0 * * * *".into(),
+					CronExpression::parse("0 * * * *").unwrap(),
+				),
+				..schedule("hourly", "null")
+			};
+
+			let _m = schedule_dao::Sync::mock(
+				schedule_dao::SyncInput(vec![
+					schedule_dao::SyncItem {
+						name: "hourly".into(),
+						action_id: 0,
+						cron: Some("0 * * * *".into()),
+						interval_seconds: None,
+						next_run_at: at(2022, 8, 20, 11, 0),
+					},
+					schedule_dao::SyncItem {
+						name: "often".into(),
+						action_id: 0,
+						cron: None,
+						interval_seconds: Some(600),
+						next_run_at: now + 600,
+					},
+				]),
+				(),
+			);
+
+			let result = super::sync(&[hourly, schedule("often", "null")], now).await;
+
+			assert_eq!(result, Ok(()));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_start_due() {
+		run_test(|_| async {
+			let now = at(2022, 8, 20, 10, 7);
+			let running = schedule_dao::Schedule {
+				locked_until: Some(now + 1),
+				..state("running", false, false, now)
+			};
+
+			let _m1 = schedule_dao::Select::mock(
+				(),
+				schedule_dao::SelectOutput(vec![
+					state("due", false, false, now),
+					state("later", false, false, now + 1),
+					state("paused", true, false, now),
+					state("triggered", true, true, now + 1),
+					state("taken", false, false, now),
+					running,
+				]),
+			);
+			let start = |name: &str, started| {
+				schedule_dao::Start::mock(
+					schedule_dao::StartInput {
+						name: name.into(),
+						run_id: super::run_id(now),
+						now,
+						lease: 60,
+					},
+					schedule_dao::StartOutput { started },
+				)
+			};
+			let _m2 = start("due", true);
+			let _m3 = start("triggered", true);
+			let _m4 = start("taken", false);
+
+			let schedules = [
+				"due",
+				"later",
+				"paused",
+				"triggered",
+				"taken",
+				"running",
+				"new",
+			]
+			.map(|name| schedule(name, "null"));
+
+			let result = super::start_due(&schedules, now, 60).await;
+
+			assert_eq!(
+				result,
+				Ok(vec![schedule("due", "null"), schedule("triggered", "null")])
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run() {
+		run_test(|helper| async move {
+			let now = at(2022, 8, 20, 10, 7);
+			let finish = |name: &str, error: Option<&str>| {
+				schedule_dao::Finish::mock(
+					schedule_dao::FinishInput {
+						name: name.into(),
+						run_id: super::run_id(now),
+						next_run_at: now + 600,
+						error: error.map(String::from),
+					},
+					(),
+				)
+			};
+			let _m1 = finish("ok", None);
+			let _m2 = finish("error", Some("NotHook"));
+
+			let definitions = job_impl::tests::definitions();
+			let ok = schedule("ok", r#"{"id":9,"error":null}"#);
+			let error = schedule("error", r#"{"id":10,"error":"hook"}"#);
+
+			let result = super::run(&Application::default(), &definitions, &ok, now).await;
+			assert_eq!(result, Ok(()));
+			assert_eq!(job_impl::tests::ran(), vec![9]);
+
+			let result = super::run(&Application::default(), &definitions, &error, now).await;
+			assert_eq!(result, Ok(()));
+			assert!(job_impl::tests::ran().is_empty());
+			assert_eq!(
+				helper.pop_log(),
+				Some("ERROR - [schedule] error failed: NotHook".into()),
+			);
+		})
+		.await;
+	}
+}
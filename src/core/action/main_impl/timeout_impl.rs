@@ -0,0 +1,209 @@
+use std::{future::Future, time::Duration};
+
+use super::action_impl::ActionTypeWrapper;
+
+use crate::core::action::{
+	action_type::general_action_type::ActionType,
+	data::action_data::{
+		ActionContext, Application, DescriptiveError, ErrorCategory, ErrorData, TimeoutError,
+	},
+	definition::{action::ActionError, action_helpers::DescriptiveRequestContext},
+};
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+pub fn timeout<T: ActionType>(action_type: T, application: &Application) -> u32 {
+	action_type.timeout().unwrap_or(application.request_timeout)
+}
+
+////////////////////////////////////////////////
+//////////////////// ACTION ////////////////////
+////////////////////////////////////////////////
+
+pub async fn run<T, C, F>(
+	action_context: &ActionContext<T, C>,
+	timeout: u32,
+	future: F,
+) -> Result<F::Output, TimeoutError>
+where
+	T: ActionType,
+	C: DescriptiveRequestContext,
+	F: Future,
+{
+	tokio::time::timeout(Duration::from_millis(timeout.into()), future)
+		.await
+		.map_err(|_| {
+			warn!("{}", description(action_context, timeout));
+			TimeoutError { timeout }
+		})
+}
+
+fn description<T: ActionType, C: DescriptiveRequestContext>(
+	action_context: &ActionContext<T, C>,
+	timeout: u32,
+) -> String {
+	let action = format!(
+		"[action({action_scope}::{action_type} - {action_id})]",
+		action_scope = T::scope(),
+		action_type = ActionTypeWrapper(action_context.action_type),
+		action_id = action_context.action_type.id(),
+	);
+	let context = action_context
+		.context
+		.as_ref()
+		.map(|context| format!(" [context={context}]", context = context.description()))
+		.unwrap_or_default();
+
+	format!("{action} [timeout={timeout}ms]{context}")
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+impl ActionError for TimeoutError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		None
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		Self::error_msg(
+			"action.timeout",
+			"The action took too long to complete. Please try again later.".into(),
+		)
+	}
+
+	fn category(&self) -> ErrorCategory {
+		ErrorCategory::Timeout
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::{borrow::Cow, time::Duration};
+
+	use crate::core::action::action_type::general_action_type::{ActionScope, ActionType};
+	use crate::core::action::data::action_data::tests::ApplicationBuilder;
+	use crate::core::action::data::action_data::{ActionContext, ErrorCategory, ErrorData};
+	use crate::core::action::data::action_data::{RequestContext, TimeoutError};
+	use crate::core::action::definition::action::ActionError;
+	use crate::core::action::definition::action_helpers::DescriptiveInfo;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[derive(Clone, Debug, Eq, PartialEq)]
+	struct TestContext;
+
+	impl RequestContext for TestContext {}
+
+	impl DescriptiveInfo for TestContext {
+		fn description(&self) -> Cow<'_, str> {
+			"test(context)".into()
+		}
+	}
+
+	#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+	enum TestActionType {
+		Default,
+		Long,
+	}
+
+	impl ActionType for TestActionType {
+		fn scope() -> ActionScope {
+			ActionScope::Automatic
+		}
+
+		fn id(&self) -> u32 {
+			match self {
+				TestActionType::Default => 1,
+				TestActionType::Long => 2,
+			}
+		}
+
+		fn from_id(_: u32) -> Option<Self> {
+			None
+		}
+
+		fn timeout(&self) -> Option<u32> {
+			match self {
+				TestActionType::Default => None,
+				TestActionType::Long => Some(60_000),
+			}
+		}
+	}
+
+	fn action_context(action_type: TestActionType) -> ActionContext<TestActionType, TestContext> {
+		ActionContext {
+			action_type,
+			context: Some(TestContext),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_timeout() {
+		run_test(|_| async {
+			let application = ApplicationBuilder::new().request_timeout(250).build();
+
+			assert_eq!(super::timeout(TestActionType::Default, &application), 250);
+			assert_eq!(super::timeout(TestActionType::Long, &application), 60_000);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_ok() {
+		run_test(|_| async {
+			let action_context = action_context(TestActionType::Default);
+			let result = super::run(&action_context, 1000, async { 7 }).await;
+
+			assert_eq!(result, Ok(7));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_run_timeout() {
+		run_test(|helper| async move {
+			let action_context = action_context(TestActionType::Default);
+			let result = super::run(&action_context, 10, async {
+				tokio::time::sleep(Duration::from_millis(1000)).await;
+				7
+			})
+			.await;
+
+			assert_eq!(result, Err(TimeoutError { timeout: 10 }));
+			assert_eq!(
+				&helper.pop_log(),
+				&Some(
+					"WARN - [action(Automatic::Default - 1)] [timeout=10ms] [context=test(context)]"
+						.into()
+				)
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_error() {
+		run_test(|_| async {
+			let error = TimeoutError { timeout: 10 };
+
+			assert_eq!(error.private_error(), None);
+			assert_eq!(
+				error.public_error(),
+				Some(ErrorData {
+					code: "action.timeout".into(),
+					msg: "The action took too long to complete. Please try again later.".into(),
+					params: None,
+				})
+			);
+			assert_eq!(error.category(), ErrorCategory::Timeout);
+		})
+		.await;
+	}
+}
@@ -1,40 +1,53 @@
 use std::borrow::Cow;
 
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
+
+use super::{interceptor_impl::USER_INTERCEPTORS, pipeline_impl};
 
 use crate::core::action::{
+	data::audit_data::AuditActor,
 	data::{
-		action_data::{ActionContext, DescriptiveError, ErrorData},
+		action_data::{
+			Application, DescriptiveError, ErrorCategory, ErrorData, PanicError, TimeoutError,
+		},
+		idempotency_data::IdempotencyError,
+		interceptor_data::InterceptorError,
 		user_action_data::{
 			UserActionError, UserAuthRequestContext, UserAuthSession, UserErrorInfo,
 			UserNoAuthRequestContext, UserOutputInfo, UserRequestContext, UserSession,
 			UserUnconfirmedRequestContext, UserUnconfirmedSession,
 		},
+		validation_data::ValidationError,
 	},
-	definition::action_helpers::DescriptiveInfo,
+	definition::action_helpers::{AuditInfo, DescriptiveInfo, PipelineError, PipelineInfo},
 };
 use crate::{
-	core::action::{
-		data::{
-			action_data::{Application, Request, RequestBasicData},
-			user_action_data::{
-				UserActionInput, UserAuthInputResult, UserAuthRequestInput, UserNoAuthInputResult,
-				UserNoAuthRequestInput, UserNoAuthSession, UserRequestInput,
-				UserUnconfirmedInputResult, UserUnconfirmedRequestInput,
+	core::{
+		action::{
+			data::{
+				action_data::{AuthBasicContext, RequestBasicData},
+				user_action_data::{
+					UserActionInput, UserAuthInputResult, UserAuthRequestInput,
+					UserNoAuthInputResult, UserNoAuthRequestInput, UserNoAuthSession,
+					UserRequestInput, UserUnconfirmedInputResult, UserUnconfirmedRequestInput,
+				},
 			},
+			definition::action::{Action, ActionError, UserAction},
+			definition::action::{ActionInput, ActionOutput},
+		},
+		external::{
+			data::external_exception::ExternalException, definition::external::ExternalAction,
 		},
-		definition::action::{Action, ActionError, UserAction},
-		definition::action::{ActionInput, ActionOutput},
 	},
-	lib::traits::async_from::AsyncFrom,
+	external::dao::main::user_session_dao,
+	lib::{data::str::Str, traits::async_from::AsyncFrom},
+	shared::data::user_data::UserId,
 };
 
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
-impl<I: ActionInput> ActionInput for UserRequestInput<I> {}
-
 impl DescriptiveInfo for UserSession {
 	fn description(&self) -> Cow<'_, str> {
 		match self {
@@ -49,7 +62,38 @@ impl DescriptiveInfo for UserSession {
 
 impl DescriptiveInfo for UserRequestContext {
 	fn description(&self) -> Cow<'_, str> {
-		self.session.description()
+		format!(
+			"{session} {request}",
+			session = self.session.description(),
+			request = self.request.description(),
+		)
+		.into()
+	}
+}
+
+impl PipelineInfo for UserRequestContext {
+	fn application(&self) -> &Application {
+		&self.application
+	}
+
+	fn idempotency_key(&self) -> Option<&Str> {
+		self.request.idempotency_key.as_ref()
+	}
+}
+
+impl AuditInfo for UserRequestContext {
+	fn actor(&self) -> AuditActor {
+		match &self.session {
+			UserSession::Auth(UserAuthSession { user_id, .. }) => AuditActor::User(*user_id),
+			UserSession::Unconfirmed(UserUnconfirmedSession { user_id, .. }) => {
+				AuditActor::User(*user_id)
+			}
+			UserSession::NoAuth(_) => AuditActor::Anonymous,
+		}
+	}
+
+	fn ip(&self) -> Option<Str> {
+		Some(self.request.ip.clone())
 	}
 }
 
@@ -60,23 +104,75 @@ impl DescriptiveInfo for UserRequestContext {
 #[rocket::async_trait]
 impl<I: Send> AsyncFrom<RequestBasicData<I>> for Result<UserRequestInput<I>, UserActionError> {
 	async fn from(input: RequestBasicData<I>) -> Self {
+		let RequestBasicData { data, context } = input;
+		let AuthBasicContext {
+			token,
+			application,
+			request,
+		} = context;
+
+		let session = match token {
+			Some(token) => session_from_token(token).await?,
+			None => UserSession::NoAuth(UserNoAuthSession {
+				created_at: Utc::now(),
+			}),
+		};
+
 		Ok(UserRequestInput {
-			data: input.data,
+			data,
 			context: UserRequestContext {
-				application: Application {
-					request_timeout: 1000,
-				},
-				session: UserSession::NoAuth(UserNoAuthSession {
-					created_at: Utc::now(),
-				}),
-				request: Request {
-					ip: "1.2.3.4".into(),
-				},
+				application,
+				session,
+				request,
 			},
 		})
 	}
 }
 
+pub async fn session_from_token(token: String) -> Result<UserSession, UserActionError> {
+	let user_session_dao::SelectOutput(session) =
+		user_session_dao::Select::run(user_session_dao::SelectInput::ByToken(token))
+			.await
+			.map_err(UserActionError::SessionError)?;
+
+	let user_session_dao::UserSession {
+		id: session_id,
+		user_id: UserId(user_id),
+		confirmed,
+		created_at,
+		expires_at,
+		revoked_at,
+	} = session.ok_or(UserActionError::InvalidToken)?;
+
+	// a revoked session (after a logout) is handled as if there was no token
+	if revoked_at.is_some() {
+		return Ok(UserSession::NoAuth(UserNoAuthSession {
+			created_at: Utc::now(),
+		}));
+	}
+
+	if expires_at <= Utc::now().timestamp() {
+		return Err(UserActionError::ExpiredToken);
+	}
+
+	let created_at = Utc.timestamp(created_at, 0);
+	let user_id = user_id as u64;
+
+	Ok(if confirmed {
+		UserSession::Auth(UserAuthSession {
+			session_id,
+			created_at,
+			user_id,
+		})
+	} else {
+		UserSession::Unconfirmed(UserUnconfirmedSession {
+			session_id,
+			created_at,
+			user_id,
+		})
+	})
+}
+
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
@@ -269,21 +365,95 @@ impl ActionError for UserActionError {
 		match self {
 			UserActionError::Authenticated => None,
 			UserActionError::Unauthenticated => None,
+			UserActionError::Forbidden => None,
+			UserActionError::InvalidToken => None,
+			UserActionError::ExpiredToken => None,
+			UserActionError::SessionError(error) => error.private_error(),
+			UserActionError::InvalidInput(error) => error.private_error(),
+			UserActionError::Timeout(error) => error.private_error(),
+			UserActionError::Panic(error) => error.private_error(),
+			UserActionError::Intercepted(error) => error.private_error(),
+			UserActionError::Idempotency(error) => error.private_error(),
+			UserActionError::Outbox(error) => error.private_error(),
 		}
 	}
 
 	fn public_error(&self) -> Option<ErrorData> {
 		match self {
-			UserActionError::Authenticated => {
-				Self::error_msg("You can't execute this action while authenticated.".into())
-			}
-			UserActionError::Unauthenticated => {
-				Self::error_msg("You must be authenticated to execute this action.".into())
-			}
+			UserActionError::Authenticated => Self::error_msg(
+				"user.authenticated",
+				"You can't execute this action while authenticated.".into(),
+			),
+			UserActionError::Unauthenticated => Self::error_msg(
+				"user.unauthenticated",
+				"You must be authenticated to execute this action.".into(),
+			),
+			UserActionError::Forbidden => Self::error_msg(
+				"user.forbidden",
+				"You are not allowed to execute this action.".into(),
+			),
+			UserActionError::InvalidToken => Self::error_msg(
+				"user.session_invalid",
+				"Your session is invalid. Please log in again.".into(),
+			),
+			UserActionError::ExpiredToken => Self::error_msg(
+				"user.session_expired",
+				"Your session has expired. Please log in again.".into(),
+			),
+			UserActionError::SessionError(error) => error.public_error(),
+			UserActionError::InvalidInput(error) => error.public_error(),
+			UserActionError::Timeout(error) => error.public_error(),
+			UserActionError::Panic(error) => error.public_error(),
+			UserActionError::Intercepted(error) => error.public_error(),
+			UserActionError::Idempotency(error) => error.public_error(),
+			UserActionError::Outbox(error) => error.public_error(),
+		}
+	}
+
+	fn category(&self) -> ErrorCategory {
+		match self {
+			UserActionError::Authenticated => ErrorCategory::Forbidden,
+			UserActionError::Unauthenticated => ErrorCategory::Unauthenticated,
+			UserActionError::Forbidden => ErrorCategory::Forbidden,
+			UserActionError::InvalidToken => ErrorCategory::Unauthenticated,
+			UserActionError::ExpiredToken => ErrorCategory::Unauthenticated,
+			UserActionError::SessionError(error) => error.category(),
+			UserActionError::InvalidInput(error) => error.category(),
+			UserActionError::Timeout(error) => error.category(),
+			UserActionError::Panic(error) => error.category(),
+			UserActionError::Intercepted(error) => error.category(),
+			UserActionError::Idempotency(error) => error.category(),
+			UserActionError::Outbox(error) => error.category(),
 		}
 	}
 }
 
+impl PipelineError for UserActionError {
+	fn invalid_input(error: ValidationError) -> Self {
+		UserActionError::InvalidInput(error)
+	}
+
+	fn timeout(error: TimeoutError) -> Self {
+		UserActionError::Timeout(error)
+	}
+
+	fn panic(error: PanicError) -> Self {
+		UserActionError::Panic(error)
+	}
+
+	fn outbox(error: ExternalException) -> Self {
+		UserActionError::Outbox(error)
+	}
+
+	fn idempotency(error: IdempotencyError) -> Self {
+		UserActionError::Idempotency(error)
+	}
+
+	fn intercepted(error: InterceptorError) -> Self {
+		UserActionError::Intercepted(Box::new(error))
+	}
+}
+
 ////////////////////////////////////////////////
 /////////////////// ACTION /////////////////////
 ////////////////////////////////////////////////
@@ -297,46 +467,14 @@ where
 	T: UserAction<I, O, E> + Send + 'static,
 {
 	async fn run(input: UserActionInput<I>) -> Result<UserOutputInfo<O>, UserErrorInfo<E>> {
-		let context = input
-			.as_ref()
-			.map(|ok_input| Some(ok_input.context.clone()))
-			.unwrap_or(None);
-
-		let action_context = ActionContext {
-			action_type: Self::action_type(),
-			context,
-		};
-
-		match input {
-			Ok(ok_input) => {
-				let action_result = Self::new(ok_input).await;
-
-				match action_result {
-					Ok(action) => {
-						let result = action.run_inner().await;
-
-						match result {
-							Ok(data) => Ok(UserOutputInfo {
-								action_context,
-								data,
-							}),
-							Err(error) => Err(UserErrorInfo {
-								action_context,
-								error,
-							}),
-						}
-					}
-					Err(error) => Err(UserErrorInfo {
-						action_context,
-						error,
-					}),
-				}
-			}
-			Err(error) => Err(UserErrorInfo {
-				action_context,
-				error: E::from(error),
-			}),
-		}
+		pipeline_impl::run(
+			&USER_INTERCEPTORS.list(),
+			Self::action_type(),
+			input,
+			Self::new,
+			Self::run_inner,
+		)
+		.await
 	}
 }
 
@@ -346,10 +484,19 @@ where
 
 #[cfg(test)]
 pub mod tests {
-	use crate::core::action::data::action_data::ActionContext;
+	use std::time::Duration;
+
+	use chrono::{TimeZone, Utc};
+	use mockito::Mock;
+
+	use crate::core::action::data::action_data::tests::{ApplicationBuilder, RequestBuilder};
+	use crate::core::action::data::action_data::{
+		ActionContext, AuthBasicContext, PanicError, TimeoutError,
+	};
 	use crate::core::action::data::user_action_data::tests::{
 		UserAuthSessionBuilder, UserRequestContextBuilder, UserUnconfirmedSessionBuilder,
 	};
+	use crate::core::action::data::user_action_data::UserActionInput;
 	use crate::core::action::data::user_action_data::{
 		UserActionError, UserAuthRequestContext, UserAuthSession, UserNoAuthRequestContext,
 		UserOutputInfo, UserSession, UserUnconfirmedRequestContext, UserUnconfirmedSession,
@@ -359,9 +506,15 @@ pub mod tests {
 	};
 	use crate::core::action::definition::action::Action;
 	use crate::core::action::definition::action::UserAction;
+	use crate::core::action::definition::action_helpers::ActionErrorHelper;
+	use crate::core::action::main_impl::metrics_impl;
 	use crate::core::action::{
 		action_type::user_action_type::UserActionType, data::action_data::ActionErrorInfo,
 	};
+	use crate::core::external::definition::external::tests::ExternalMocker;
+	use crate::external::dao::main::user_session_dao;
+	use crate::lib::traits::async_from::AsyncFrom;
+	use crate::shared::data::user_data::UserId;
 	use crate::tests::test_utils::tests::run_test;
 
 	#[derive(Debug)]
@@ -376,6 +529,9 @@ pub mod tests {
 	#[derive(Debug)]
 	pub struct TestActionUnconfirmed(RequestInput<(), UserUnconfirmedRequestContext>);
 
+	#[derive(Debug)]
+	pub struct TestActionSlow;
+
 	#[rocket::async_trait]
 	impl UserAction<(), (), UserActionError> for TestAction {
 		fn action_type() -> UserActionType {
@@ -469,6 +625,168 @@ pub mod tests {
 		}
 	}
 
+	#[rocket::async_trait]
+	impl UserAction<(), (), UserActionError> for TestActionSlow {
+		fn action_type() -> UserActionType {
+			UserActionType::Test
+		}
+
+		async fn new(_: RequestInput<(), UserRequestContext>) -> Result<Self, UserActionError> {
+			Ok(Self)
+		}
+
+		async fn run_inner(self) -> Result<(), UserActionError> {
+			tokio::time::sleep(Duration::from_millis(1000)).await;
+			Ok(())
+		}
+	}
+
+	#[derive(Debug)]
+	pub struct TestActionPanic;
+
+	#[rocket::async_trait]
+	impl UserAction<(), (), UserActionError> for TestActionPanic {
+		fn action_type() -> UserActionType {
+			UserActionType::Test
+		}
+
+		async fn new(_: RequestInput<(), UserRequestContext>) -> Result<Self, UserActionError> {
+			Ok(Self)
+		}
+
+		async fn run_inner(self) -> Result<(), UserActionError> {
+			todo!()
+		}
+	}
+
+	fn mock_session(token: &str, session: Option<user_session_dao::UserSession>) -> Mock {
+		user_session_dao::Select::mock(
+			user_session_dao::SelectInput::ByToken(token.into()),
+			user_session_dao::SelectOutput(session),
+		)
+	}
+
+	fn session_data(
+		user_id: i64,
+		confirmed: bool,
+		expires_in: i64,
+	) -> user_session_dao::UserSession {
+		user_session_dao::UserSession {
+			id: 1,
+			user_id: UserId(user_id),
+			confirmed,
+			created_at: 1_650_000_000,
+			expires_at: Utc::now().timestamp() + expires_in,
+			revoked_at: None,
+		}
+	}
+
+	async fn request_context(token: Option<&str>) -> Result<UserRequestContext, UserActionError> {
+		let context = AuthBasicContext {
+			token: token.map(String::from),
+			application: ApplicationBuilder::new().build(),
+			request: RequestBuilder::new().ip("10.0.0.1".into()).build(),
+		};
+
+		<UserActionInput<()> as AsyncFrom<_>>::from(context.data(()))
+			.await
+			.map(|input| input.context)
+	}
+
+	#[tokio::test]
+	async fn test_request_no_token() {
+		run_test(|_| async {
+			let context = request_context(None).await.unwrap();
+			assert!(matches!(context.session, UserSession::NoAuth(_)));
+			assert_eq!(
+				&context.request,
+				&RequestBuilder::new().ip("10.0.0.1".into()).build()
+			);
+			assert_eq!(&context.application, &ApplicationBuilder::new().build());
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_request_auth_token() {
+		run_test(|_| async {
+			let _m = mock_session("token-auth", Some(session_data(5, true, 3600)));
+
+			let context = request_context(Some("token-auth")).await.unwrap();
+			assert_eq!(
+				&context.session,
+				&UserSession::Auth(UserAuthSession {
+					session_id: 1,
+					created_at: Utc.timestamp(1_650_000_000, 0),
+					user_id: 5,
+				})
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_request_unconfirmed_token() {
+		run_test(|_| async {
+			let _m = mock_session("token-unconfirmed", Some(session_data(6, false, 3600)));
+
+			let context = request_context(Some("token-unconfirmed")).await.unwrap();
+			assert_eq!(
+				&context.session,
+				&UserSession::Unconfirmed(UserUnconfirmedSession {
+					session_id: 1,
+					created_at: Utc.timestamp(1_650_000_000, 0),
+					user_id: 6,
+				})
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_request_unknown_token() {
+		run_test(|_| async {
+			let _m = mock_session("token-unknown", None);
+
+			let result = request_context(Some("token-unknown")).await;
+			assert_eq!(&result, &Err(UserActionError::InvalidToken));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_request_revoked_token() {
+		run_test(|_| async {
+			let mut session = session_data(8, true, 3600);
+			session.revoked_at = Some(1_650_000_100);
+			let _m = mock_session("token-revoked", Some(session));
+
+			let context = request_context(Some("token-revoked")).await.unwrap();
+			assert!(matches!(context.session, UserSession::NoAuth(_)));
+
+			let result = TestActionAuth::run(Ok(RequestInput { context, data: () })).await;
+			assert!(matches!(
+				result,
+				Err(ActionErrorInfo {
+					error: UserActionError::Unauthenticated,
+					..
+				})
+			));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_request_expired_token() {
+		run_test(|_| async {
+			let _m = mock_session("token-expired", Some(session_data(7, true, -1)));
+
+			let result = request_context(Some("token-expired")).await;
+			assert_eq!(&result, &Err(UserActionError::ExpiredToken));
+		})
+		.await;
+	}
+
 	#[tokio::test]
 	async fn test_input_context_no_auth() {
 		run_test(|_| async {
@@ -846,4 +1164,74 @@ pub mod tests {
 		})
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_timeout() {
+		run_test(|helper| async move {
+			let context = UserRequestContextBuilder::new()
+				.application(ApplicationBuilder::new().request_timeout(10).build())
+				.build();
+			let action_context = ActionContext {
+				action_type: TestActionSlow::action_type(),
+				context: Some(context.clone()),
+			};
+
+			let result = TestActionSlow::run(Ok(RequestInput { data: (), context })).await;
+			assert_eq!(
+				&result,
+				&Err(ActionErrorInfo {
+					action_context,
+					error: UserActionError::Timeout(TimeoutError { timeout: 10 }),
+				}),
+			);
+			assert_eq!(
+				&helper.pop_log(),
+				&Some(
+					concat!(
+						"WARN - [action(User::Test - 0)] [timeout=10ms] ",
+						"[context=user(not authenticated) request(request-01)]"
+					)
+					.into()
+				)
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_panic() {
+		run_test(|helper| async move {
+			let context = UserRequestContextBuilder::new().build();
+			let action_context = ActionContext {
+				action_type: TestActionPanic::action_type(),
+				context: Some(context.clone()),
+			};
+
+			let result = TestActionPanic::run(Ok(RequestInput { data: (), context })).await;
+			let error = ActionErrorInfo {
+				action_context,
+				error: UserActionError::Panic(PanicError {
+					msg: "not yet implemented".into(),
+				}),
+			};
+			assert_eq!(&result, &Err(error));
+
+			assert_eq!(result.unwrap_err().handle(), None);
+			assert_eq!(
+				&helper.pop_log(),
+				&Some(
+					concat!(
+						"ERROR - [action(User::Test - 0)] ",
+						"[private=the action panicked: not yet implemented] ",
+						"[context=user(not authenticated) request(request-01)]"
+					)
+					.into()
+				)
+			);
+
+			let series = r#"scope="User",action="Test",outcome="private_error""#;
+			assert!(metrics_impl::render().contains(&format!("action_calls_total{{{series}}} ")));
+		})
+		.await;
+	}
 }
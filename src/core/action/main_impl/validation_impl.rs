@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use crate::core::action::{
+	data::{
+		action_data::{DescriptiveError, ErrorCategory, ErrorData},
+		validation_data::{TextRule, ValidationError, ValidationResult, Validator},
+	},
+	definition::action::ActionError,
+};
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+impl TextRule {
+	fn code(&self) -> &'static str {
+		match self {
+			TextRule::Required => "required",
+			TextRule::Length { .. } => "length",
+			TextRule::Email => "email",
+			TextRule::Format { code, .. } => code,
+		}
+	}
+
+	fn check(&self, value: &str) -> bool {
+		match self {
+			TextRule::Required => !value.trim().is_empty(),
+			TextRule::Length { min, max } => (*min..=*max).contains(&value.chars().count()),
+			TextRule::Email => is_email(value),
+			TextRule::Format { check, .. } => check(value),
+		}
+	}
+}
+
+fn is_email(value: &str) -> bool {
+	let mut parts = value.split('@');
+
+	match (parts.next(), parts.next(), parts.next()) {
+		(Some(local), Some(domain), None) => {
+			!local.is_empty()
+				&& value.len() <= 254
+				&& !value.chars().any(char::is_whitespace)
+				&& domain.split('.').count() > 1
+				&& domain.split('.').all(|label| !label.is_empty())
+		}
+		_ => false,
+	}
+}
+
+// only the first failing rule of each field is reported
+impl Validator {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn text(self, field: &'static str, value: &str, rules: &[TextRule]) -> Self {
+		match rules.iter().find(|rule| !rule.check(value)) {
+			Some(rule) => self.error(field, rule.code()),
+			None => self,
+		}
+	}
+
+	pub fn range<T: PartialOrd>(self, field: &'static str, value: T, min: T, max: T) -> Self {
+		self.check(field, "range", min <= value && value <= max)
+	}
+
+	pub fn check(self, field: &'static str, code: &'static str, valid: bool) -> Self {
+		if valid {
+			self
+		} else {
+			self.error(field, code)
+		}
+	}
+
+	pub fn result(self) -> ValidationResult {
+		if self.errors.is_empty() {
+			Ok(())
+		} else {
+			Err(ValidationError {
+				fields: self.errors,
+			})
+		}
+	}
+
+	fn error(mut self, field: &'static str, code: &'static str) -> Self {
+		self.errors
+			.entry(field.into())
+			.or_insert_with(|| code.into());
+		self
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+impl ActionError for ValidationError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		None
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		let params: HashMap<_, _> = self.fields.clone().into_iter().collect();
+
+		Some(ErrorData {
+			code: "validation.invalid_input".into(),
+			msg: "The input is invalid.".into(),
+			params: Some(params),
+		})
+	}
+
+	fn category(&self) -> ErrorCategory {
+		ErrorCategory::Validation
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::collections::{BTreeMap, HashMap};
+
+	use crate::core::action::data::action_data::{ErrorCategory, ErrorData};
+	use crate::core::action::data::validation_data::{TextRule, ValidationError, Validator};
+	use crate::core::action::definition::action::ActionError;
+	use crate::tests::test_utils::tests::run_test;
+
+	fn digits(value: &str) -> bool {
+		value.chars().all(|char| char.is_ascii_digit())
+	}
+
+	#[tokio::test]
+	async fn test_text_ok() {
+		run_test(|_| async {
+			let result = Validator::new()
+				.text("name", "User 01", &[TextRule::Required])
+				.text("email", "user-01@domain.test", &[TextRule::Email])
+				.text("pass", "p4$$w0rd", &[TextRule::Length { min: 8, max: 8 }])
+				.text(
+					"code",
+					"0123",
+					&[TextRule::Format {
+						code: "digits",
+						check: digits,
+					}],
+				)
+				.range("age", 18, 0, 150)
+				.result();
+
+			assert_eq!(result, Ok(()));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_text_error() {
+		run_test(|_| async {
+			let result = Validator::new()
+				.text(
+					"name",
+					" ",
+					&[TextRule::Required, TextRule::Length { min: 3, max: 5 }],
+				)
+				.text("email", "user-01@domain", &[TextRule::Email])
+				.text("pass", "p4$$", &[TextRule::Length { min: 8, max: 128 }])
+				.text(
+					"code",
+					"01a3",
+					&[TextRule::Format {
+						code: "digits",
+						check: digits,
+					}],
+				)
+				.range("age", 151, 0, 150)
+				.result();
+
+			let fields = BTreeMap::from([
+				("name".into(), "required".into()),
+				("email".into(), "email".into()),
+				("pass".into(), "length".into()),
+				("code".into(), "digits".into()),
+				("age".into(), "range".into()),
+			]);
+
+			assert_eq!(result, Err(ValidationError { fields }));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_email() {
+		run_test(|_| async {
+			let valid = ["a@b.co", "user.name+tag@sub.domain.test"];
+			let invalid = [
+				"", "a", "a@", "@b.co", "a@b", "a@@b.co", "a@b..co", "a b@c.co",
+			];
+
+			for email in valid {
+				assert!(super::is_email(email), "{email}");
+			}
+
+			for email in invalid {
+				assert!(!super::is_email(email), "{email}");
+			}
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_public_error() {
+		run_test(|_| async {
+			let error = ValidationError {
+				fields: BTreeMap::from([("email".into(), "email".into())]),
+			};
+
+			assert_eq!(
+				error.public_error(),
+				Some(ErrorData {
+					code: "validation.invalid_input".into(),
+					msg: "The input is invalid.".into(),
+					params: Some(HashMap::from([("email".into(), "email".into())])),
+				})
+			);
+			assert_eq!(error.category(), ErrorCategory::Validation);
+		})
+		.await;
+	}
+}
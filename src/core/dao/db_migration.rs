@@ -1,9 +1,6 @@
 use rocket::{fairing::AdHoc, Build, Rocket};
 
-use rocket_sync_db_pools::diesel;
-
-#[database("main")]
-struct Db(diesel::SqliteConnection);
+use crate::core::{dao::db_pool, external::data::external_exception::ExternalException};
 
 async fn run_migrations(rocket: Rocket<Build>) -> Rocket<Build> {
 	// This macro from `diesel_migrations` defines an `embedded_migrations`
@@ -11,9 +8,9 @@ async fn run_migrations(rocket: Rocket<Build>) -> Rocket<Build> {
 	// specified directory, initializing the database.
 	embed_migrations!("migrations");
 
-	let conn = Db::get_one(&rocket).await.expect("database connection");
-	conn.run(|c| embedded_migrations::run(c))
+	db_pool::run(|c| Ok::<_, ExternalException>(embedded_migrations::run(c)))
 		.await
+		.expect("database connection")
 		.expect("diesel migrations");
 
 	rocket
@@ -22,7 +19,7 @@ async fn run_migrations(rocket: Rocket<Build>) -> Rocket<Build> {
 pub fn stage() -> AdHoc {
 	AdHoc::on_ignite("Diesel SQLite Stage", |rocket| async {
 		rocket
-			.attach(Db::fairing())
+			.attach(db_pool::stage())
 			.attach(AdHoc::on_ignite("Diesel Migrations", run_migrations))
 	})
 }
@@ -0,0 +1,69 @@
+use std::sync::OnceLock;
+
+use rocket::{fairing::AdHoc, Build, Rocket};
+use rocket_sync_db_pools::{diesel::SqliteConnection, r2d2, Poolable};
+
+use crate::core::external::data::external_exception::ExternalException;
+
+pub const MAIN_DB: &str = "main";
+
+type DbPool = r2d2::Pool<<SqliteConnection as Poolable>::Manager>;
+
+static MAIN_POOL: OnceLock<DbPool> = OnceLock::new();
+
+async fn init_pool(rocket: Rocket<Build>) -> Result<Rocket<Build>, Rocket<Build>> {
+	if MAIN_POOL.get().is_some() {
+		return Ok(rocket);
+	}
+
+	// The pool is created with the same `databases.main` configuration (and the
+	// same connection setup) that `rocket_sync_db_pools` uses for `#[database]`
+	// guards, but is kept globally so that the external actions, which don't
+	// receive a request, can run queries.
+	let (rocket, result) = tokio::task::spawn_blocking(move || {
+		let result = SqliteConnection::pool(MAIN_DB, &rocket);
+		(rocket, result)
+	})
+	.await
+	.expect("database pool initialization");
+
+	match result {
+		Ok(pool) => {
+			let _ = MAIN_POOL.set(pool);
+			Ok(rocket)
+		}
+		Err(error) => {
+			error!("database pool error for `{MAIN_DB}`: {error:?}");
+			Err(rocket)
+		}
+	}
+}
+
+pub fn stage() -> AdHoc {
+	AdHoc::try_on_ignite("Main Database Pool", init_pool)
+}
+
+pub async fn run<F, R, E>(function: F) -> Result<R, ExternalException>
+where
+	F: FnOnce(&SqliteConnection) -> Result<R, E> + Send + 'static,
+	R: Send + 'static,
+	E: Into<ExternalException> + Send + 'static,
+{
+	let pool = MAIN_POOL
+		.get()
+		.cloned()
+		.ok_or_else(|| ExternalException::DbPoolError("database pool not initialized".into()))?;
+
+	let result = tokio::task::spawn_blocking(move || {
+		let conn = pool
+			.get()
+			.map_err(|error| ExternalException::DbPoolError(error.to_string().into()))?;
+		function(&conn).map_err(Into::into)
+	})
+	.await;
+
+	match result {
+		Ok(result) => result,
+		Err(error) => std::panic::resume_unwind(error.into_panic()),
+	}
+}
@@ -1 +1,2 @@
 pub mod db_migration;
+pub mod db_pool;
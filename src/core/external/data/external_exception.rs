@@ -1,6 +1,9 @@
-use crate::core::action::{
-	data::action_data::{DescriptiveError, ErrorData},
-	definition::action::ActionError,
+use crate::{
+	core::action::{
+		data::action_data::{DescriptiveError, ErrorCategory, ErrorData},
+		definition::action::ActionError,
+	},
+	lib::data::str::Str,
 };
 
 #[derive(Debug)]
@@ -10,7 +13,10 @@ pub struct DbErrorWrapper(pub diesel::result::Error);
 #[allow(dead_code)]
 pub enum ExternalException {
 	Unknown,
+	NotFound,
 	DbError(DbErrorWrapper),
+	DbPoolError(Str),
+	MailError(Str),
 }
 
 impl PartialEq for DbErrorWrapper {
@@ -23,7 +29,10 @@ impl Eq for DbErrorWrapper {}
 
 impl From<diesel::result::Error> for ExternalException {
 	fn from(error: diesel::result::Error) -> Self {
-		ExternalException::DbError(DbErrorWrapper(error))
+		match error {
+			diesel::result::Error::NotFound => ExternalException::NotFound,
+			error => ExternalException::DbError(DbErrorWrapper(error)),
+		}
 	}
 }
 
@@ -31,16 +40,44 @@ impl ActionError for ExternalException {
 	fn private_error(&self) -> Option<DescriptiveError> {
 		match self {
 			ExternalException::Unknown => Some(DescriptiveError::empty()),
+			ExternalException::NotFound => None,
 			ExternalException::DbError(DbErrorWrapper(source)) => {
 				Some(DescriptiveError::source(source))
 			}
+			ExternalException::DbPoolError(msg) | ExternalException::MailError(msg) => {
+				Some(DescriptiveError {
+					msg: Some(msg.clone()),
+					data: None,
+					source: None,
+				})
+			}
 		}
 	}
 
 	fn public_error(&self) -> Option<ErrorData> {
 		match self {
 			ExternalException::Unknown => None,
+			ExternalException::NotFound => Self::error_msg(
+				"external.not_found",
+				"The requested item was not found.".into(),
+			),
 			ExternalException::DbError(_) => None,
+			ExternalException::DbPoolError(_) => None,
+			ExternalException::MailError(_) => None,
+		}
+	}
+
+	fn category(&self) -> ErrorCategory {
+		match self {
+			ExternalException::Unknown => ErrorCategory::Internal,
+			ExternalException::NotFound => ErrorCategory::NotFound,
+			ExternalException::DbError(DbErrorWrapper(diesel::result::Error::DatabaseError(
+				diesel::result::DatabaseErrorKind::UniqueViolation,
+				_,
+			))) => ErrorCategory::Conflict,
+			ExternalException::DbError(_) => ErrorCategory::Internal,
+			ExternalException::DbPoolError(_) => ErrorCategory::Internal,
+			ExternalException::MailError(_) => ErrorCategory::Upstream,
 		}
 	}
 }
@@ -25,5 +25,11 @@ pub mod tests {
 
 	pub trait ExternalMocker<I, O>: ExternalTest<I, O> {
 		fn mock(input: I, output: O) -> mockito::Mock;
+
+		/// Mocks the calls with any input matched (as json) by the matcher.
+		fn mock_matching(input: mockito::Matcher, output: O) -> mockito::Mock;
+
+		/// Mocks the calls with any input, that no other mock matches.
+		fn mock_fallback(output: O) -> mockito::Mock;
 	}
 }
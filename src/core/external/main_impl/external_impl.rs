@@ -1,7 +1,7 @@
 #[cfg(test)]
 pub mod tests {
-	use crate::tests::test_utils::tests::run_test;
-	use mockito::{mock, Mock};
+	use crate::tests::test_utils::tests::{init_mocks, run_test};
+	use mockito::{mock, Matcher, Mock};
 	use serde::de::DeserializeOwned;
 
 	use crate::{
@@ -20,6 +20,8 @@ pub mod tests {
 		I: serde::Serialize,
 		O: DeserializeOwned,
 	{
+		init_mocks();
+
 		let method = match method {
 			MockExternalMethod::Select => reqwest::Method::GET,
 			MockExternalMethod::Insert => reqwest::Method::PUT,
@@ -50,6 +52,38 @@ pub mod tests {
 	where
 		I: serde::Serialize,
 		O: serde::Serialize,
+	{
+		let input = Matcher::Exact(serde_json::to_string(&input).unwrap());
+		mock_external_matching(action, method, input, output)
+	}
+
+	/// Mocks the calls with the inputs (as json) matched by the matcher.
+	pub fn mock_external_matching<O>(
+		action: Str,
+		method: MockExternalMethod,
+		input: Matcher,
+		output: O,
+	) -> Mock
+	where
+		O: serde::Serialize,
+	{
+		mock_request(action, method, input, output).create()
+	}
+
+	/// Mocks the calls with any input, that aren't matched by another mock.
+	pub fn mock_external_fallback<O>(action: Str, method: MockExternalMethod, output: O) -> Mock
+	where
+		O: serde::Serialize,
+	{
+		// no call is expected, so that the other mocks are always used first
+		mock_request(action, method, Matcher::Any, output)
+			.expect(0)
+			.create()
+	}
+
+	fn mock_request<O>(action: Str, method: MockExternalMethod, input: Matcher, output: O) -> Mock
+	where
+		O: serde::Serialize,
 	{
 		let method = match method {
 			MockExternalMethod::Select => "GET",
@@ -59,10 +93,9 @@ pub mod tests {
 		};
 		let output = serde_json::to_string(&output).unwrap();
 		mock(method, format!("/mock/dao/{action}").as_ref())
-			.match_body(serde_json::to_string(&input).unwrap().as_ref())
+			.match_body(input)
 			.with_body(output.as_ref())
 			.with_status(200)
-			.create()
 	}
 
 	#[rocket::async_trait]
@@ -86,6 +119,14 @@ pub mod tests {
 		fn mock(input: I, output: O) -> mockito::Mock {
 			mock_external(Self::name(), Self::method(), input, output)
 		}
+
+		fn mock_matching(input: Matcher, output: O) -> mockito::Mock {
+			mock_external_matching(Self::name(), Self::method(), input, output)
+		}
+
+		fn mock_fallback(output: O) -> mockito::Mock {
+			mock_external_fallback(Self::name(), Self::method(), output)
+		}
 	}
 
 	#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
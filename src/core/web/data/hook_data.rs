@@ -0,0 +1,80 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use futures::future::BoxFuture;
+use rocket::serde::json::Value;
+
+use crate::{
+	core::{
+		action::{
+			action_type::automatic_action_type::AutomaticActionType,
+			data::{
+				action_data::{Application, Request},
+				automatic_action_data::AutomaticRequestContext,
+			},
+		},
+		web::definition::web_action::WebActionResult,
+	},
+	lib::data::str::Str,
+};
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// Runs the body (as json) with the automatic action, as a hook request.
+pub type HookRunner =
+	fn(AutomaticRequestContext, String) -> BoxFuture<'static, WebActionResult<Value>>;
+
+/// An automatic action that can be called from `/hook/<source>/<action>`.
+#[derive(Clone, Copy)]
+pub struct HookDefinition {
+	pub action_type: AutomaticActionType,
+	pub runner: HookRunner,
+}
+
+/// A caller of the hooks, that signs its requests with the shared secret.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct HookSourceConfig {
+	pub secret: String,
+	pub allowed_actions: Vec<AutomaticActionType>,
+}
+
+/// The requests signed more than `replay_window` seconds before (or after)
+/// the server time are rejected.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct HookConfig {
+	pub replay_window: i64,
+	#[serde(default)]
+	pub sources: HashMap<String, HookSourceConfig>,
+}
+
+/// The signatures accepted from each source (with their timestamps) that are
+/// still within the replay window, so that a request can't be sent twice.
+#[derive(Debug, Default)]
+pub struct HookReplays(pub Mutex<HashMap<String, HashMap<Vec<u8>, i64>>>);
+
+/// The request of a hook, with the signature headers; the signature is
+/// checked against the body by the route.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HookContext {
+	pub application: Application,
+	pub request: Request,
+	pub timestamp: Option<Str>,
+	pub signature: Option<Str>,
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum HookError {
+	UnknownSource,
+	MissingSignature,
+	InvalidSignature,
+	InvalidTimestamp,
+	Replayed,
+	UnknownAction,
+	NotAllowed(AutomaticActionType),
+	InvalidBody(Str),
+}
@@ -0,0 +1,2 @@
+pub mod hook_data;
+pub mod web_data;
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use rocket::http::Status;
+
+use crate::{core::action::data::action_data::ErrorData, lib::data::str::Str};
+
+pub const PROBLEM_TYPE_PREFIX: &str = "/problems/";
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// The unix time (in seconds) at which the hook request was signed.
+pub const HOOK_TIMESTAMP_HEADER: &str = "X-Hook-Timestamp";
+
+/// The hex encoded HMAC-SHA256 of `<timestamp>.<body>`, with the secret of
+/// the source.
+pub const HOOK_SIGNATURE_HEADER: &str = "X-Hook-Signature";
+
+#[derive(Debug)]
+pub struct WebError {
+	pub status: Status,
+	pub scope: Str,
+	pub action_id: u32,
+	pub error: Option<ErrorData>,
+}
+
+/// The error body of the clients that prefer plain json to the problem
+/// details.
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct LegacyError {
+	#[serde(flatten)]
+	pub error: Option<ErrorData>,
+	pub request_id: Str,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct ProblemDetails {
+	#[serde(rename = "type")]
+	pub problem_type: Str,
+	pub title: Str,
+	pub status: u16,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub detail: Option<Str>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Str>,
+	pub scope: Str,
+	pub action_id: u32,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub params: Option<HashMap<Str, Str>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub request_id: Option<Str>,
+}
+
+impl From<WebError> for ProblemDetails {
+	fn from(error: WebError) -> Self {
+		let WebError {
+			status,
+			scope,
+			action_id,
+			error,
+		} = error;
+
+		let problem_type = error
+			.as_ref()
+			.map(|data| format!("{PROBLEM_TYPE_PREFIX}{code}", code = data.code).into())
+			.unwrap_or_else(|| "about:blank".into());
+
+		Self {
+			problem_type,
+			title: status.reason().unwrap_or_default().into(),
+			status: status.code,
+			detail: error.as_ref().map(|data| data.msg.clone()),
+			code: error.as_ref().map(|data| data.code.clone()),
+			scope,
+			action_id,
+			params: error.and_then(|data| data.params),
+			request_id: None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use rocket::http::Status;
+
+	use super::{ProblemDetails, WebError};
+	use crate::core::action::data::action_data::ErrorData;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_problem_from_error() {
+		run_test(|_| async {
+			let params = HashMap::from([("field".into(), "email".into())]);
+			let error = WebError {
+				status: Status::BadRequest,
+				scope: "User".into(),
+				action_id: 5,
+				error: Some(ErrorData {
+					code: "user.confirmation_token_invalid".into(),
+					msg: "The confirmation token is invalid or has expired.".into(),
+					params: Some(params.clone()),
+				}),
+			};
+
+			assert_eq!(
+				ProblemDetails::from(error),
+				ProblemDetails {
+					problem_type: "/problems/user.confirmation_token_invalid".into(),
+					title: "Bad Request".into(),
+					status: 400,
+					detail: Some("The confirmation token is invalid or has expired.".into()),
+					code: Some("user.confirmation_token_invalid".into()),
+					scope: "User".into(),
+					action_id: 5,
+					params: Some(params),
+					request_id: None,
+				}
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_problem_without_public_error() {
+		run_test(|_| async {
+			let error = WebError {
+				status: Status::InternalServerError,
+				scope: "User".into(),
+				action_id: 3,
+				error: None,
+			};
+
+			assert_eq!(
+				ProblemDetails::from(error),
+				ProblemDetails {
+					problem_type: "about:blank".into(),
+					title: "Internal Server Error".into(),
+					status: 500,
+					detail: None,
+					code: None,
+					scope: "User".into(),
+					action_id: 3,
+					params: None,
+					request_id: None,
+				}
+			);
+		})
+		.await;
+	}
+}
@@ -1,18 +1,19 @@
 use crate::{
 	core::action::{
 		action_type::general_action_type::ActionType,
-		data::action_data::{ErrorData, RequestInput},
+		data::action_data::RequestInput,
 		definition::{
 			action::{ActionError, ActionInput, ActionOutput},
 			action_helpers::DescriptiveRequestContext,
 		},
 	},
+	core::web::data::web_data::WebError,
 	lib::traits::async_from::AsyncInto,
 };
 use rocket::serde::json::Json;
 use std::fmt::Debug;
 
-pub type WebActionResult<O> = Result<Json<O>, Json<Option<ErrorData>>>;
+pub type WebActionResult<O> = Result<Json<O>, WebError>;
 
 #[rocket::async_trait]
 pub trait WebAction<I, O, E, R, C, A, N>: Debug
@@ -25,5 +26,5 @@ where
 	A: ActionType,
 	N: AsyncInto<Result<RequestInput<I, C>, R>> + Send + 'static,
 {
-	async fn request(input: N) -> Result<Json<O>, Json<Option<ErrorData>>>;
+	async fn request(input: N) -> WebActionResult<O>;
 }
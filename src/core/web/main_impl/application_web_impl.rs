@@ -0,0 +1,14 @@
+use rocket::fairing::AdHoc;
+
+use crate::core::action::data::action_data::{Application, DEFAULT_REQUEST_TIMEOUT};
+
+pub fn stage() -> AdHoc {
+	AdHoc::on_ignite("Application Config", |rocket| async {
+		let request_timeout = rocket
+			.figment()
+			.extract_inner::<u32>("request_timeout")
+			.unwrap_or(DEFAULT_REQUEST_TIMEOUT);
+
+		rocket.manage(Application { request_timeout })
+	})
+}
@@ -0,0 +1,591 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::Utc;
+use futures::future::BoxFuture;
+use hmac::{Hmac, Mac};
+use rocket::{
+	fairing::AdHoc,
+	request::{self, FromRequest},
+	serde::json::{Json, Value},
+	Build, Rocket,
+};
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+
+use crate::{
+	core::{
+		action::{
+			action_type::{
+				automatic_action_type::AutomaticActionType, general_action_type::ActionType,
+			},
+			data::{
+				action_data::{DescriptiveError, ErrorCategory, ErrorData, RequestInput},
+				automatic_action_data::{
+					AutomaticActionError, AutomaticRequest, AutomaticRequestContext,
+					AutomaticRequestInput,
+				},
+			},
+			definition::action::{ActionError, ActionInput, ActionOutput, AutomaticAction},
+		},
+		web::{
+			data::{
+				hook_data::{
+					HookConfig, HookContext, HookDefinition, HookError, HookReplays, HookRunner,
+					HookSourceConfig,
+				},
+				web_data::{WebError, HOOK_SIGNATURE_HEADER, HOOK_TIMESTAMP_HEADER},
+			},
+			definition::web_action::{WebAction, WebActionResult},
+			main_impl::{user_web_impl, web_impl},
+		},
+	},
+	lib::data::str::Str,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const DEFAULT_REPLAY_WINDOW: i64 = 300;
+
+// const initialized, so the actions can register from their ctors in any
+// order
+static HOOKS: Mutex<Vec<HookDefinition>> = Mutex::new(Vec::new());
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for HookContext {
+	type Error = ();
+
+	async fn from_request(req: &'r request::Request<'_>) -> request::Outcome<Self, Self::Error> {
+		let header = |name| {
+			req.headers()
+				.get_one(name)
+				.map(str::trim)
+				.filter(|value| !value.is_empty())
+				.map(|value| Str::from(value.to_string()))
+		};
+
+		request::Outcome::Success(HookContext {
+			application: user_web_impl::application(req),
+			request: user_web_impl::request(req),
+			timestamp: header(HOOK_TIMESTAMP_HEADER),
+			signature: header(HOOK_SIGNATURE_HEADER),
+		})
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// ERROR /////////////////////
+////////////////////////////////////////////////
+
+impl ActionError for HookError {
+	fn private_error(&self) -> Option<DescriptiveError> {
+		match self {
+			HookError::InvalidBody(error) => Some(DescriptiveError::source(error)),
+			_ => None,
+		}
+	}
+
+	fn public_error(&self) -> Option<ErrorData> {
+		match self {
+			HookError::MissingSignature => Self::error_msg(
+				"hook.signature_missing",
+				"The hook request must be signed.".into(),
+			),
+			// the sources that aren't configured can't be told apart from the
+			// wrong secrets
+			HookError::UnknownSource | HookError::InvalidSignature => Self::error_msg(
+				"hook.signature_invalid",
+				"The signature of the hook request is invalid.".into(),
+			),
+			HookError::InvalidTimestamp => Self::error_msg(
+				"hook.timestamp_invalid",
+				"The timestamp of the hook request is invalid or expired.".into(),
+			),
+			HookError::Replayed => Self::error_msg(
+				"hook.request_replayed",
+				"The hook request was already received.".into(),
+			),
+			HookError::UnknownAction => {
+				Self::error_msg("hook.unknown_action", "The hook action is unknown.".into())
+			}
+			HookError::NotAllowed(_) => Self::error_msg(
+				"hook.not_allowed",
+				"The hook source is not allowed to call this action.".into(),
+			),
+			HookError::InvalidBody(_) => Self::error_msg(
+				"hook.invalid_body",
+				"The body of the hook request is invalid.".into(),
+			),
+		}
+	}
+
+	fn category(&self) -> ErrorCategory {
+		match self {
+			HookError::UnknownSource => ErrorCategory::Unauthenticated,
+			HookError::MissingSignature => ErrorCategory::Unauthenticated,
+			HookError::InvalidSignature => ErrorCategory::Unauthenticated,
+			HookError::InvalidTimestamp => ErrorCategory::Unauthenticated,
+			HookError::Replayed => ErrorCategory::Unauthenticated,
+			HookError::UnknownAction => ErrorCategory::NotFound,
+			HookError::NotAllowed(_) => ErrorCategory::Forbidden,
+			HookError::InvalidBody(_) => ErrorCategory::Validation,
+		}
+	}
+}
+
+fn web_error(action_id: u32, error: HookError) -> WebError {
+	WebError {
+		status: web_impl::status(error.category()),
+		scope: AutomaticActionType::scope().to_string().into(),
+		action_id,
+		error: error.public_error(),
+	}
+}
+
+////////////////////////////////////////////////
+/////////////////// REGISTRY ///////////////////
+////////////////////////////////////////////////
+
+fn handle<A, I, O, E>(
+	context: AutomaticRequestContext,
+	body: String,
+) -> BoxFuture<'static, WebActionResult<Value>>
+where
+	A: AutomaticAction<I, O, E> + Send + 'static,
+	I: ActionInput + DeserializeOwned + Send + 'static,
+	O: ActionOutput,
+	E: ActionError + From<AutomaticActionError> + Send,
+{
+	Box::pin(async move {
+		let data = serde_json::from_str::<I>(&body).map_err(|error| {
+			web_error(
+				A::action_type().id(),
+				HookError::InvalidBody(error.to_string().into()),
+			)
+		})?;
+		let input: AutomaticRequestInput<I> = RequestInput { context, data };
+
+		let Json(output) = <A as WebAction<
+			I,
+			O,
+			E,
+			AutomaticActionError,
+			AutomaticRequestContext,
+			AutomaticActionType,
+			AutomaticRequestInput<I>,
+		>>::request(input)
+		.await?;
+
+		Ok(Json(serde_json::to_value(output).unwrap_or_default()))
+	})
+}
+
+/// Allows the automatic action to be called from the hook route, by the
+/// sources configured for it.
+pub fn register<A, I, O, E>()
+where
+	A: AutomaticAction<I, O, E> + Send + 'static,
+	I: ActionInput + DeserializeOwned + Send + 'static,
+	O: ActionOutput,
+	E: ActionError + From<AutomaticActionError> + Send,
+{
+	let runner: HookRunner = handle::<A, I, O, E>;
+	HOOKS.lock().unwrap().push(HookDefinition {
+		action_type: A::action_type(),
+		runner,
+	});
+}
+
+pub fn definitions() -> Vec<HookDefinition> {
+	HOOKS.lock().unwrap().clone()
+}
+
+////////////////////////////////////////////////
+////////////////// SIGNATURE ///////////////////
+////////////////////////////////////////////////
+
+fn mac(secret: &str, timestamp: &str, body: &str) -> HmacSha256 {
+	// hmac accepts keys of any length
+	let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+	mac.update(timestamp.as_bytes());
+	mac.update(b".");
+	mac.update(body.as_bytes());
+	mac
+}
+
+/// Authenticates the source of the request, signed with its secret at most
+/// `replay_window` seconds away from `now`, and only once.
+pub fn verify<'a>(
+	config: &'a HookConfig,
+	replays: &HookReplays,
+	source: &str,
+	context: &HookContext,
+	body: &str,
+	now: i64,
+) -> Result<&'a HookSourceConfig, HookError> {
+	let (timestamp, signature) = match (&context.timestamp, &context.signature) {
+		(Some(timestamp), Some(signature)) => (timestamp, signature),
+		_ => return Err(HookError::MissingSignature),
+	};
+
+	let name = source;
+	let source = config.sources.get(name).ok_or(HookError::UnknownSource)?;
+
+	// the signature is compared in constant time
+	let signature = hex::decode(signature.as_bytes()).map_err(|_| HookError::InvalidSignature)?;
+	mac(&source.secret, timestamp, body)
+		.verify_slice(&signature)
+		.map_err(|_| HookError::InvalidSignature)?;
+
+	let timestamp = timestamp
+		.parse::<i64>()
+		.map_err(|_| HookError::InvalidTimestamp)?;
+	let window = config.replay_window.unsigned_abs();
+	if now.abs_diff(timestamp) > window {
+		return Err(HookError::InvalidTimestamp);
+	}
+
+	// only the signatures within the window are kept, the older ones are
+	// rejected by their timestamp
+	let mut replays = replays.0.lock().unwrap_or_else(|error| error.into_inner());
+	let seen = replays.entry(name.to_string()).or_default();
+	seen.retain(|_, seen_at| now.abs_diff(*seen_at) <= window);
+	if seen.insert(signature, timestamp).is_some() {
+		return Err(HookError::Replayed);
+	}
+
+	Ok(source)
+}
+
+// the action is given by its id or by its name
+fn action_type(action: &str) -> Option<AutomaticActionType> {
+	action
+		.parse::<u32>()
+		.ok()
+		.and_then(AutomaticActionType::from_id)
+		.or_else(|| AutomaticActionType::from_name(action))
+}
+
+// the source must be verified first
+fn authorize(
+	source: &HookSourceConfig,
+	definitions: &[HookDefinition],
+	action_type: Option<AutomaticActionType>,
+) -> Result<HookDefinition, HookError> {
+	let action_type = action_type.ok_or(HookError::UnknownAction)?;
+
+	if !source.allowed_actions.contains(&action_type) {
+		return Err(HookError::NotAllowed(action_type));
+	}
+
+	definitions
+		.iter()
+		.find(|definition| definition.action_type == action_type)
+		.copied()
+		.ok_or(HookError::UnknownAction)
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTION /////////////////////
+////////////////////////////////////////////////
+
+/// Calls the automatic action with the body, as a hook request, once the
+/// source is authenticated and allowed to call it.
+pub async fn call(
+	config: &HookConfig,
+	replays: &HookReplays,
+	context: HookContext,
+	source: &str,
+	action: &str,
+	body: String,
+) -> WebActionResult<Value> {
+	let action_type = action_type(action);
+	let now = Utc::now().timestamp();
+
+	let definition = verify(config, replays, source, &context, &body, now)
+		.and_then(|source| authorize(source, &definitions(), action_type))
+		.map_err(|error| {
+			warn!("[hook] the request of {source:?} for {action:?} was rejected: {error:?}");
+			web_error(action_type.map(|item| item.id()).unwrap_or_default(), error)
+		})?;
+
+	let HookContext {
+		application,
+		request,
+		..
+	} = context;
+	let context = AutomaticRequestContext {
+		application,
+		request: AutomaticRequest::Hook(request),
+	};
+
+	(definition.runner)(context, body).await
+}
+
+async fn load_config(rocket: Rocket<Build>) -> Result<Rocket<Build>, Rocket<Build>> {
+	// a missing section has no sources, an invalid one stops the launch
+	let config = if rocket.figment().find_value("hooks").is_ok() {
+		rocket.figment().extract_inner::<HookConfig>("hooks")
+	} else {
+		Ok(HookConfig {
+			replay_window: DEFAULT_REPLAY_WINDOW,
+			sources: HashMap::new(),
+		})
+	};
+
+	let config = match config {
+		Ok(config) => config,
+		Err(error) => {
+			error!("[hook] the hook config is invalid: {error}");
+			return Err(rocket);
+		}
+	};
+
+	// anyone could sign the requests of a source without a secret
+	let unsigned = config
+		.sources
+		.iter()
+		.find(|(_, source)| source.secret.trim().is_empty());
+	if let Some((name, _)) = unsigned {
+		error!("[hook] the hook config is invalid: the source {name} has no secret");
+		return Err(rocket);
+	}
+
+	Ok(rocket.manage(config).manage(HookReplays::default()))
+}
+
+pub fn stage() -> AdHoc {
+	AdHoc::try_on_ignite("Hook Config", load_config)
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use std::collections::HashMap;
+
+	use hmac::Mac;
+
+	use crate::core::action::action_type::automatic_action_type::AutomaticActionType;
+	use crate::core::action::data::action_data::tests::{ApplicationBuilder, RequestBuilder};
+	use crate::core::web::data::hook_data::{
+		HookConfig, HookContext, HookError, HookReplays, HookSourceConfig,
+	};
+	use crate::tests::test_utils::tests::run_test;
+
+	const NOW: i64 = 1_650_000_000;
+
+	pub fn sign(secret: &str, timestamp: &str, body: &str) -> String {
+		hex::encode(super::mac(secret, timestamp, body).finalize().into_bytes())
+	}
+
+	pub fn config() -> HookConfig {
+		HookConfig {
+			replay_window: 300,
+			sources: HashMap::from([(
+				"billing".into(),
+				HookSourceConfig {
+					secret: "billing-secret".into(),
+					allowed_actions: vec![AutomaticActionType::Auto],
+				},
+			)]),
+		}
+	}
+
+	fn context(timestamp: i64, signature: &str) -> HookContext {
+		HookContext {
+			application: ApplicationBuilder::new().build(),
+			request: RequestBuilder::new().build(),
+			timestamp: Some(timestamp.to_string().into()),
+			signature: Some(signature.to_string().into()),
+		}
+	}
+
+	fn signed(secret: &str, timestamp: i64, body: &str) -> HookContext {
+		context(timestamp, &sign(secret, &timestamp.to_string(), body))
+	}
+
+	#[tokio::test]
+	async fn test_sign() {
+		run_test(|_| async {
+			// the HMAC-SHA256 of `1650000000.{"param1":"a","param2":1}`
+			assert_eq!(
+				sign(
+					"billing-secret",
+					&NOW.to_string(),
+					r#"{"param1":"a","param2":1}"#
+				),
+				"f24429bffb41ee7d1c9cf7a4a0350883b39a421e41d60aaa4f99090f099aae4e"
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_verify() {
+		run_test(|_| async {
+			let config = config();
+			let replays = HookReplays::default();
+			let body = r#"{"param1":"a","param2":1}"#;
+			let verify = |source, context: &HookContext| {
+				super::verify(&config, &replays, source, context, body, NOW)
+			};
+
+			assert_eq!(
+				verify("billing", &signed("billing-secret", NOW - 10, body)),
+				Ok(&config.sources["billing"])
+			);
+			assert_eq!(
+				verify("shipping", &signed("billing-secret", NOW, body)),
+				Err(HookError::UnknownSource)
+			);
+			assert_eq!(
+				verify(
+					"shipping",
+					&HookContext {
+						signature: None,
+						..signed("billing-secret", NOW, body)
+					}
+				),
+				Err(HookError::MissingSignature)
+			);
+			assert_eq!(
+				verify("billing", &signed("other-secret", NOW, body)),
+				Err(HookError::InvalidSignature)
+			);
+			assert_eq!(
+				verify("billing", &context(NOW, "not hex")),
+				Err(HookError::InvalidSignature)
+			);
+			assert_eq!(
+				super::verify(
+					&config,
+					&replays,
+					"billing",
+					&signed("billing-secret", NOW, body),
+					r#"{"param1":"b","param2":1}"#,
+					NOW
+				),
+				Err(HookError::InvalidSignature)
+			);
+			assert_eq!(
+				verify(
+					"billing",
+					&HookContext {
+						signature: None,
+						..signed("billing-secret", NOW, body)
+					}
+				),
+				Err(HookError::MissingSignature)
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_verify_replay_window() {
+		run_test(|_| async {
+			let config = config();
+			let replays = HookReplays::default();
+			let verify = |timestamp| {
+				super::verify(
+					&config,
+					&replays,
+					"billing",
+					&signed("billing-secret", timestamp, ""),
+					"",
+					NOW,
+				)
+				.map(|_| ())
+			};
+
+			assert_eq!(verify(NOW - 300), Ok(()));
+			assert_eq!(verify(NOW + 300), Ok(()));
+			assert_eq!(verify(NOW - 301), Err(HookError::InvalidTimestamp));
+			assert_eq!(verify(NOW + 301), Err(HookError::InvalidTimestamp));
+			assert_eq!(verify(i64::MIN), Err(HookError::InvalidTimestamp));
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_verify_replayed() {
+		run_test(|_| async {
+			let mut config = config();
+			config.sources.insert(
+				"shipping".into(),
+				HookSourceConfig {
+					secret: "shipping-secret".into(),
+					allowed_actions: vec![],
+				},
+			);
+			let replays = HookReplays::default();
+			let verify = |source, secret, timestamp, now| {
+				super::verify(
+					&config,
+					&replays,
+					source,
+					&signed(secret, timestamp, ""),
+					"",
+					now,
+				)
+				.map(|_| ())
+			};
+
+			assert_eq!(verify("billing", "billing-secret", NOW, NOW), Ok(()));
+			assert_eq!(
+				verify("billing", "billing-secret", NOW, NOW + 10),
+				Err(HookError::Replayed)
+			);
+			// the signatures are kept by source
+			assert_eq!(verify("shipping", "shipping-secret", NOW, NOW), Ok(()));
+			assert_eq!(
+				verify("billing", "billing-secret", NOW + 1, NOW + 10),
+				Ok(())
+			);
+
+			// out of the window, the timestamp is rejected first
+			assert_eq!(
+				verify("billing", "billing-secret", NOW, NOW + 301),
+				Err(HookError::InvalidTimestamp)
+			);
+			// the signatures out of the window are dropped
+			assert_eq!(
+				verify("billing", "billing-secret", NOW + 400, NOW + 400),
+				Ok(())
+			);
+			assert_eq!(replays.0.lock().unwrap()["billing"].len(), 1);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_authorize() {
+		run_test(|_| async {
+			let config = config();
+			let definitions = super::definitions();
+			let authorize = |action| {
+				super::authorize(
+					&config.sources["billing"],
+					&definitions,
+					super::action_type(action),
+				)
+				.map(|definition| definition.action_type)
+			};
+
+			assert_eq!(authorize("1"), Ok(AutomaticActionType::Auto));
+			assert_eq!(authorize("Auto"), Ok(AutomaticActionType::Auto));
+			assert_eq!(
+				authorize("Web"),
+				Err(HookError::NotAllowed(AutomaticActionType::Web))
+			);
+			assert_eq!(authorize("999"), Err(HookError::UnknownAction));
+		})
+		.await;
+	}
+}
@@ -1,4 +1,7 @@
+pub mod application_web_impl;
 pub mod automatic_web_impl;
+pub mod hook_web_impl;
 pub mod moderator_web_impl;
+pub mod request_id_web_impl;
 pub mod user_web_impl;
 pub mod web_impl;
@@ -0,0 +1,78 @@
+use rocket::{fairing::AdHoc, http::Header, Request};
+
+use crate::{core::web::data::web_data::REQUEST_ID_HEADER, lib::data::str::Str};
+
+const REQUEST_ID_BYTES: usize = 16;
+const REQUEST_ID_MAX_LEN: usize = 128;
+
+struct RequestId(Str);
+
+// the id ends up in the logs, so only a safe subset is accepted from the client
+fn valid(id: &str) -> bool {
+	!id.is_empty()
+		&& id.len() <= REQUEST_ID_MAX_LEN
+		&& id
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
+
+#[cfg(not(test))]
+fn generate() -> Str {
+	use argon2::password_hash::rand_core::{OsRng, RngCore};
+
+	let mut bytes = [0u8; REQUEST_ID_BYTES];
+	OsRng.fill_bytes(&mut bytes);
+	hex::encode(bytes).into()
+}
+
+#[cfg(test)]
+fn generate() -> Str {
+	// deterministic in tests, so that the responses can be matched
+	hex::encode([7u8; REQUEST_ID_BYTES]).into()
+}
+
+/// The id of the request, sent by the client in the header or generated; the
+/// same id is returned for the whole request.
+pub fn request_id(req: &Request<'_>) -> Str {
+	let RequestId(id) = req.local_cache(|| {
+		let id = req
+			.headers()
+			.get_one(REQUEST_ID_HEADER)
+			.map(str::trim)
+			.filter(|id| valid(id))
+			.map(|id| id.to_string().into())
+			.unwrap_or_else(generate);
+		RequestId(id)
+	});
+	id.clone()
+}
+
+pub fn stage() -> AdHoc {
+	AdHoc::on_response("Request Id", |req, res| {
+		Box::pin(async move {
+			res.set_header(Header::new(REQUEST_ID_HEADER, request_id(req).to_string()));
+		})
+	})
+}
+
+#[cfg(test)]
+pub mod tests {
+	use crate::tests::test_utils::tests::run_test;
+
+	pub fn request_id() -> String {
+		hex::encode([7u8; super::REQUEST_ID_BYTES])
+	}
+
+	#[tokio::test]
+	async fn test_valid() {
+		run_test(|_| async {
+			assert!(super::valid("a1b2c3"));
+			assert!(super::valid("trace:01.span-02_03"));
+			assert!(!super::valid(""));
+			assert!(!super::valid("line\nbreak"));
+			assert!(!super::valid("with space"));
+			assert!(!super::valid(&"a".repeat(super::REQUEST_ID_MAX_LEN + 1)));
+		})
+		.await;
+	}
+}
@@ -1,17 +1,48 @@
-use crate::core::action::data::action_data::AuthBasicContext;
+use crate::core::{
+	action::data::action_data::{Application, AuthBasicContext, Request},
+	web::{data::web_data::IDEMPOTENCY_KEY_HEADER, main_impl::request_id_web_impl},
+};
 use rocket::request::{self, FromRequest};
 
+pub fn application(req: &request::Request<'_>) -> Application {
+	req.rocket()
+		.state::<Application>()
+		.cloned()
+		.unwrap_or_default()
+}
+
+pub fn request(req: &request::Request<'_>) -> Request {
+	Request {
+		ip: req
+			.client_ip()
+			.map(|ip| ip.to_string().into())
+			.unwrap_or_else(|| "".into()),
+		request_id: request_id_web_impl::request_id(req),
+		idempotency_key: req
+			.headers()
+			.get_one(IDEMPOTENCY_KEY_HEADER)
+			.map(str::trim)
+			.filter(|key| !key.is_empty())
+			.map(|key| key.to_string().into()),
+	}
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for AuthBasicContext {
 	type Error = ();
 
 	async fn from_request(req: &'r request::Request<'_>) -> request::Outcome<Self, Self::Error> {
+		let application = application(req);
+		let request = request(req);
+
 		request::Outcome::Success(AuthBasicContext {
 			token: req
 				.headers()
 				.get("auth")
 				.next()
 				.map(|value| value.to_string()),
+			application,
+			request,
 		})
 	}
 }
@@ -2,17 +2,67 @@ use crate::{
 	core::{
 		action::{
 			action_type::general_action_type::ActionType,
-			data::action_data::{ActionErrorInfo, ActionResultInfo, ErrorData, RequestInput},
+			data::action_data::{ActionErrorInfo, ActionResultInfo, ErrorCategory, RequestInput},
 			definition::{
 				action::{Action, ActionError, ActionInput, ActionOutput},
 				action_helpers::{ActionErrorHelper, DescriptiveRequestContext},
 			},
 		},
-		web::definition::web_action::WebAction,
+		web::{
+			data::web_data::{LegacyError, ProblemDetails, WebError},
+			definition::web_action::{WebAction, WebActionResult},
+			main_impl::request_id_web_impl,
+		},
 	},
-	lib::traits::async_from::AsyncInto,
+	lib::{data::str::Str, traits::async_from::AsyncInto},
+};
+use rocket::{
+	http::{ContentType, Status},
+	response::{self, status::Custom, Responder},
+	serde::json::Json,
+	Request, Response,
 };
-use rocket::serde::json::Json;
+
+pub fn status(category: ErrorCategory) -> Status {
+	match category {
+		ErrorCategory::Validation => Status::BadRequest,
+		ErrorCategory::Unauthenticated => Status::Unauthorized,
+		ErrorCategory::Forbidden => Status::Forbidden,
+		ErrorCategory::NotFound => Status::NotFound,
+		ErrorCategory::Conflict => Status::Conflict,
+		ErrorCategory::RateLimited => Status::TooManyRequests,
+		ErrorCategory::Internal => Status::InternalServerError,
+		ErrorCategory::Upstream => Status::BadGateway,
+		ErrorCategory::Timeout => Status::GatewayTimeout,
+	}
+}
+
+impl<'r> Responder<'r, 'static> for WebError {
+	fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+		let legacy = req
+			.accept()
+			.map(|accept| accept.preferred().media_type().is_json())
+			.unwrap_or(false);
+
+		if legacy {
+			let error = LegacyError {
+				error: self.error,
+				request_id: request_id_web_impl::request_id(req),
+			};
+			Custom(self.status, Json(error)).respond_to(req)
+		} else {
+			let status = self.status;
+			let problem = ProblemDetails {
+				request_id: Some(request_id_web_impl::request_id(req)),
+				..ProblemDetails::from(self)
+			};
+			Response::build_from(Json(problem).respond_to(req)?)
+				.status(status)
+				.header(ContentType::new("application", "problem+json"))
+				.ok()
+		}
+	}
+}
 
 #[rocket::async_trait]
 impl<I, O, E, R, C, A, T, N> WebAction<I, O, E, R, C, A, N> for T
@@ -27,13 +77,45 @@ where
 	T: Action<Result<RequestInput<I, C>, R>, ActionResultInfo<A, C, O>, ActionErrorInfo<A, C, E>>
 		+ 'static,
 {
-	async fn request(input: N) -> Result<Json<O>, Json<Option<ErrorData>>> {
+	async fn request(input: N) -> WebActionResult<O> {
 		Self::run(input.into().await)
 			.await
 			.map(|out| Json(out.data))
-			.map_err(|err| {
-				let err = err.handle();
-				Json(err)
+			.map_err(|err| WebError {
+				status: status(err.error.category()),
+				scope: Str::from(A::scope().to_string()),
+				action_id: err.action_context.action_type.id(),
+				error: err.handle(),
 			})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use rocket::http::Status;
+
+	use crate::core::action::data::action_data::ErrorCategory;
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_status() {
+		run_test(|_| async {
+			let cases = [
+				(ErrorCategory::Validation, Status::BadRequest),
+				(ErrorCategory::Unauthenticated, Status::Unauthorized),
+				(ErrorCategory::Forbidden, Status::Forbidden),
+				(ErrorCategory::NotFound, Status::NotFound),
+				(ErrorCategory::Conflict, Status::Conflict),
+				(ErrorCategory::RateLimited, Status::TooManyRequests),
+				(ErrorCategory::Internal, Status::InternalServerError),
+				(ErrorCategory::Upstream, Status::BadGateway),
+				(ErrorCategory::Timeout, Status::GatewayTimeout),
+			];
+
+			for (category, status) in cases {
+				assert_eq!(super::status(category), status);
+			}
+		})
+		.await;
+	}
+}
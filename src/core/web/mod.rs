@@ -1,2 +1,3 @@
+pub mod data;
 pub mod definition;
 pub mod main_impl;
@@ -0,0 +1,240 @@
+use diesel::{Connection, ExpressionMethods, QueryDsl, Queryable, RunQueryDsl};
+use rocket_sync_db_pools::diesel;
+
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	action_audit (id) {
+		id -> BigInt,
+		scope -> Text,
+		action_id -> BigInt,
+		actor_type -> Text,
+		actor_id -> Nullable<BigInt>,
+		ip -> Nullable<Text>,
+		created_at -> BigInt,
+		duration_ms -> BigInt,
+		success -> Bool,
+		error_code -> Nullable<Text>,
+		input -> Text,
+	}
+}
+
+no_arg_sql_function!(
+	last_insert_rowid,
+	diesel::sql_types::BigInt,
+	"Represents the SQL last_insert_rowid() function"
+);
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertInput {
+	pub scope: String,
+	pub action_id: i64,
+	pub actor_type: String,
+	pub actor_id: Option<i64>,
+	pub ip: Option<String>,
+	pub created_at: i64,
+	pub duration_ms: i64,
+	pub success: bool,
+	pub error_code: Option<String>,
+	pub input: String,
+}
+
+/// The filters that are `None` are not applied; the records are returned from
+/// the most recent to the oldest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectInput {
+	pub actor_type: Option<String>,
+	pub actor_id: Option<i64>,
+	pub scope: Option<String>,
+	pub action_id: Option<i64>,
+	pub from: Option<i64>,
+	pub to: Option<i64>,
+	pub limit: i64,
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertOutput {
+	pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Queryable, Serialize, Deserialize)]
+pub struct ActionAudit {
+	pub id: i64,
+	pub scope: String,
+	pub action_id: i64,
+	pub actor_type: String,
+	pub actor_id: Option<i64>,
+	pub ip: Option<String>,
+	pub created_at: i64,
+	pub duration_ms: i64,
+	pub success: bool,
+	pub error_code: Option<String>,
+	pub input: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectOutput(pub Vec<ActionAudit>);
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Insert;
+
+pub struct Select;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+#[cfg_attr(test, allow(dead_code))]
+async fn insert(input: InsertInput) -> Result<InsertOutput, ExternalException> {
+	let InsertInput {
+		scope,
+		action_id,
+		actor_type,
+		actor_id,
+		ip,
+		created_at,
+		duration_ms,
+		success,
+		error_code,
+		input,
+	} = input;
+	let id = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			diesel::insert_into(action_audit::table)
+				.values((
+					action_audit::scope.eq(scope),
+					action_audit::action_id.eq(action_id),
+					action_audit::actor_type.eq(actor_type),
+					action_audit::actor_id.eq(actor_id),
+					action_audit::ip.eq(ip),
+					action_audit::created_at.eq(created_at),
+					action_audit::duration_ms.eq(duration_ms),
+					action_audit::success.eq(success),
+					action_audit::error_code.eq(error_code),
+					action_audit::input.eq(input),
+				))
+				.execute(conn)?;
+			diesel::select(last_insert_rowid).get_result::<i64>(conn)
+		})
+	})
+	.await?;
+	Ok(InsertOutput { id })
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn select(input: SelectInput) -> Result<SelectOutput, ExternalException> {
+	let SelectInput {
+		actor_type,
+		actor_id,
+		scope,
+		action_id,
+		from,
+		to,
+		limit,
+	} = input;
+	let items = db_pool::run(move |conn| {
+		let mut query = action_audit::table.into_boxed();
+
+		if let Some(actor_type) = actor_type {
+			query = query.filter(action_audit::actor_type.eq(actor_type));
+		}
+
+		if let Some(actor_id) = actor_id {
+			query = query.filter(action_audit::actor_id.eq(actor_id));
+		}
+
+		if let Some(scope) = scope {
+			query = query.filter(action_audit::scope.eq(scope));
+		}
+
+		if let Some(action_id) = action_id {
+			query = query.filter(action_audit::action_id.eq(action_id));
+		}
+
+		if let Some(from) = from {
+			query = query.filter(action_audit::created_at.ge(from));
+		}
+
+		if let Some(to) = to {
+			query = query.filter(action_audit::created_at.lt(to));
+		}
+
+		query
+			.order((action_audit::created_at.desc(), action_audit::id.desc()))
+			.limit(limit)
+			.load::<ActionAudit>(conn)
+	})
+	.await?;
+	Ok(SelectOutput(items))
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::InsertInput, super::InsertOutput> for super::Insert {
+		async fn run(input: super::InsertInput) -> Result<super::InsertOutput, ExternalException> {
+			metrics_impl::external("action_audit_dao::Insert", super::insert(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::SelectInput, super::SelectOutput> for super::Select {
+		async fn run(input: super::SelectInput) -> Result<super::SelectOutput, ExternalException> {
+			metrics_impl::external("action_audit_dao::Select", super::select(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::core::external::data::external_exception::ExternalException;
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::InsertInput, super::InsertOutput> for super::Insert {
+		fn name() -> Str {
+			"insert-action-audit".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Insert
+		}
+	}
+
+	impl ExternalTest<super::SelectInput, super::SelectOutput> for super::Select {
+		fn name() -> Str {
+			"select-action-audit".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Select
+		}
+	}
+}
@@ -0,0 +1,235 @@
+use chrono::Utc;
+use diesel::{
+	BoolExpressionMethods, Connection, ExpressionMethods, QueryDsl, Queryable, RunQueryDsl,
+};
+use rocket_sync_db_pools::diesel;
+
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	event_outbox (id) {
+		id -> BigInt,
+		action_id -> BigInt,
+		event -> Text,
+		created_at -> BigInt,
+		delivered_at -> Nullable<BigInt>,
+		attempts -> BigInt,
+		last_error -> Nullable<Text>,
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The event (as json) for the automatic action that subscribed to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewEvent {
+	pub action_id: i64,
+	pub event: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertInput(pub Vec<NewEvent>);
+
+/// The events not delivered yet, that still have attempts left, from the
+/// oldest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectPendingInput {
+	pub max_attempts: i64,
+	pub limit: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UpdateInput {
+	Delivered { id: i64 },
+	Failed { id: i64, error: String },
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Queryable, Serialize, Deserialize)]
+pub struct OutboxEvent {
+	pub id: i64,
+	pub action_id: i64,
+	pub event: String,
+	pub created_at: i64,
+	pub attempts: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectPendingOutput(pub Vec<OutboxEvent>);
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Insert;
+
+pub struct SelectPending;
+
+pub struct Update;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+#[cfg_attr(test, allow(dead_code))]
+async fn insert(input: InsertInput) -> Result<(), ExternalException> {
+	let InsertInput(events) = input;
+	let now = Utc::now().timestamp();
+	db_pool::run(move |conn| {
+		conn.transaction(|| {
+			for NewEvent { action_id, event } in events {
+				diesel::insert_into(event_outbox::table)
+					.values((
+						event_outbox::action_id.eq(action_id),
+						event_outbox::event.eq(event),
+						event_outbox::created_at.eq(now),
+						event_outbox::attempts.eq(0),
+					))
+					.execute(conn)?;
+			}
+			Ok::<_, diesel::result::Error>(())
+		})
+	})
+	.await
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn select_pending(
+	input: SelectPendingInput,
+) -> Result<SelectPendingOutput, ExternalException> {
+	let SelectPendingInput {
+		max_attempts,
+		limit,
+	} = input;
+	let events = db_pool::run(move |conn| {
+		event_outbox::table
+			.filter(
+				event_outbox::delivered_at
+					.is_null()
+					.and(event_outbox::attempts.lt(max_attempts)),
+			)
+			.order(event_outbox::id.asc())
+			.limit(limit)
+			.select((
+				event_outbox::id,
+				event_outbox::action_id,
+				event_outbox::event,
+				event_outbox::created_at,
+				event_outbox::attempts,
+			))
+			.load::<OutboxEvent>(conn)
+	})
+	.await?;
+	Ok(SelectPendingOutput(events))
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn update(input: UpdateInput) -> Result<(), ExternalException> {
+	let now = Utc::now().timestamp();
+	db_pool::run(move |conn| match input {
+		UpdateInput::Delivered { id } => diesel::update(event_outbox::table.find(id))
+			.set((
+				event_outbox::delivered_at.eq(now),
+				event_outbox::attempts.eq(event_outbox::attempts + 1),
+			))
+			.execute(conn),
+		UpdateInput::Failed { id, error } => diesel::update(event_outbox::table.find(id))
+			.set((
+				event_outbox::last_error.eq(error),
+				event_outbox::attempts.eq(event_outbox::attempts + 1),
+			))
+			.execute(conn),
+	})
+	.await?;
+	Ok(())
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::InsertInput, ()> for super::Insert {
+		async fn run(input: super::InsertInput) -> Result<(), ExternalException> {
+			metrics_impl::external("event_outbox_dao::Insert", super::insert(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::SelectPendingInput, super::SelectPendingOutput>
+		for super::SelectPending
+	{
+		async fn run(
+			input: super::SelectPendingInput,
+		) -> Result<super::SelectPendingOutput, ExternalException> {
+			metrics_impl::external(
+				"event_outbox_dao::SelectPending",
+				super::select_pending(input),
+			)
+			.await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::UpdateInput, ()> for super::Update {
+		async fn run(input: super::UpdateInput) -> Result<(), ExternalException> {
+			metrics_impl::external("event_outbox_dao::Update", super::update(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::core::external::data::external_exception::ExternalException;
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::InsertInput, ()> for super::Insert {
+		fn name() -> Str {
+			"insert-event-outbox".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Insert
+		}
+	}
+
+	impl ExternalTest<super::SelectPendingInput, super::SelectPendingOutput> for super::SelectPending {
+		fn name() -> Str {
+			"select-pending-event-outbox".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Select
+		}
+	}
+
+	impl ExternalTest<super::UpdateInput, ()> for super::Update {
+		fn name() -> Str {
+			"update-event-outbox".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+}
@@ -0,0 +1,232 @@
+use chrono::Utc;
+use diesel::{
+	BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl,
+};
+use rocket_sync_db_pools::diesel;
+
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	idempotency_key (actor, action, key) {
+		actor -> Text,
+		action -> Text,
+		key -> Text,
+		fingerprint -> Text,
+		response -> Nullable<Text>,
+		created_at -> BigInt,
+		expires_at -> BigInt,
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdempotencyId {
+	pub actor: String,
+	pub action: String,
+	pub key: String,
+}
+
+/// Starts the request of the key, unless there is already one that didn't
+/// expire.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeginInput {
+	pub id: IdempotencyId,
+	pub fingerprint: String,
+	pub ttl_seconds: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompleteInput {
+	pub id: IdempotencyId,
+	pub response: String,
+}
+
+/// Forgets the request that didn't complete, so that it can be retried.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseInput {
+	pub id: IdempotencyId,
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BeginOutput {
+	Started,
+	InProgress,
+	Conflict,
+	Replay(String),
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Begin;
+
+pub struct Complete;
+
+pub struct Release;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+#[cfg_attr(test, allow(dead_code))]
+async fn begin(input: BeginInput) -> Result<BeginOutput, ExternalException> {
+	let BeginInput {
+		id: IdempotencyId { actor, action, key },
+		fingerprint,
+		ttl_seconds,
+	} = input;
+	let now = Utc::now().timestamp();
+	let output = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			let row = idempotency_key::table
+				.find((&actor, &action, &key))
+				.filter(idempotency_key::expires_at.gt(now))
+				.select((idempotency_key::fingerprint, idempotency_key::response))
+				.first::<(String, Option<String>)>(conn)
+				.optional()?;
+
+			match row {
+				Some((stored, _)) if stored != fingerprint => Ok(BeginOutput::Conflict),
+				Some((_, Some(response))) => Ok(BeginOutput::Replay(response)),
+				Some((_, None)) => Ok(BeginOutput::InProgress),
+				None => {
+					diesel::replace_into(idempotency_key::table)
+						.values((
+							idempotency_key::actor.eq(&actor),
+							idempotency_key::action.eq(&action),
+							idempotency_key::key.eq(&key),
+							idempotency_key::fingerprint.eq(&fingerprint),
+							idempotency_key::response.eq(None::<String>),
+							idempotency_key::created_at.eq(now),
+							idempotency_key::expires_at.eq(now + ttl_seconds),
+						))
+						.execute(conn)?;
+					Ok::<_, diesel::result::Error>(BeginOutput::Started)
+				}
+			}
+		})
+	})
+	.await?;
+	Ok(output)
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn complete(input: CompleteInput) -> Result<(), ExternalException> {
+	let CompleteInput {
+		id: IdempotencyId { actor, action, key },
+		response,
+	} = input;
+	db_pool::run(move |conn| {
+		diesel::update(idempotency_key::table.find((actor, action, key)))
+			.set(idempotency_key::response.eq(response))
+			.execute(conn)
+	})
+	.await?;
+	Ok(())
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn release(input: ReleaseInput) -> Result<(), ExternalException> {
+	let ReleaseInput {
+		id: IdempotencyId { actor, action, key },
+	} = input;
+	db_pool::run(move |conn| {
+		diesel::delete(
+			idempotency_key::table.filter(
+				idempotency_key::actor
+					.eq(actor)
+					.and(idempotency_key::action.eq(action))
+					.and(idempotency_key::key.eq(key))
+					.and(idempotency_key::response.is_null()),
+			),
+		)
+		.execute(conn)
+	})
+	.await?;
+	Ok(())
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::BeginInput, super::BeginOutput> for super::Begin {
+		async fn run(input: super::BeginInput) -> Result<super::BeginOutput, ExternalException> {
+			metrics_impl::external("idempotency_dao::Begin", super::begin(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::CompleteInput, ()> for super::Complete {
+		async fn run(input: super::CompleteInput) -> Result<(), ExternalException> {
+			metrics_impl::external("idempotency_dao::Complete", super::complete(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::ReleaseInput, ()> for super::Release {
+		async fn run(input: super::ReleaseInput) -> Result<(), ExternalException> {
+			metrics_impl::external("idempotency_dao::Release", super::release(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::core::external::data::external_exception::ExternalException;
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::BeginInput, super::BeginOutput> for super::Begin {
+		fn name() -> Str {
+			"begin-idempotency".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Insert
+		}
+	}
+
+	impl ExternalTest<super::CompleteInput, ()> for super::Complete {
+		fn name() -> Str {
+			"complete-idempotency".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<super::ReleaseInput, ()> for super::Release {
+		fn name() -> Str {
+			"release-idempotency".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Delete
+		}
+	}
+}
@@ -0,0 +1,429 @@
+use chrono::Utc;
+use diesel::{
+	BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, Queryable, RunQueryDsl,
+};
+use rocket_sync_db_pools::diesel;
+
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	job (id) {
+		id -> BigInt,
+		action_id -> BigInt,
+		input -> Text,
+		state -> Text,
+		run_at -> BigInt,
+		attempts -> BigInt,
+		max_attempts -> BigInt,
+		last_error -> Nullable<Text>,
+		locked_by -> Nullable<Text>,
+		locked_until -> Nullable<BigInt>,
+		created_at -> BigInt,
+		updated_at -> BigInt,
+	}
+}
+
+no_arg_sql_function!(
+	last_insert_rowid,
+	diesel::sql_types::BigInt,
+	"Represents the SQL last_insert_rowid() function"
+);
+
+pub const STATE_PENDING: &str = "pending";
+
+pub const STATE_RUNNING: &str = "running";
+
+pub const STATE_DONE: &str = "done";
+
+pub const STATE_DEAD: &str = "dead";
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// The input (as json) of the automatic action, to run in `delay` seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertInput {
+	pub action_id: i64,
+	pub input: String,
+	pub delay: i64,
+	pub max_attempts: i64,
+}
+
+/// Leases the next job that is due, or whose lease expired, for `lease`
+/// seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimInput {
+	pub worker: String,
+	pub lease: i64,
+}
+
+/// Only the worker that holds the lease can complete or fail the job.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompleteInput {
+	pub id: i64,
+	pub worker: String,
+}
+
+/// The job runs again in `retry_in` seconds, or it's dead when there's no
+/// retry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailInput {
+	pub id: i64,
+	pub worker: String,
+	pub error: String,
+	pub retry_in: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectInput {
+	pub state: Option<String>,
+	pub action_id: Option<i64>,
+	pub limit: i64,
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Queryable, Serialize, Deserialize)]
+pub struct Job {
+	pub id: i64,
+	pub action_id: i64,
+	pub input: String,
+	pub state: String,
+	pub run_at: i64,
+	pub attempts: i64,
+	pub max_attempts: i64,
+	pub last_error: Option<String>,
+	pub created_at: i64,
+	pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertOutput {
+	pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimOutput(pub Option<Job>);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectOutput(pub Vec<Job>);
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Insert;
+
+pub struct Claim;
+
+pub struct Complete;
+
+pub struct Fail;
+
+pub struct Select;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+// the columns of `Job`, without the lease
+macro_rules! job_columns {
+	() => {
+		(
+			job::id,
+			job::action_id,
+			job::input,
+			job::state,
+			job::run_at,
+			job::attempts,
+			job::max_attempts,
+			job::last_error,
+			job::created_at,
+			job::updated_at,
+		)
+	};
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn insert(input: InsertInput) -> Result<InsertOutput, ExternalException> {
+	let InsertInput {
+		action_id,
+		input,
+		delay,
+		max_attempts,
+	} = input;
+	let now = Utc::now().timestamp();
+	let id = db_pool::run(move |conn| {
+		conn.immediate_transaction(|| {
+			diesel::insert_into(job::table)
+				.values((
+					job::action_id.eq(action_id),
+					job::input.eq(input),
+					job::state.eq(STATE_PENDING),
+					job::run_at.eq(now + delay),
+					job::attempts.eq(0),
+					job::max_attempts.eq(max_attempts),
+					job::created_at.eq(now),
+					job::updated_at.eq(now),
+				))
+				.execute(conn)?;
+			diesel::select(last_insert_rowid).get_result::<i64>(conn)
+		})
+	})
+	.await?;
+	Ok(InsertOutput { id })
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn claim(input: ClaimInput) -> Result<ClaimOutput, ExternalException> {
+	let ClaimInput { worker, lease } = input;
+	let now = Utc::now().timestamp();
+	let job = db_pool::run(move |conn| {
+		// the immediate transaction takes the write lock before selecting, so
+		// two workers can't claim the same job
+		conn.immediate_transaction(|| {
+			let expired = job::state.eq(STATE_RUNNING).and(job::locked_until.le(now));
+
+			diesel::update(job::table.filter(expired.and(job::attempts.ge(job::max_attempts))))
+				.set((
+					job::state.eq(STATE_DEAD),
+					job::last_error.eq("the lease expired"),
+					job::locked_by.eq(None::<String>),
+					job::locked_until.eq(None::<i64>),
+					job::updated_at.eq(now),
+				))
+				.execute(conn)?;
+
+			let item = job::table
+				.filter(
+					job::state
+						.eq(STATE_PENDING)
+						.and(job::run_at.le(now))
+						.or(expired),
+				)
+				.order((job::run_at.asc(), job::id.asc()))
+				.select(job_columns!())
+				.first::<Job>(conn)
+				.optional()?;
+
+			let item = match item {
+				Some(item) => item,
+				None => return Ok(None),
+			};
+
+			diesel::update(job::table.find(item.id))
+				.set((
+					job::state.eq(STATE_RUNNING),
+					job::attempts.eq(job::attempts + 1),
+					job::locked_by.eq(worker),
+					job::locked_until.eq(now + lease),
+					job::updated_at.eq(now),
+				))
+				.execute(conn)?;
+
+			Ok::<_, diesel::result::Error>(Some(Job {
+				state: STATE_RUNNING.into(),
+				attempts: item.attempts + 1,
+				updated_at: now,
+				..item
+			}))
+		})
+	})
+	.await?;
+	Ok(ClaimOutput(job))
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn complete(input: CompleteInput) -> Result<(), ExternalException> {
+	let CompleteInput { id, worker } = input;
+	let now = Utc::now().timestamp();
+	db_pool::run(move |conn| {
+		diesel::update(job::table.find(id).filter(job::locked_by.eq(worker)))
+			.set((
+				job::state.eq(STATE_DONE),
+				job::last_error.eq(None::<String>),
+				job::locked_by.eq(None::<String>),
+				job::locked_until.eq(None::<i64>),
+				job::updated_at.eq(now),
+			))
+			.execute(conn)
+	})
+	.await?;
+	Ok(())
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn fail(input: FailInput) -> Result<(), ExternalException> {
+	let FailInput {
+		id,
+		worker,
+		error,
+		retry_in,
+	} = input;
+	let now = Utc::now().timestamp();
+	db_pool::run(move |conn| {
+		let target = job::table.find(id).filter(job::locked_by.eq(worker));
+		let unlock = (
+			job::last_error.eq(error),
+			job::locked_by.eq(None::<String>),
+			job::locked_until.eq(None::<i64>),
+			job::updated_at.eq(now),
+		);
+
+		match retry_in {
+			Some(retry_in) => diesel::update(target)
+				.set((
+					job::state.eq(STATE_PENDING),
+					job::run_at.eq(now + retry_in),
+					unlock,
+				))
+				.execute(conn),
+			None => diesel::update(target)
+				.set((job::state.eq(STATE_DEAD), unlock))
+				.execute(conn),
+		}
+	})
+	.await?;
+	Ok(())
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn select(input: SelectInput) -> Result<SelectOutput, ExternalException> {
+	let SelectInput {
+		state,
+		action_id,
+		limit,
+	} = input;
+	let items = db_pool::run(move |conn| {
+		let mut query = job::table.select(job_columns!()).into_boxed();
+
+		if let Some(state) = state {
+			query = query.filter(job::state.eq(state));
+		}
+
+		if let Some(action_id) = action_id {
+			query = query.filter(job::action_id.eq(action_id));
+		}
+
+		query
+			.order((job::updated_at.desc(), job::id.desc()))
+			.limit(limit)
+			.load::<Job>(conn)
+	})
+	.await?;
+	Ok(SelectOutput(items))
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::InsertInput, super::InsertOutput> for super::Insert {
+		async fn run(input: super::InsertInput) -> Result<super::InsertOutput, ExternalException> {
+			metrics_impl::external("job_dao::Insert", super::insert(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::ClaimInput, super::ClaimOutput> for super::Claim {
+		async fn run(input: super::ClaimInput) -> Result<super::ClaimOutput, ExternalException> {
+			metrics_impl::external("job_dao::Claim", super::claim(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::CompleteInput, ()> for super::Complete {
+		async fn run(input: super::CompleteInput) -> Result<(), ExternalException> {
+			metrics_impl::external("job_dao::Complete", super::complete(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::FailInput, ()> for super::Fail {
+		async fn run(input: super::FailInput) -> Result<(), ExternalException> {
+			metrics_impl::external("job_dao::Fail", super::fail(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::SelectInput, super::SelectOutput> for super::Select {
+		async fn run(input: super::SelectInput) -> Result<super::SelectOutput, ExternalException> {
+			metrics_impl::external("job_dao::Select", super::select(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::core::external::data::external_exception::ExternalException;
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::InsertInput, super::InsertOutput> for super::Insert {
+		fn name() -> Str {
+			"insert-job".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Insert
+		}
+	}
+
+	impl ExternalTest<super::ClaimInput, super::ClaimOutput> for super::Claim {
+		fn name() -> Str {
+			"claim-job".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<super::CompleteInput, ()> for super::Complete {
+		fn name() -> Str {
+			"complete-job".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<super::FailInput, ()> for super::Fail {
+		fn name() -> Str {
+			"fail-job".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<super::SelectInput, super::SelectOutput> for super::Select {
+		fn name() -> Str {
+			"select-job".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Select
+		}
+	}
+}
@@ -1,2 +1,11 @@
+pub mod action_audit_dao;
+pub mod event_outbox_dao;
+pub mod idempotency_dao;
+pub mod job_dao;
+pub mod moderator_dao;
+pub mod rate_limit_dao;
+pub mod schedule_dao;
+pub mod user_confirmation_dao;
 pub mod user_dao;
+pub mod user_password_reset_dao;
 pub mod user_session_dao;
@@ -0,0 +1,133 @@
+use diesel::{OptionalExtension, QueryDsl, Queryable, RunQueryDsl};
+use rocket_sync_db_pools::diesel;
+
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	moderator (user_id) {
+		user_id -> BigInt,
+		admin -> Bool,
+		allowed_actions -> Text,
+	}
+}
+
+#[derive(Queryable)]
+struct ModeratorRow {
+	user_id: i64,
+	admin: bool,
+	allowed_actions: String,
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum SelectInput {
+	ByUser(UserId),
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Moderator {
+	pub user_id: UserId,
+	pub admin: bool,
+	pub allowed_actions: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectOutput(pub Option<Moderator>);
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Select;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+// unknown ids are ignored, so removed action types don't block the moderator
+#[cfg_attr(test, allow(dead_code))]
+fn parse_actions(allowed_actions: &str) -> Vec<u32> {
+	allowed_actions
+		.split(',')
+		.filter_map(|id| id.trim().parse().ok())
+		.collect()
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn select(input: SelectInput) -> Result<SelectOutput, ExternalException> {
+	let row = db_pool::run(move |conn| match input {
+		SelectInput::ByUser(UserId(user_id)) => moderator::table
+			.find(user_id)
+			.first::<ModeratorRow>(conn)
+			.optional(),
+	})
+	.await?;
+	let moderator = row.map(|row| Moderator {
+		user_id: UserId(row.user_id),
+		admin: row.admin,
+		allowed_actions: parse_actions(&row.allowed_actions),
+	});
+	Ok(SelectOutput(moderator))
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::SelectInput, super::SelectOutput> for super::Select {
+		async fn run(input: super::SelectInput) -> Result<super::SelectOutput, ExternalException> {
+			metrics_impl::external("moderator_dao::Select", super::select(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::{
+	core::external::data::external_exception::ExternalException, shared::data::user_data::UserId,
+};
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+		tests::test_utils::tests::run_test,
+	};
+
+	impl ExternalTest<super::SelectInput, super::SelectOutput> for super::Select {
+		fn name() -> Str {
+			"select-moderator".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Select
+		}
+	}
+
+	#[tokio::test]
+	async fn test_parse_actions() {
+		run_test(|_| async {
+			assert_eq!(super::parse_actions(""), Vec::<u32>::new());
+			assert_eq!(super::parse_actions("1, 5,x,,6"), vec![1, 5, 6]);
+		})
+		.await;
+	}
+}
@@ -0,0 +1,122 @@
+use diesel::{Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use rocket_sync_db_pools::diesel;
+
+use crate::core::{
+	action::{data::rate_limit_data::RateLimitBucket, main_impl::rate_limit_impl},
+	dao::db_pool,
+};
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	rate_limit (key) {
+		key -> Text,
+		tokens -> Double,
+		updated_at -> BigInt,
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// Takes a call from the bucket of the key (`now` in unix milliseconds).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TakeInput {
+	pub key: String,
+	pub capacity: u32,
+	pub window: u32,
+	pub now: i64,
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+/// The seconds until the next call is allowed, when the bucket is empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TakeOutput {
+	pub retry_after: Option<u32>,
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Take;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+#[cfg_attr(test, allow(dead_code))]
+async fn take(input: TakeInput) -> Result<TakeOutput, ExternalException> {
+	let TakeInput {
+		key,
+		capacity,
+		window,
+		now,
+	} = input;
+	let retry_after = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			let bucket = rate_limit::table
+				.find(&key)
+				.select((rate_limit::tokens, rate_limit::updated_at))
+				.first::<(f64, i64)>(conn)
+				.optional()?
+				.map(|(tokens, updated_at)| RateLimitBucket { tokens, updated_at });
+			let (bucket, result) = rate_limit_impl::take(bucket, capacity, window, now);
+			diesel::replace_into(rate_limit::table)
+				.values((
+					rate_limit::key.eq(&key),
+					rate_limit::tokens.eq(bucket.tokens),
+					rate_limit::updated_at.eq(bucket.updated_at),
+				))
+				.execute(conn)?;
+			Ok::<_, diesel::result::Error>(result.err().map(|error| error.retry_after))
+		})
+	})
+	.await?;
+	Ok(TakeOutput { retry_after })
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::TakeInput, super::TakeOutput> for super::Take {
+		async fn run(input: super::TakeInput) -> Result<super::TakeOutput, ExternalException> {
+			metrics_impl::external("rate_limit_dao::Take", super::take(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::core::external::data::external_exception::ExternalException;
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::TakeInput, super::TakeOutput> for super::Take {
+		fn name() -> Str {
+			"take-rate-limit".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+}
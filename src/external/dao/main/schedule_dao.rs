@@ -0,0 +1,413 @@
+use diesel::{
+	BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, QueryDsl, Queryable,
+	RunQueryDsl,
+};
+use rocket_sync_db_pools::diesel;
+
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	schedule (name) {
+		name -> Text,
+		action_id -> BigInt,
+		cron -> Nullable<Text>,
+		interval_seconds -> Nullable<BigInt>,
+		paused -> Bool,
+		triggered -> Bool,
+		locked_by -> Nullable<Text>,
+		locked_until -> Nullable<BigInt>,
+		last_run_at -> Nullable<BigInt>,
+		last_error -> Nullable<Text>,
+		next_run_at -> BigInt,
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+/// A configured schedule; the `next_run_at` is only stored for the new
+/// schedules and the ones whose timing changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncItem {
+	pub name: String,
+	pub action_id: i64,
+	pub cron: Option<String>,
+	pub interval_seconds: Option<i64>,
+	pub next_run_at: i64,
+}
+
+/// The schedules that aren't configured anymore are removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncInput(pub Vec<SyncItem>);
+
+/// Leases the schedule for `lease` seconds to the run, when it's due (or
+/// triggered) and not running.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StartInput {
+	pub name: String,
+	pub run_id: String,
+	pub now: i64,
+	pub lease: i64,
+}
+
+/// Only the run that holds the lease can finish the schedule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinishInput {
+	pub name: String,
+	pub run_id: String,
+	pub next_run_at: i64,
+	pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateInput {
+	pub name: String,
+	pub paused: bool,
+}
+
+/// The schedule runs on the next tick, even when it's paused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerInput {
+	pub name: String,
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Queryable, Serialize, Deserialize)]
+pub struct Schedule {
+	pub name: String,
+	pub action_id: i64,
+	pub cron: Option<String>,
+	pub interval_seconds: Option<i64>,
+	pub paused: bool,
+	pub triggered: bool,
+	pub locked_until: Option<i64>,
+	pub last_run_at: Option<i64>,
+	pub last_error: Option<String>,
+	pub next_run_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectOutput(pub Vec<Schedule>);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StartOutput {
+	pub started: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateOutput {
+	pub updated: bool,
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Sync;
+
+pub struct Select;
+
+pub struct Start;
+
+pub struct Finish;
+
+pub struct Update;
+
+pub struct Trigger;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+#[cfg_attr(test, allow(dead_code))]
+async fn sync(input: SyncInput) -> Result<(), ExternalException> {
+	let SyncInput(items) = input;
+	db_pool::run(move |conn| {
+		conn.transaction(|| {
+			let names = items
+				.iter()
+				.map(|item| item.name.clone())
+				.collect::<Vec<_>>();
+			diesel::delete(schedule::table.filter(schedule::name.ne_all(names))).execute(conn)?;
+
+			for item in items {
+				let SyncItem {
+					name,
+					action_id,
+					cron,
+					interval_seconds,
+					next_run_at,
+				} = item;
+
+				let timing = schedule::table
+					.find(&name)
+					.select((schedule::cron, schedule::interval_seconds))
+					.first::<(Option<String>, Option<i64>)>(conn)
+					.optional()?;
+
+				match timing {
+					None => diesel::insert_into(schedule::table)
+						.values((
+							schedule::name.eq(name),
+							schedule::action_id.eq(action_id),
+							schedule::cron.eq(cron),
+							schedule::interval_seconds.eq(interval_seconds),
+							schedule::next_run_at.eq(next_run_at),
+						))
+						.execute(conn)?,
+					Some(timing) if timing == (cron.clone(), interval_seconds) => {
+						diesel::update(schedule::table.find(name))
+							.set(schedule::action_id.eq(action_id))
+							.execute(conn)?
+					}
+					Some(_) => diesel::update(schedule::table.find(name))
+						.set((
+							schedule::action_id.eq(action_id),
+							schedule::cron.eq(cron),
+							schedule::interval_seconds.eq(interval_seconds),
+							schedule::next_run_at.eq(next_run_at),
+						))
+						.execute(conn)?,
+				};
+			}
+
+			Ok::<_, diesel::result::Error>(())
+		})
+	})
+	.await
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn select() -> Result<SelectOutput, ExternalException> {
+	let items = db_pool::run(move |conn| {
+		// the columns of `Schedule`, without the run that holds the lease
+		schedule::table
+			.select((
+				schedule::name,
+				schedule::action_id,
+				schedule::cron,
+				schedule::interval_seconds,
+				schedule::paused,
+				schedule::triggered,
+				schedule::locked_until,
+				schedule::last_run_at,
+				schedule::last_error,
+				schedule::next_run_at,
+			))
+			.order(schedule::name.asc())
+			.load::<Schedule>(conn)
+	})
+	.await?;
+	Ok(SelectOutput(items))
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn start(input: StartInput) -> Result<StartOutput, ExternalException> {
+	let StartInput {
+		name,
+		run_id,
+		now,
+		lease,
+	} = input;
+	let count = db_pool::run(move |conn| {
+		let idle = schedule::locked_until
+			.is_null()
+			.or(schedule::locked_until.le(now));
+		let due = schedule::triggered.eq(true).or(schedule::paused
+			.eq(false)
+			.and(schedule::next_run_at.le(now)));
+
+		diesel::update(schedule::table.find(name).filter(idle.and(due)))
+			.set((
+				schedule::locked_by.eq(run_id),
+				schedule::locked_until.eq(now + lease),
+				schedule::last_run_at.eq(now),
+				schedule::triggered.eq(false),
+			))
+			.execute(conn)
+	})
+	.await?;
+	Ok(StartOutput { started: count > 0 })
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn finish(input: FinishInput) -> Result<(), ExternalException> {
+	let FinishInput {
+		name,
+		run_id,
+		next_run_at,
+		error,
+	} = input;
+	db_pool::run(move |conn| {
+		diesel::update(
+			schedule::table
+				.find(name)
+				.filter(schedule::locked_by.eq(run_id)),
+		)
+		.set((
+			schedule::locked_by.eq(None::<String>),
+			schedule::locked_until.eq(None::<i64>),
+			schedule::next_run_at.eq(next_run_at),
+			schedule::last_error.eq(error),
+		))
+		.execute(conn)
+	})
+	.await?;
+	Ok(())
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn update(input: UpdateInput) -> Result<UpdateOutput, ExternalException> {
+	let UpdateInput { name, paused } = input;
+	let count = db_pool::run(move |conn| {
+		diesel::update(schedule::table.find(name))
+			.set(schedule::paused.eq(paused))
+			.execute(conn)
+	})
+	.await?;
+	Ok(UpdateOutput { updated: count > 0 })
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn trigger(input: TriggerInput) -> Result<UpdateOutput, ExternalException> {
+	let TriggerInput { name } = input;
+	let count = db_pool::run(move |conn| {
+		diesel::update(schedule::table.find(name))
+			.set(schedule::triggered.eq(true))
+			.execute(conn)
+	})
+	.await?;
+	Ok(UpdateOutput { updated: count > 0 })
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::SyncInput, ()> for super::Sync {
+		async fn run(input: super::SyncInput) -> Result<(), ExternalException> {
+			metrics_impl::external("schedule_dao::Sync", super::sync(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<(), super::SelectOutput> for super::Select {
+		async fn run(_: ()) -> Result<super::SelectOutput, ExternalException> {
+			metrics_impl::external("schedule_dao::Select", super::select()).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::StartInput, super::StartOutput> for super::Start {
+		async fn run(input: super::StartInput) -> Result<super::StartOutput, ExternalException> {
+			metrics_impl::external("schedule_dao::Start", super::start(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::FinishInput, ()> for super::Finish {
+		async fn run(input: super::FinishInput) -> Result<(), ExternalException> {
+			metrics_impl::external("schedule_dao::Finish", super::finish(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::UpdateInput, super::UpdateOutput> for super::Update {
+		async fn run(input: super::UpdateInput) -> Result<super::UpdateOutput, ExternalException> {
+			metrics_impl::external("schedule_dao::Update", super::update(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::TriggerInput, super::UpdateOutput> for super::Trigger {
+		async fn run(input: super::TriggerInput) -> Result<super::UpdateOutput, ExternalException> {
+			metrics_impl::external("schedule_dao::Trigger", super::trigger(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::core::external::data::external_exception::ExternalException;
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::SyncInput, ()> for super::Sync {
+		fn name() -> Str {
+			"sync-schedule".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<(), super::SelectOutput> for super::Select {
+		fn name() -> Str {
+			"select-schedule".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Select
+		}
+	}
+
+	impl ExternalTest<super::StartInput, super::StartOutput> for super::Start {
+		fn name() -> Str {
+			"start-schedule".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<super::FinishInput, ()> for super::Finish {
+		fn name() -> Str {
+			"finish-schedule".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<super::UpdateInput, super::UpdateOutput> for super::Update {
+		fn name() -> Str {
+			"update-schedule".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<super::TriggerInput, super::UpdateOutput> for super::Trigger {
+		fn name() -> Str {
+			"trigger-schedule".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+}
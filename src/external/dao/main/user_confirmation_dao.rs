@@ -0,0 +1,262 @@
+use chrono::Utc;
+use diesel::{Connection, ExpressionMethods, OptionalExtension, QueryDsl, Queryable, RunQueryDsl};
+use rocket_sync_db_pools::diesel;
+
+use super::{
+	user_dao::user,
+	user_session_dao::{token_hash, user_session},
+};
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	user_confirmation (id) {
+		id -> BigInt,
+		user_id -> BigInt,
+		token_hash -> Text,
+		created_at -> BigInt,
+		expires_at -> BigInt,
+		used_at -> Nullable<BigInt>,
+	}
+}
+
+no_arg_sql_function!(
+	last_insert_rowid,
+	diesel::sql_types::BigInt,
+	"Represents the SQL last_insert_rowid() function"
+);
+
+#[derive(Queryable)]
+struct UserConfirmationRow {
+	id: i64,
+	user_id: i64,
+	created_at: i64,
+	expires_at: i64,
+	used_at: Option<i64>,
+}
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InsertInput {
+	pub user_id: UserId,
+	pub token: String,
+	pub ttl_seconds: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum SelectInput {
+	LastByUser(UserId),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct UseInput {
+	pub token: String,
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertOutput {
+	pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserConfirmation {
+	pub id: i64,
+	pub user_id: UserId,
+	pub created_at: i64,
+	pub expires_at: i64,
+	pub used_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectOutput(pub Option<UserConfirmation>);
+
+/// The user of the confirmation, if the token was valid (not expired and not
+/// used before); the token can't be used again after that, and the user (with
+/// its sessions) is confirmed in the same transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UseOutput(pub Option<UserId>);
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Insert;
+
+pub struct Select;
+
+pub struct Use;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+#[cfg_attr(test, allow(dead_code))]
+async fn insert(input: InsertInput) -> Result<InsertOutput, ExternalException> {
+	let InsertInput {
+		user_id: UserId(user_id),
+		token,
+		ttl_seconds,
+	} = input;
+	let created_at = Utc::now().timestamp();
+	let expires_at = created_at + ttl_seconds;
+	let id = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			diesel::insert_into(user_confirmation::table)
+				.values((
+					user_confirmation::user_id.eq(user_id),
+					user_confirmation::token_hash.eq(token_hash(&token)),
+					user_confirmation::created_at.eq(created_at),
+					user_confirmation::expires_at.eq(expires_at),
+				))
+				.execute(conn)?;
+			diesel::select(last_insert_rowid).get_result::<i64>(conn)
+		})
+	})
+	.await?;
+	Ok(InsertOutput { id })
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn select(input: SelectInput) -> Result<SelectOutput, ExternalException> {
+	let row = db_pool::run(move |conn| match input {
+		SelectInput::LastByUser(UserId(user_id)) => user_confirmation::table
+			.filter(user_confirmation::user_id.eq(user_id))
+			.order(user_confirmation::id.desc())
+			.select((
+				user_confirmation::id,
+				user_confirmation::user_id,
+				user_confirmation::created_at,
+				user_confirmation::expires_at,
+				user_confirmation::used_at,
+			))
+			.first::<UserConfirmationRow>(conn)
+			.optional(),
+	})
+	.await?;
+	let confirmation = row.map(|row| UserConfirmation {
+		id: row.id,
+		user_id: UserId(row.user_id),
+		created_at: row.created_at,
+		expires_at: row.expires_at,
+		used_at: row.used_at,
+	});
+	Ok(SelectOutput(confirmation))
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn use_token(input: UseInput) -> Result<UseOutput, ExternalException> {
+	let UseInput { token } = input;
+	let now = Utc::now().timestamp();
+	let user_id = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			let row = user_confirmation::table
+				.filter(user_confirmation::token_hash.eq(token_hash(&token)))
+				.filter(user_confirmation::used_at.is_null())
+				.filter(user_confirmation::expires_at.gt(now))
+				.select((user_confirmation::id, user_confirmation::user_id))
+				.first::<(i64, i64)>(conn)
+				.optional()?;
+
+			if let Some((id, user_id)) = row {
+				diesel::update(user_confirmation::table.find(id))
+					.set(user_confirmation::used_at.eq(now))
+					.execute(conn)?;
+				diesel::update(user::table.find(user_id))
+					.set(user::confirmed.eq(true))
+					.execute(conn)?;
+				diesel::update(user_session::table.filter(user_session::user_id.eq(user_id)))
+					.set(user_session::confirmed.eq(true))
+					.execute(conn)?;
+			}
+
+			Ok::<_, diesel::result::Error>(row.map(|(_, user_id)| UserId(user_id)))
+		})
+	})
+	.await?;
+	Ok(UseOutput(user_id))
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::InsertInput, super::InsertOutput> for super::Insert {
+		async fn run(input: super::InsertInput) -> Result<super::InsertOutput, ExternalException> {
+			metrics_impl::external("user_confirmation_dao::Insert", super::insert(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::SelectInput, super::SelectOutput> for super::Select {
+		async fn run(input: super::SelectInput) -> Result<super::SelectOutput, ExternalException> {
+			metrics_impl::external("user_confirmation_dao::Select", super::select(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::UseInput, super::UseOutput> for super::Use {
+		async fn run(input: super::UseInput) -> Result<super::UseOutput, ExternalException> {
+			metrics_impl::external("user_confirmation_dao::Use", super::use_token(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::{
+	core::external::data::external_exception::ExternalException, shared::data::user_data::UserId,
+};
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::InsertInput, super::InsertOutput> for super::Insert {
+		fn name() -> Str {
+			"register-user-confirmation".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Insert
+		}
+	}
+
+	impl ExternalTest<super::SelectInput, super::SelectOutput> for super::Select {
+		fn name() -> Str {
+			"select-user-confirmation".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Select
+		}
+	}
+
+	impl ExternalTest<super::UseInput, super::UseOutput> for super::Use {
+		fn name() -> Str {
+			"use-user-confirmation".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+}
@@ -1,29 +1,35 @@
-use diesel::{Insertable, Queryable, RunQueryDsl};
+use diesel::{Connection, ExpressionMethods, QueryDsl, Queryable, RunQueryDsl};
 use rocket_sync_db_pools::diesel;
-use rocket_sync_db_pools::diesel::prelude::ExpressionMethods;
+
+use crate::core::dao::db_pool;
 
 ////////////////////////////////////////////////
 //////////////////// TABLE /////////////////////
 ////////////////////////////////////////////////
 
-#[database("diesel")]
-struct Db(diesel::SqliteConnection);
-
 table! {
 	user (id) {
 		id -> BigInt,
 		name -> Text,
 		email -> Text,
 		encrypted_pass -> Text,
+		confirmed -> Bool,
 	}
 }
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Queryable, Insertable)]
-#[table_name = "user"]
+
+no_arg_sql_function!(
+	last_insert_rowid,
+	diesel::sql_types::BigInt,
+	"Represents the SQL last_insert_rowid() function"
+);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Queryable)]
 pub struct User {
 	pub id: i64,
 	pub name: String,
 	pub email: String,
 	pub encrypted_pass: String,
+	pub confirmed: bool,
 }
 
 ////////////////////////////////////////////////
@@ -34,7 +40,13 @@ pub struct User {
 pub struct InsertInput {
 	pub name: String,
 	pub email: String,
-	pub pass: String,
+	pub encrypted_pass: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct UpdatePassInput {
+	pub id: UserId,
+	pub encrypted_pass: String,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -43,6 +55,8 @@ pub struct DeleteInput(pub UserId);
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum SelectInput {
 	ById(UserId),
+	ByLogin(String),
+	ByEmail(String),
 	First,
 	Last,
 }
@@ -59,6 +73,16 @@ pub struct InsertOutput {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SelectOutput(pub User);
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdatePassOutput {
+	pub updated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeleteOutput {
+	pub deleted: bool,
+}
+
 ////////////////////////////////////////////////
 /////////////////// ACTIONS ////////////////////
 ////////////////////////////////////////////////
@@ -67,49 +91,91 @@ pub struct Insert;
 
 pub struct Select;
 
+pub struct UpdatePass;
+
 pub struct Delete;
 
 ////////////////////////////////////////////////
 ///////////////////// IMPL /////////////////////
 ////////////////////////////////////////////////
 
+#[cfg_attr(test, allow(dead_code))]
+async fn insert(input: InsertInput) -> Result<InsertOutput, ExternalException> {
+	let InsertInput {
+		name,
+		email,
+		encrypted_pass,
+	} = input;
+	let id = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			diesel::insert_into(user::table)
+				.values((
+					user::name.eq(name),
+					user::email.eq(email),
+					user::encrypted_pass.eq(encrypted_pass),
+					user::confirmed.eq(false),
+				))
+				.execute(conn)?;
+			diesel::select(last_insert_rowid).get_result::<i64>(conn)
+		})
+	})
+	.await?;
+	Ok(InsertOutput { id: UserId(id) })
+}
+
 #[cfg_attr(test, allow(dead_code))]
 async fn select(input: SelectInput) -> Result<SelectOutput, ExternalException> {
-	Ok(match input {
-		SelectInput::ById(UserId(id)) => SelectOutput(User {
-			id,
-			name: format!("User {id:?}").into(),
-			email: format!("user-{id:?}@domain.test").into(),
-			encrypted_pass: format!("p4$$w0rd{id:?}").into(),
-		}),
-		SelectInput::First => SelectOutput(User {
-			id: 11,
-			name: "User 20".into(),
-			email: "user-20@domain.test".into(),
-			encrypted_pass: "p4$$w0rd20".into(),
-		}),
-		SelectInput::Last => SelectOutput(User {
-			id: 13,
-			name: "User 13".into(),
-			email: "user-13@domain.test".into(),
-			encrypted_pass: "p4$$w0rd13".into(),
-		}),
+	let user = db_pool::run(move |conn| match input {
+		SelectInput::ById(UserId(id)) => user::table.find(id).first::<User>(conn),
+		// the names can't have an `@`, so the login is a single user
+		SelectInput::ByLogin(login) if login.contains('@') => user::table
+			.filter(user::email.eq(login))
+			.first::<User>(conn),
+		SelectInput::ByLogin(login) => user::table.filter(user::name.eq(login)).first::<User>(conn),
+		SelectInput::ByEmail(email) => user::table
+			.filter(user::email.eq(email))
+			.first::<User>(conn),
+		SelectInput::First => user::table.order(user::id.asc()).first::<User>(conn),
+		SelectInput::Last => user::table.order(user::id.desc()).first::<User>(conn),
 	})
+	.await?;
+	Ok(SelectOutput(user))
 }
 
-async fn delete(db: Db, input: DeleteInput) -> Result<(), ExternalException> {
+#[cfg_attr(test, allow(dead_code))]
+async fn update_pass(input: UpdatePassInput) -> Result<UpdatePassOutput, ExternalException> {
+	let UpdatePassInput {
+		id: UserId(id),
+		encrypted_pass,
+	} = input;
+	let amount = db_pool::run(move |conn| {
+		diesel::update(user::table.find(id))
+			.set(user::encrypted_pass.eq(encrypted_pass))
+			.execute(conn)
+	})
+	.await?;
+	Ok(UpdatePassOutput {
+		updated: amount > 0,
+	})
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn delete(input: DeleteInput) -> Result<DeleteOutput, ExternalException> {
 	let DeleteInput(UserId(id)) = input;
-	db.run(move |conn| {
+	let amount = db_pool::run(move |conn| {
 		diesel::delete(user::table)
 			.filter(user::id.eq(id))
 			.execute(conn)
 	})
 	.await?;
-	Ok(())
+	Ok(DeleteOutput {
+		deleted: amount > 0,
+	})
 }
 
 #[cfg(not(test))]
 pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
 	use crate::core::external::{
 		data::external_exception::ExternalException, definition::external::ExternalAction,
 	};
@@ -117,23 +183,30 @@ pub mod main {
 	#[rocket::async_trait]
 	impl ExternalAction<super::InsertInput, super::InsertOutput> for super::Insert {
 		async fn run(input: super::InsertInput) -> Result<super::InsertOutput, ExternalException> {
-			drop(input);
-			todo!()
+			metrics_impl::external("user_dao::Insert", super::insert(input)).await
 		}
 	}
 
 	#[rocket::async_trait]
-	impl ExternalAction<super::DeleteInput, ()> for super::Delete {
-		async fn run(input: super::DeleteInput) -> Result<(), ExternalException> {
-			drop(input);
-			todo!()
+	impl ExternalAction<super::UpdatePassInput, super::UpdatePassOutput> for super::UpdatePass {
+		async fn run(
+			input: super::UpdatePassInput,
+		) -> Result<super::UpdatePassOutput, ExternalException> {
+			metrics_impl::external("user_dao::UpdatePass", super::update_pass(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::DeleteInput, super::DeleteOutput> for super::Delete {
+		async fn run(input: super::DeleteInput) -> Result<super::DeleteOutput, ExternalException> {
+			metrics_impl::external("user_dao::Delete", super::delete(input)).await
 		}
 	}
 
 	#[rocket::async_trait]
 	impl ExternalAction<super::SelectInput, super::SelectOutput> for super::Select {
 		async fn run(input: super::SelectInput) -> Result<super::SelectOutput, ExternalException> {
-			super::select(input).await
+			metrics_impl::external("user_dao::Select", super::select(input)).await
 		}
 	}
 }
@@ -163,7 +236,17 @@ pub mod tests {
 		}
 	}
 
-	impl ExternalTest<super::DeleteInput, ()> for super::Delete {
+	impl ExternalTest<super::UpdatePassInput, super::UpdatePassOutput> for super::UpdatePass {
+		fn name() -> Str {
+			"update-user-pass".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+
+	impl ExternalTest<super::DeleteInput, super::DeleteOutput> for super::Delete {
 		fn name() -> Str {
 			"delete-user".into()
 		}
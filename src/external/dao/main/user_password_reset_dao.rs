@@ -0,0 +1,188 @@
+use chrono::Utc;
+use diesel::{Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use rocket_sync_db_pools::diesel;
+
+use super::{user_dao::user, user_session_dao::token_hash};
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	user_password_reset (id) {
+		id -> BigInt,
+		user_id -> BigInt,
+		token_hash -> Text,
+		created_at -> BigInt,
+		expires_at -> BigInt,
+		used_at -> Nullable<BigInt>,
+	}
+}
+
+no_arg_sql_function!(
+	last_insert_rowid,
+	diesel::sql_types::BigInt,
+	"Represents the SQL last_insert_rowid() function"
+);
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InsertInput {
+	pub user_id: UserId,
+	pub token: String,
+	pub ttl_seconds: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct UseInput {
+	pub token: String,
+	pub encrypted_pass: String,
+}
+
+////////////////////////////////////////////////
+//////////////////// OUTPUT ////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertOutput {
+	pub id: i64,
+}
+
+/// The user of the password reset, if the token was valid (not expired and not
+/// used before); the token can't be used again after that, and the password of
+/// the user is updated in the same transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UseOutput(pub Option<UserId>);
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct Insert;
+
+pub struct Use;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+#[cfg_attr(test, allow(dead_code))]
+async fn insert(input: InsertInput) -> Result<InsertOutput, ExternalException> {
+	let InsertInput {
+		user_id: UserId(user_id),
+		token,
+		ttl_seconds,
+	} = input;
+	let created_at = Utc::now().timestamp();
+	let expires_at = created_at + ttl_seconds;
+	let id = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			diesel::insert_into(user_password_reset::table)
+				.values((
+					user_password_reset::user_id.eq(user_id),
+					user_password_reset::token_hash.eq(token_hash(&token)),
+					user_password_reset::created_at.eq(created_at),
+					user_password_reset::expires_at.eq(expires_at),
+				))
+				.execute(conn)?;
+			diesel::select(last_insert_rowid).get_result::<i64>(conn)
+		})
+	})
+	.await?;
+	Ok(InsertOutput { id })
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn use_token(input: UseInput) -> Result<UseOutput, ExternalException> {
+	let UseInput {
+		token,
+		encrypted_pass,
+	} = input;
+	let now = Utc::now().timestamp();
+	let user_id = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			let row = user_password_reset::table
+				.filter(user_password_reset::token_hash.eq(token_hash(&token)))
+				.filter(user_password_reset::used_at.is_null())
+				.filter(user_password_reset::expires_at.gt(now))
+				.select((user_password_reset::id, user_password_reset::user_id))
+				.first::<(i64, i64)>(conn)
+				.optional()?;
+
+			if let Some((id, user_id)) = row {
+				diesel::update(user_password_reset::table.find(id))
+					.set(user_password_reset::used_at.eq(now))
+					.execute(conn)?;
+				diesel::update(user::table.find(user_id))
+					.set(user::encrypted_pass.eq(encrypted_pass))
+					.execute(conn)?;
+			}
+
+			Ok::<_, diesel::result::Error>(row.map(|(_, user_id)| UserId(user_id)))
+		})
+	})
+	.await?;
+	Ok(UseOutput(user_id))
+}
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::InsertInput, super::InsertOutput> for super::Insert {
+		async fn run(input: super::InsertInput) -> Result<super::InsertOutput, ExternalException> {
+			metrics_impl::external("user_password_reset_dao::Insert", super::insert(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::UseInput, super::UseOutput> for super::Use {
+		async fn run(input: super::UseInput) -> Result<super::UseOutput, ExternalException> {
+			metrics_impl::external("user_password_reset_dao::Use", super::use_token(input)).await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+use crate::{
+	core::external::data::external_exception::ExternalException, shared::data::user_data::UserId,
+};
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::InsertInput, super::InsertOutput> for super::Insert {
+		fn name() -> Str {
+			"register-user-password-reset".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Insert
+		}
+	}
+
+	impl ExternalTest<super::UseInput, super::UseOutput> for super::Use {
+		fn name() -> Str {
+			"use-user-password-reset".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
+}
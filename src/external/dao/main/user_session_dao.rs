@@ -1,19 +1,63 @@
+use chrono::Utc;
+use diesel::{Connection, ExpressionMethods, OptionalExtension, QueryDsl, Queryable, RunQueryDsl};
+use rocket_sync_db_pools::diesel;
+use sha2::{Digest, Sha256};
+
+use crate::core::dao::db_pool;
+
+////////////////////////////////////////////////
+//////////////////// TABLE /////////////////////
+////////////////////////////////////////////////
+
+table! {
+	user_session (id) {
+		id -> BigInt,
+		user_id -> BigInt,
+		token_hash -> Text,
+		confirmed -> Bool,
+		created_at -> BigInt,
+		expires_at -> BigInt,
+		revoked_at -> Nullable<BigInt>,
+	}
+}
+
+no_arg_sql_function!(
+	last_insert_rowid,
+	diesel::sql_types::BigInt,
+	"Represents the SQL last_insert_rowid() function"
+);
+
+#[derive(Queryable)]
+struct UserSessionRow {
+	id: i64,
+	user_id: i64,
+	confirmed: bool,
+	created_at: i64,
+	expires_at: i64,
+	revoked_at: Option<i64>,
+}
+
 ////////////////////////////////////////////////
 //////////////////// INPUT /////////////////////
 ////////////////////////////////////////////////
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InsertInput {
-	pub name: String,
-	pub email: String,
-	pub pass: String,
+	pub user_id: UserId,
+	pub token: String,
+	pub confirmed: bool,
+	pub ttl_seconds: i64,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum SelectInput {
-	ById(UserId),
-	First,
-	Last,
+	ByToken(String),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum RevokeInput {
+	ById(i64),
+	ByUser(UserId),
 }
 
 ////////////////////////////////////////////////
@@ -22,15 +66,25 @@ pub enum SelectInput {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InsertOutput {
-	pub id: UserId,
+	pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserSession {
+	pub id: i64,
+	pub user_id: UserId,
+	pub confirmed: bool,
+	pub created_at: i64,
+	pub expires_at: i64,
+	pub revoked_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct SelectOutput {
-	pub id: UserId,
-	pub name: String,
-	pub email: String,
-	pub encrypted_pass: String,
+pub struct SelectOutput(pub Option<UserSession>);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevokeOutput {
+	pub revoked: u64,
 }
 
 ////////////////////////////////////////////////
@@ -41,12 +95,103 @@ pub struct Insert;
 
 pub struct Select;
 
+pub struct Revoke;
+
 ////////////////////////////////////////////////
 ///////////////////// IMPL /////////////////////
 ////////////////////////////////////////////////
 
+// Only a hash of the token is persisted, the token itself is known only by
+// the client.
+#[cfg_attr(test, allow(dead_code))]
+pub fn token_hash(token: &str) -> String {
+	hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn insert(input: InsertInput) -> Result<InsertOutput, ExternalException> {
+	let InsertInput {
+		user_id: UserId(user_id),
+		token,
+		confirmed,
+		ttl_seconds,
+	} = input;
+	let created_at = Utc::now().timestamp();
+	let expires_at = created_at + ttl_seconds;
+	let id = db_pool::run(move |conn| {
+		conn.transaction(|| {
+			diesel::insert_into(user_session::table)
+				.values((
+					user_session::user_id.eq(user_id),
+					user_session::token_hash.eq(token_hash(&token)),
+					user_session::confirmed.eq(confirmed),
+					user_session::created_at.eq(created_at),
+					user_session::expires_at.eq(expires_at),
+				))
+				.execute(conn)?;
+			diesel::select(last_insert_rowid).get_result::<i64>(conn)
+		})
+	})
+	.await?;
+	Ok(InsertOutput { id })
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn select(input: SelectInput) -> Result<SelectOutput, ExternalException> {
+	let row = db_pool::run(move |conn| match input {
+		SelectInput::ByToken(token) => user_session::table
+			.filter(user_session::token_hash.eq(token_hash(&token)))
+			.select((
+				user_session::id,
+				user_session::user_id,
+				user_session::confirmed,
+				user_session::created_at,
+				user_session::expires_at,
+				user_session::revoked_at,
+			))
+			.first::<UserSessionRow>(conn)
+			.optional(),
+	})
+	.await?;
+	let session = row.map(|row| UserSession {
+		id: row.id,
+		user_id: UserId(row.user_id),
+		confirmed: row.confirmed,
+		created_at: row.created_at,
+		expires_at: row.expires_at,
+		revoked_at: row.revoked_at,
+	});
+	Ok(SelectOutput(session))
+}
+
+#[cfg_attr(test, allow(dead_code))]
+async fn revoke(input: RevokeInput) -> Result<RevokeOutput, ExternalException> {
+	let now = Utc::now().timestamp();
+	let amount = db_pool::run(move |conn| {
+		let active = user_session::revoked_at.is_null();
+		let revoked_at = user_session::revoked_at.eq(now);
+		match input {
+			RevokeInput::ById(id) => diesel::update(user_session::table.find(id).filter(active))
+				.set(revoked_at)
+				.execute(conn),
+			RevokeInput::ByUser(UserId(user_id)) => diesel::update(
+				user_session::table
+					.filter(user_session::user_id.eq(user_id))
+					.filter(active),
+			)
+			.set(revoked_at)
+			.execute(conn),
+		}
+	})
+	.await?;
+	Ok(RevokeOutput {
+		revoked: amount as u64,
+	})
+}
+
 #[cfg(not(test))]
 pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
 	use crate::core::external::{
 		data::external_exception::ExternalException, definition::external::ExternalAction,
 	};
@@ -54,16 +199,21 @@ pub mod main {
 	#[rocket::async_trait]
 	impl ExternalAction<super::InsertInput, super::InsertOutput> for super::Insert {
 		async fn run(input: super::InsertInput) -> Result<super::InsertOutput, ExternalException> {
-			drop(input);
-			todo!()
+			metrics_impl::external("user_session_dao::Insert", super::insert(input)).await
 		}
 	}
 
 	#[rocket::async_trait]
 	impl ExternalAction<super::SelectInput, super::SelectOutput> for super::Select {
 		async fn run(input: super::SelectInput) -> Result<super::SelectOutput, ExternalException> {
-			drop(input);
-			todo!()
+			metrics_impl::external("user_session_dao::Select", super::select(input)).await
+		}
+	}
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::RevokeInput, super::RevokeOutput> for super::Revoke {
+		async fn run(input: super::RevokeInput) -> Result<super::RevokeOutput, ExternalException> {
+			metrics_impl::external("user_session_dao::Revoke", super::revoke(input)).await
 		}
 	}
 }
@@ -72,7 +222,9 @@ pub mod main {
 //////////////////// TESTS /////////////////////
 ////////////////////////////////////////////////
 
-use crate::shared::data::user_data::UserId;
+use crate::{
+	core::external::data::external_exception::ExternalException, shared::data::user_data::UserId,
+};
 
 #[cfg(test)]
 pub mod tests {
@@ -100,4 +252,14 @@ pub mod tests {
 			MockExternalMethod::Select
 		}
 	}
+
+	impl ExternalTest<super::RevokeInput, super::RevokeOutput> for super::Revoke {
+		fn name() -> Str {
+			"revoke-user-session".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Update
+		}
+	}
 }
@@ -0,0 +1,127 @@
+use std::sync::OnceLock;
+
+use rocket::fairing::AdHoc;
+
+use super::outbox_mailer::OutboxMailer;
+use crate::core::external::data::external_exception::ExternalException;
+
+////////////////////////////////////////////////
+//////////////////// INPUT /////////////////////
+////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mail {
+	pub to: String,
+	pub subject: String,
+	pub body: String,
+}
+
+////////////////////////////////////////////////
+/////////////////// BACKEND ////////////////////
+////////////////////////////////////////////////
+
+// Mails are delivered by the backend defined in the `mail` config, so that a
+// different transport (like SMTP) can be plugged in without changing the
+// actions that send them.
+pub trait Mailer: Send + Sync {
+	fn send(&self, mail: &Mail) -> Result<(), ExternalException>;
+}
+
+pub struct LogMailer;
+
+impl Mailer for LogMailer {
+	fn send(&self, mail: &Mail) -> Result<(), ExternalException> {
+		let Mail { to, subject, .. } = mail;
+		info!("mail to {to}: {subject}");
+		Ok(())
+	}
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+enum MailConfig {
+	Log,
+	Outbox { outbox_dir: String },
+}
+
+static MAILER: OnceLock<Box<dyn Mailer>> = OnceLock::new();
+
+#[cfg_attr(test, allow(dead_code))]
+fn mailer() -> &'static dyn Mailer {
+	MAILER.get_or_init(|| Box::new(LogMailer)).as_ref()
+}
+
+pub fn stage() -> AdHoc {
+	AdHoc::try_on_ignite("Mailer", |rocket| async {
+		let config = rocket
+			.figment()
+			.extract_inner::<MailConfig>("mail")
+			.unwrap_or(MailConfig::Log);
+
+		let mailer: Box<dyn Mailer> = match config {
+			MailConfig::Log => Box::new(LogMailer),
+			MailConfig::Outbox { outbox_dir } => match OutboxMailer::new(outbox_dir.into()) {
+				Ok(mailer) => Box::new(mailer),
+				Err(error) => {
+					error!("mail outbox error: {error:?}");
+					return Err(rocket);
+				}
+			},
+		};
+
+		let _ = MAILER.set(mailer);
+		Ok(rocket)
+	})
+}
+
+////////////////////////////////////////////////
+/////////////////// ACTIONS ////////////////////
+////////////////////////////////////////////////
+
+pub struct SendMail;
+
+////////////////////////////////////////////////
+///////////////////// IMPL /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(not(test))]
+pub mod main {
+	use crate::core::action::main_impl::metrics_impl;
+	use crate::core::external::{
+		data::external_exception::ExternalException, definition::external::ExternalAction,
+	};
+
+	#[rocket::async_trait]
+	impl ExternalAction<super::Mail, ()> for super::SendMail {
+		async fn run(input: super::Mail) -> Result<(), ExternalException> {
+			metrics_impl::external("mailer::SendMail", async {
+				tokio::task::spawn_blocking(move || super::mailer().send(&input))
+					.await
+					.map_err(|error| ExternalException::MailError(error.to_string().into()))?
+			})
+			.await
+		}
+	}
+}
+
+////////////////////////////////////////////////
+//////////////////// TESTS /////////////////////
+////////////////////////////////////////////////
+
+#[cfg(test)]
+pub mod tests {
+	use crate::{
+		core::external::definition::external::tests::{ExternalTest, MockExternalMethod},
+		lib::data::str::Str,
+	};
+
+	impl ExternalTest<super::Mail, ()> for super::SendMail {
+		fn name() -> Str {
+			"send-mail".into()
+		}
+
+		fn method() -> MockExternalMethod {
+			MockExternalMethod::Insert
+		}
+	}
+}
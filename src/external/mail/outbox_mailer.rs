@@ -0,0 +1,82 @@
+use std::{
+	fs,
+	path::PathBuf,
+	sync::atomic::{AtomicU64, Ordering},
+};
+
+use chrono::Utc;
+
+use super::mailer::{Mail, Mailer};
+use crate::core::external::data::external_exception::ExternalException;
+
+// Writes each mail as a `.eml` file in a directory instead of delivering it,
+// so that the mails can be inspected locally and in tests.
+pub struct OutboxMailer {
+	dir: PathBuf,
+	counter: AtomicU64,
+}
+
+impl OutboxMailer {
+	pub fn new(dir: PathBuf) -> Result<Self, ExternalException> {
+		fs::create_dir_all(&dir)
+			.map_err(|error| ExternalException::MailError(error.to_string().into()))?;
+		Ok(Self {
+			dir,
+			counter: AtomicU64::new(0),
+		})
+	}
+}
+
+impl Mailer for OutboxMailer {
+	fn send(&self, mail: &Mail) -> Result<(), ExternalException> {
+		let Mail { to, subject, body } = mail;
+		let now = Utc::now();
+		let count = self.counter.fetch_add(1, Ordering::SeqCst);
+		let file_name = format!("{}-{count:04}.eml", now.timestamp_millis());
+		let content = format!(
+			"Date: {date}\r\nTo: {to}\r\nSubject: {subject}\r\n\r\n{body}\r\n",
+			date = now.to_rfc2822(),
+		);
+		fs::write(self.dir.join(file_name), content)
+			.map_err(|error| ExternalException::MailError(error.to_string().into()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use super::OutboxMailer;
+	use crate::external::mail::mailer::{Mail, Mailer};
+	use crate::tests::test_utils::tests::run_test;
+
+	#[tokio::test]
+	async fn test_send() {
+		run_test(|_| async {
+			let dir = std::env::temp_dir().join(format!("outbox-test-{}", std::process::id()));
+			let mailer = OutboxMailer::new(dir.clone()).unwrap();
+
+			mailer
+				.send(&Mail {
+					to: "user-01@domain.test".into(),
+					subject: "Subject 01".into(),
+					body: "Body 01".into(),
+				})
+				.unwrap();
+
+			let files = fs::read_dir(&dir)
+				.unwrap()
+				.map(|entry| entry.unwrap().path())
+				.collect::<Vec<_>>();
+			assert_eq!(files.len(), 1);
+
+			let content = fs::read_to_string(&files[0]).unwrap();
+			assert!(content.contains("To: user-01@domain.test\r\n"));
+			assert!(content.contains("Subject: Subject 01\r\n"));
+			assert!(content.ends_with("\r\n\r\nBody 01\r\n"));
+
+			fs::remove_dir_all(&dir).unwrap();
+		})
+		.await;
+	}
+}
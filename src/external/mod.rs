@@ -1 +1,2 @@
 pub mod dao;
+pub mod mail;
@@ -9,8 +9,6 @@ extern crate log;
 #[macro_use]
 extern crate rocket;
 #[macro_use]
-extern crate rocket_sync_db_pools;
-#[macro_use]
 extern crate serde;
 extern crate strum;
 
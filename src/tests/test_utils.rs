@@ -1,9 +1,12 @@
 #[cfg(test)]
 pub mod tests {
+	use crate::core::action::main_impl::audit_impl;
 	use crate::lib::data::str::Str;
 	use futures::Future;
 	use log::{Level, LevelFilter, Metadata, Record};
-	use std::sync::{Arc, Mutex};
+	use std::cell::RefCell;
+	use std::sync::{Arc, Mutex, Once};
+	use std::thread::LocalKey;
 
 	lazy_static::lazy_static! {
 		static ref MY_LOGGER: MyLogger = MyLogger(Arc::new(Mutex::new(vec![])));
@@ -50,6 +53,17 @@ pub mod tests {
 		log::set_max_level(LevelFilter::Info);
 	}
 
+	/// Creates the mocks kept for the whole run, before the first call to the
+	/// mock server (it can't be started from the ctors).
+	pub fn init_mocks() {
+		static MOCKS: Once = Once::new();
+
+		MOCKS.call_once(|| {
+			// every action writes its audit record
+			std::mem::forget(audit_impl::tests::mock_any());
+		});
+	}
+
 	pub async fn run_test<T: Future<Output = ()>, F: Fn(TestHelper) -> T>(function: F) {
 		let helper = TestHelper;
 		helper.clear_log();
@@ -57,4 +71,45 @@ pub mod tests {
 		let helper = TestHelper;
 		assert_eq!(helper.pop_log(), None, "Verify that no log remained");
 	}
+
+	/// The values recorded by the test doubles, for the test running on the
+	/// thread: `static X: Recorder<T> = const { Recorder::new() }` in a
+	/// `thread_local!`.
+	pub struct Recorder<T>(RefCell<Vec<T>>);
+
+	impl<T> Recorder<T> {
+		pub const fn new() -> Self {
+			Self(RefCell::new(Vec::new()))
+		}
+	}
+
+	impl<T> Default for Recorder<T> {
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+
+	pub trait RecorderKey<T> {
+		fn record(&'static self, value: T);
+
+		/// The last recorded value.
+		fn pop(&'static self) -> Option<T>;
+
+		/// The recorded values, in order, leaving the recorder empty.
+		fn take(&'static self) -> Vec<T>;
+	}
+
+	impl<T: 'static> RecorderKey<T> for LocalKey<Recorder<T>> {
+		fn record(&'static self, value: T) {
+			self.with(|recorder| recorder.0.borrow_mut().push(value));
+		}
+
+		fn pop(&'static self) -> Option<T> {
+			self.with(|recorder| recorder.0.borrow_mut().pop())
+		}
+
+		fn take(&'static self) -> Vec<T> {
+			self.with(|recorder| recorder.0.take())
+		}
+	}
 }
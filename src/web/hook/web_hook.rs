@@ -0,0 +1,236 @@
+use rocket::{serde::json::Value, State};
+
+use crate::core::web::{
+	data::hook_data::{HookConfig, HookContext, HookReplays},
+	definition::web_action::WebActionResult,
+	main_impl::hook_web_impl,
+};
+
+#[post("/<source>/<action>", data = "<body>")]
+async fn hook(
+	config: &State<HookConfig>,
+	replays: &State<HookReplays>,
+	context: HookContext,
+	source: &str,
+	action: &str,
+	body: String,
+) -> WebActionResult<Value> {
+	hook_web_impl::call(config, replays, context, source, action, body).await
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+	routes![hook]
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::Utc;
+	use rocket::{
+		http::{Header, Status},
+		local::blocking::{Client, LocalResponse},
+		serde::json::{serde_json::json, Value},
+	};
+
+	use crate::core::web::{
+		data::{
+			hook_data::HookReplays,
+			web_data::{HOOK_SIGNATURE_HEADER, HOOK_TIMESTAMP_HEADER},
+		},
+		main_impl::{hook_web_impl, request_id_web_impl},
+	};
+
+	fn get_client() -> Client {
+		Client::tracked(
+			rocket::build()
+				.manage(hook_web_impl::tests::config())
+				.manage(HookReplays::default())
+				.mount("/hook", super::routes()),
+		)
+		.unwrap()
+	}
+
+	fn post<'c>(
+		client: &'c Client,
+		uri: &str,
+		secret: &str,
+		timestamp: i64,
+		body: &str,
+	) -> LocalResponse<'c> {
+		let timestamp = timestamp.to_string();
+		client
+			.post(uri.to_string())
+			.header(Header::new(HOOK_TIMESTAMP_HEADER, timestamp.clone()))
+			.header(Header::new(
+				HOOK_SIGNATURE_HEADER,
+				hook_web_impl::tests::sign(secret, &timestamp, body),
+			))
+			.body(body)
+			.dispatch()
+	}
+
+	fn signature_invalid() -> Value {
+		json!({
+			"type": "/problems/hook.signature_invalid",
+			"title": "Unauthorized",
+			"status": 401,
+			"detail": "The signature of the hook request is invalid.",
+			"code": "hook.signature_invalid",
+			"scope": "Automatic",
+			"action_id": 1,
+			"request_id": request_id_web_impl::tests::request_id(),
+		})
+	}
+
+	#[test]
+	fn hook_ok() {
+		let client = get_client();
+		let body = r#"{"param1":"paid","param2":42}"#;
+
+		let response = post(
+			&client,
+			"/hook/billing/Auto",
+			"billing-secret",
+			Utc::now().timestamp(),
+			body,
+		);
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(json!({
+				"id": 1,
+				"auto": "hook",
+				"param1": "paid",
+				"param2": 42,
+			})),
+		);
+	}
+
+	// the same response as a wrong secret, so that the sources can't be guessed
+	#[test]
+	fn hook_unknown_source() {
+		let client = get_client();
+
+		let response = post(
+			&client,
+			"/hook/shipping/1",
+			"billing-secret",
+			Utc::now().timestamp(),
+			"{}",
+		);
+
+		assert_eq!(response.status(), Status::Unauthorized);
+		assert_eq!(response.into_json::<Value>(), Some(signature_invalid()),);
+	}
+
+	#[test]
+	fn hook_invalid_signature() {
+		let client = get_client();
+
+		let response = post(
+			&client,
+			"/hook/billing/1",
+			"other-secret",
+			Utc::now().timestamp(),
+			"{}",
+		);
+
+		assert_eq!(response.status(), Status::Unauthorized);
+		assert_eq!(response.into_json::<Value>(), Some(signature_invalid()),);
+	}
+
+	#[test]
+	fn hook_expired() {
+		let client = get_client();
+
+		let response = post(
+			&client,
+			"/hook/billing/1",
+			"billing-secret",
+			Utc::now().timestamp() - 3600,
+			r#"{"param1":"paid","param2":42}"#,
+		);
+
+		assert_eq!(response.status(), Status::Unauthorized);
+	}
+
+	#[test]
+	fn hook_replayed() {
+		let client = get_client();
+		let timestamp = Utc::now().timestamp();
+		let body = r#"{"param1":"paid","param2":42}"#;
+
+		let response = post(
+			&client,
+			"/hook/billing/1",
+			"billing-secret",
+			timestamp,
+			body,
+		);
+		assert_eq!(response.status(), Status::Ok);
+
+		let response = post(
+			&client,
+			"/hook/billing/1",
+			"billing-secret",
+			timestamp,
+			body,
+		);
+		assert_eq!(response.status(), Status::Unauthorized);
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(json!({
+				"type": "/problems/hook.request_replayed",
+				"title": "Unauthorized",
+				"status": 401,
+				"detail": "The hook request was already received.",
+				"code": "hook.request_replayed",
+				"scope": "Automatic",
+				"action_id": 1,
+				"request_id": request_id_web_impl::tests::request_id(),
+			})),
+		);
+	}
+
+	#[test]
+	fn hook_unsigned() {
+		let client = get_client();
+
+		let response = client
+			.post("/hook/billing/1")
+			.body(r#"{"param1":"paid","param2":42}"#)
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Unauthorized);
+	}
+
+	#[test]
+	fn hook_not_allowed() {
+		let client = get_client();
+
+		let response = post(
+			&client,
+			"/hook/billing/Web",
+			"billing-secret",
+			Utc::now().timestamp(),
+			"{}",
+		);
+
+		assert_eq!(response.status(), Status::Forbidden);
+	}
+
+	#[test]
+	fn hook_invalid_body() {
+		let client = get_client();
+
+		let response = post(
+			&client,
+			"/hook/billing/1",
+			"billing-secret",
+			Utc::now().timestamp(),
+			r#"{"param1":"paid"}"#,
+		);
+
+		assert_eq!(response.status(), Status::BadRequest);
+	}
+}
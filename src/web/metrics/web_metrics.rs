@@ -0,0 +1,43 @@
+use rocket::http::ContentType;
+
+use crate::core::action::main_impl::metrics_impl;
+
+#[get("/")]
+fn metrics() -> (ContentType, String) {
+	let content_type = ContentType::with_params("text", "plain", ("version", "0.0.4"));
+	(content_type, metrics_impl::render())
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+	routes![metrics]
+}
+
+#[cfg(test)]
+mod tests {
+	use rocket::{http::Status, local::blocking::Client};
+
+	fn get_client() -> Client {
+		Client::tracked(rocket::build().mount("/metrics", super::routes())).unwrap()
+	}
+
+	#[test]
+	fn metrics() {
+		let client = get_client();
+
+		let response = client.get("/metrics").dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(
+			response
+				.content_type()
+				.map(|content_type| content_type.to_string()),
+			Some("text/plain; version=0.0.4".into())
+		);
+
+		let body = response.into_string().unwrap();
+		assert!(body.contains("# TYPE action_calls_total counter\n"));
+		assert!(body.contains("# TYPE action_duration_seconds histogram\n"));
+		assert!(body.contains("# TYPE external_calls_total counter\n"));
+		assert!(body.contains("# TYPE external_duration_seconds histogram\n"));
+	}
+}
@@ -1,2 +1,5 @@
+pub mod hook;
+pub mod metrics;
+pub mod moderator;
 pub mod user;
 pub mod web_root;
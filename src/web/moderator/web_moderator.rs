@@ -0,0 +1,295 @@
+use crate::{
+	business::action::{
+		audit::select_audit_action,
+		job::select_jobs_action,
+		registry::select_actions_action,
+		schedule::{select_schedules_action, trigger_schedule_action, update_schedule_action},
+	},
+	core::{
+		action::data::action_data::AuthBasicContext,
+		web::definition::web_action::{WebAction, WebActionResult},
+	},
+};
+
+#[get("/actions")]
+async fn select_actions(
+	context: AuthBasicContext,
+) -> WebActionResult<select_actions_action::Output> {
+	select_actions_action::Action::request(context.data(())).await
+}
+
+#[get("/audit?<input..>")]
+async fn select_audit(
+	context: AuthBasicContext,
+	input: select_audit_action::Input,
+) -> WebActionResult<select_audit_action::Output> {
+	select_audit_action::Action::request(context.data(input)).await
+}
+
+#[get("/jobs?<input..>")]
+async fn select_jobs(
+	context: AuthBasicContext,
+	input: select_jobs_action::Input,
+) -> WebActionResult<select_jobs_action::Output> {
+	select_jobs_action::Action::request(context.data(input)).await
+}
+
+#[get("/schedules")]
+async fn select_schedules(
+	context: AuthBasicContext,
+) -> WebActionResult<select_schedules_action::Output> {
+	select_schedules_action::Action::request(context.data(())).await
+}
+
+#[patch("/schedules/<name>?<paused>")]
+async fn update_schedule(
+	context: AuthBasicContext,
+	name: String,
+	paused: bool,
+) -> WebActionResult<()> {
+	let input = context.data(update_schedule_action::Input { name, paused });
+	update_schedule_action::Action::request(input).await
+}
+
+#[post("/schedules/<name>/trigger")]
+async fn trigger_schedule(context: AuthBasicContext, name: String) -> WebActionResult<()> {
+	let input = context.data(trigger_schedule_action::Input { name });
+	trigger_schedule_action::Action::request(input).await
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+	routes![
+		select_actions,
+		select_audit,
+		select_jobs,
+		select_schedules,
+		update_schedule,
+		trigger_schedule
+	]
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		business::action::registry::select_actions_action,
+		core::{
+			action::{
+				action_type::{
+					general_action_type::ActionType, moderator_action_type::ModeratorActionType,
+				},
+				main_impl::registry_impl,
+			},
+			external::definition::external::tests::ExternalMocker,
+			web::main_impl::request_id_web_impl,
+		},
+		external::dao::main::{
+			action_audit_dao, job_dao, moderator_dao, schedule_dao, user_session_dao,
+		},
+		shared::data::user_data::UserId,
+	};
+	use rocket::{
+		http::{Header, Status},
+		local::blocking::Client,
+		serde::json::{serde_json::json, Value},
+	};
+
+	fn get_client() -> Client {
+		Client::tracked(rocket::build().mount("/moderator", super::routes())).unwrap()
+	}
+
+	fn mock_session(token: &str, user_id: i64) -> mockito::Mock {
+		user_session_dao::Select::mock(
+			user_session_dao::SelectInput::ByToken(token.into()),
+			user_session_dao::SelectOutput(Some(user_session_dao::UserSession {
+				id: 31,
+				user_id: UserId(user_id),
+				confirmed: true,
+				created_at: 1_650_000_000,
+				expires_at: i64::MAX,
+				revoked_at: None,
+			})),
+		)
+	}
+
+	fn mock_moderator(user_id: i64, allowed_actions: &[ModeratorActionType]) -> mockito::Mock {
+		moderator_dao::Select::mock(
+			moderator_dao::SelectInput::ByUser(UserId(user_id)),
+			moderator_dao::SelectOutput(Some(moderator_dao::Moderator {
+				user_id: UserId(user_id),
+				admin: false,
+				allowed_actions: allowed_actions.iter().map(|item| item.id()).collect(),
+			})),
+		)
+	}
+
+	#[test]
+	fn select_actions_ok() {
+		let client = get_client();
+
+		let _m1 = mock_session("token-moderator", 7);
+		let _m2 = mock_moderator(7, &[ModeratorActionType::SelectActions]);
+
+		let response = client
+			.get("/moderator/actions")
+			.header(Header::new("auth", "token-moderator"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(
+			response.into_json(),
+			Some(select_actions_action::Output {
+				items: registry_impl::list(),
+			}),
+		);
+	}
+
+	#[test]
+	fn select_actions_unauthenticated() {
+		let client = get_client();
+
+		let response = client.get("/moderator/actions").dispatch();
+
+		assert_eq!(response.status(), Status::Unauthorized);
+	}
+
+	#[test]
+	fn select_actions_not_moderator() {
+		let client = get_client();
+
+		let _m1 = mock_session("token-user", 8);
+		let _m2 = moderator_dao::Select::mock(
+			moderator_dao::SelectInput::ByUser(UserId(8)),
+			moderator_dao::SelectOutput(None),
+		);
+
+		let response = client
+			.get("/moderator/actions")
+			.header(Header::new("auth", "token-user"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Forbidden);
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(json!({
+				"type": "/problems/moderator.not_moderator",
+				"title": "Forbidden",
+				"status": 403,
+				"detail": "You must be a moderator to execute this action.",
+				"code": "moderator.not_moderator",
+				"scope": "Moderator",
+				"action_id": 6,
+				"request_id": request_id_web_impl::tests::request_id(),
+			})),
+		);
+	}
+
+	#[test]
+	fn select_actions_not_allowed() {
+		let client = get_client();
+
+		let _m1 = mock_session("token-moderator", 7);
+		let _m2 = mock_moderator(7, &[ModeratorActionType::SelectAudit]);
+
+		let response = client
+			.get("/moderator/actions")
+			.header(Header::new("auth", "token-moderator"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Forbidden);
+	}
+
+	#[test]
+	fn select_audit_ok() {
+		let client = get_client();
+
+		let _m1 = mock_session("token-moderator", 7);
+		let _m2 = mock_moderator(7, &[ModeratorActionType::SelectAudit]);
+		let _m3 = action_audit_dao::Select::mock(
+			action_audit_dao::SelectInput {
+				actor_type: None,
+				actor_id: None,
+				scope: Some("User".into()),
+				action_id: Some(2),
+				from: None,
+				to: None,
+				limit: 10,
+			},
+			action_audit_dao::SelectOutput(vec![]),
+		);
+
+		let response = client
+			.get("/moderator/audit?scope=User&action_id=2&limit=10")
+			.header(Header::new("auth", "token-moderator"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(response.into_json::<Value>(), Some(json!({ "items": [] })));
+	}
+
+	#[test]
+	fn select_jobs_dead() {
+		let client = get_client();
+
+		let _m1 = mock_session("token-moderator", 7);
+		let _m2 = mock_moderator(7, &[ModeratorActionType::SelectJobs]);
+		let _m3 = job_dao::Select::mock(
+			job_dao::SelectInput {
+				state: Some("dead".into()),
+				action_id: None,
+				limit: 20,
+			},
+			job_dao::SelectOutput(vec![]),
+		);
+
+		let response = client
+			.get("/moderator/jobs?state=dead&limit=20")
+			.header(Header::new("auth", "token-moderator"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(response.into_json::<Value>(), Some(json!({ "items": [] })));
+	}
+
+	#[test]
+	fn update_schedule_not_found() {
+		let client = get_client();
+
+		let _m1 = mock_session("token-moderator", 7);
+		let _m2 = mock_moderator(7, &[ModeratorActionType::UpdateSchedule]);
+		let _m3 = schedule_dao::Update::mock(
+			schedule_dao::UpdateInput {
+				name: "removed".into(),
+				paused: true,
+			},
+			schedule_dao::UpdateOutput { updated: false },
+		);
+
+		let response = client
+			.patch("/moderator/schedules/removed?paused=true")
+			.header(Header::new("auth", "token-moderator"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::NotFound);
+	}
+
+	#[test]
+	fn trigger_schedule_ok() {
+		let client = get_client();
+
+		let _m1 = mock_session("token-moderator", 7);
+		let _m2 = mock_moderator(7, &[ModeratorActionType::TriggerSchedule]);
+		let _m3 = schedule_dao::Trigger::mock(
+			schedule_dao::TriggerInput {
+				name: "nightly".into(),
+			},
+			schedule_dao::UpdateOutput { updated: true },
+		);
+
+		let response = client
+			.post("/moderator/schedules/nightly/trigger")
+			.header(Header::new("auth", "token-moderator"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+	}
+}
@@ -1,5 +1,9 @@
 use crate::{
-	business::action::user::{delete_user_action, register_user_action, select_user_action},
+	business::action::user::{
+		confirm_email_action, delete_user_action, login_action, logout_action,
+		register_user_action, request_password_reset_action, resend_confirmation_action,
+		reset_password_action, select_user_action,
+	},
 	core::{
 		action::data::action_data::AuthBasicContext,
 		web::definition::web_action::{WebAction, WebActionResult},
@@ -31,22 +35,125 @@ async fn select_user(
 	select_user_action::Action::request(input).await
 }
 
+#[post("/session", data = "<input>")]
+async fn login(
+	context: AuthBasicContext,
+	input: Json<login_action::Input>,
+) -> WebActionResult<login_action::Output> {
+	login_action::Action::request(context.data(input.0)).await
+}
+
+#[delete("/session?<everywhere>")]
+async fn logout(context: AuthBasicContext, everywhere: Option<bool>) -> WebActionResult<()> {
+	let input = context.data(logout_action::Input {
+		everywhere: everywhere.unwrap_or_default(),
+	});
+	logout_action::Action::request(input).await
+}
+
+#[post("/confirmation", data = "<input>")]
+async fn confirm_email(
+	context: AuthBasicContext,
+	input: Json<confirm_email_action::Input>,
+) -> WebActionResult<confirm_email_action::Output> {
+	confirm_email_action::Action::request(context.data(input.0)).await
+}
+
+#[post("/confirmation/resend")]
+async fn resend_confirmation(context: AuthBasicContext) -> WebActionResult<()> {
+	resend_confirmation_action::Action::request(context.data(())).await
+}
+
+#[post("/password-reset", data = "<input>")]
+async fn request_password_reset(
+	context: AuthBasicContext,
+	input: Json<request_password_reset_action::Input>,
+) -> WebActionResult<()> {
+	request_password_reset_action::Action::request(context.data(input.0)).await
+}
+
+#[put("/password-reset", data = "<input>")]
+async fn reset_password(
+	context: AuthBasicContext,
+	input: Json<reset_password_action::Input>,
+) -> WebActionResult<()> {
+	reset_password_action::Action::request(context.data(input.0)).await
+}
+
 pub fn routes() -> Vec<rocket::Route> {
-	routes![register_user, delete_user, select_user]
+	routes![
+		register_user,
+		delete_user,
+		select_user,
+		login,
+		logout,
+		confirm_email,
+		resend_confirmation,
+		request_password_reset,
+		reset_password
+	]
 }
 
 #[cfg(test)]
 mod tests {
 	use crate::{
-		business::action::user::{delete_user_action, register_user_action, select_user_action},
+		business::action::user::{
+			confirm_email_action, delete_user_action, login_action, logout_action,
+			register_user_action, request_password_reset_action, reset_password_action,
+			select_user_action,
+		},
+		core::{
+			action::{
+				action_type::{general_action_type::ActionType, user_action_type::UserActionType},
+				data::{
+					action_data::tests::RequestBuilder,
+					user_action_data::tests::UserRequestContextBuilder,
+				},
+				main_impl::idempotency_impl,
+			},
+			external::definition::external::tests::ExternalMocker,
+			web::main_impl::request_id_web_impl,
+		},
+		external::dao::main::{idempotency_dao, user_session_dao},
 		shared::data::user_data::UserId,
 	};
-	use rocket::{http::Status, local::blocking::Client};
+	use mockito::Mock;
+	use rocket::{
+		http::{Accept, ContentType, Header, Status},
+		local::blocking::Client,
+		serde::json::{serde_json::json, Value},
+	};
 
 	fn get_client() -> Client {
 		Client::tracked(rocket::build().mount("/user", super::routes())).unwrap()
 	}
 
+	// no public error, so the problem only has the status and the action
+	fn internal_error(action_id: u32) -> Value {
+		json!({
+			"type": "about:blank",
+			"title": "Internal Server Error",
+			"status": 500,
+			"scope": "User",
+			"action_id": action_id,
+			"request_id": request_id_web_impl::tests::request_id(),
+		})
+	}
+
+	fn mock_session(token: &str, id: i64, user_id: UserId) -> Mock {
+		user_session_dao::Select::mock(
+			user_session_dao::SelectInput::ByToken(token.into()),
+			user_session_dao::SelectOutput(Some(user_session_dao::UserSession {
+				id,
+				user_id,
+				confirmed: true,
+				created_at: 1_650_000_000,
+				expires_at: i64::MAX,
+				revoked_at: None,
+			})),
+		)
+	}
+
 	#[test]
 	fn register_user_ok() {
 		let client = get_client();
@@ -86,18 +193,25 @@ mod tests {
 		let response = client.post("/user").body(input_json).dispatch();
 
 		assert_eq!(response.status(), Status::InternalServerError);
-		assert_eq!(response.into_json(), output);
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(internal_error(UserActionType::Register.id()))
+		);
 	}
 
 	#[test]
 	fn delete_user_ok() {
 		let client = get_client();
 
+		let _m1 = mock_session("token-delete", 22, UserId(123));
 		let delete_user_action::tests::ActionMock { user_id, mocks: _m } =
 			delete_user_action::tests::mock_action(UserId(123));
 		let user_id = user_id.0;
 		let uri = format!("/user/{user_id}");
-		let response = client.delete(uri).dispatch();
+		let response = client
+			.delete(uri)
+			.header(Header::new("auth", "token-delete"))
+			.dispatch();
 
 		assert_eq!(response.status(), Status::Ok);
 		assert_eq!(response.into_json(), Some(()));
@@ -107,14 +221,26 @@ mod tests {
 	fn delete_user_error() {
 		let client = get_client();
 
-		let user_id = 123;
-		let uri = format!("/user/{user_id}");
-		let response = client.get(uri).dispatch();
-
-		let output: Option<()> = None;
+		let _m = mock_session("token-delete-error", 23, UserId(123));
+		let response = client
+			.delete("/user/123")
+			.header(Header::new("auth", "token-delete-error"))
+			.dispatch();
 
 		assert_eq!(response.status(), Status::InternalServerError);
-		assert_eq!(response.into_json(), output);
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(internal_error(UserActionType::DeleteUser.id()))
+		);
+	}
+
+	#[test]
+	fn delete_user_unauthenticated() {
+		let client = get_client();
+
+		let response = client.delete("/user/123").dispatch();
+
+		assert_eq!(response.status(), Status::Unauthorized);
 	}
 
 	#[test]
@@ -126,9 +252,13 @@ mod tests {
 			output,
 			mocks: _m,
 		} = select_user_action::tests::mock_action(UserId(123));
+		let _m1 = mock_session("token-select", 24, user_id);
 		let user_id = user_id.0;
 		let uri = format!("/user/{user_id}");
-		let response = client.get(uri).dispatch();
+		let response = client
+			.get(uri)
+			.header(Header::new("auth", "token-select"))
+			.dispatch();
 
 		assert_eq!(response.status(), Status::Ok);
 		assert_eq!(response.into_json(), Some(output));
@@ -138,13 +268,362 @@ mod tests {
 	fn select_user_error() {
 		let client = get_client();
 
-		let user_id = 123;
-		let uri = format!("/user/{user_id}");
-		let response = client.get(uri).dispatch();
+		let _m = mock_session("token-select-error", 25, UserId(123));
+		let response = client
+			.get("/user/123")
+			.header(Header::new("auth", "token-select-error"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::InternalServerError);
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(internal_error(UserActionType::SelectUser.id()))
+		);
+	}
 
-		let output: Option<select_user_action::Output> = None;
+	#[test]
+	fn select_user_error_legacy() {
+		let client = get_client();
+
+		let _m = mock_session("token-select-legacy", 26, UserId(123));
+		let response = client
+			.get("/user/123")
+			.header(Header::new("auth", "token-select-legacy"))
+			.header(Accept::JSON)
+			.dispatch();
 
 		assert_eq!(response.status(), Status::InternalServerError);
+		assert_eq!(response.content_type(), Some(ContentType::JSON));
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(json!({ "request_id": request_id_web_impl::tests::request_id() }))
+		);
+	}
+
+	#[test]
+	fn select_user_unauthenticated() {
+		let client = get_client();
+
+		let response = client.get("/user/123").dispatch();
+
+		assert_eq!(response.status(), Status::Unauthorized);
+	}
+
+	#[test]
+	fn login_ok() {
+		let client = get_client();
+
+		let input = login_action::Input {
+			login: "user-09@domain.test".into(),
+			pass: "p4$$w0rd".into(),
+		};
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let login_action::tests::ActionMock { output, mocks: _m } =
+			login_action::tests::mock_action(input, UserId(9));
+		let response = client.post("/user/session").body(input_json).dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(response.into_json(), Some(output));
+	}
+
+	#[test]
+	fn login_error() {
+		let client = get_client();
+
+		let output: Option<login_action::Output> = None;
+
+		let response = client.post("/user/session").dispatch();
+
+		assert_eq!(response.status(), Status::BadRequest);
+		assert_eq!(response.into_json(), output);
+	}
+
+	#[test]
+	fn logout_ok() {
+		let client = get_client();
+
+		let _m1 = mock_session("token-logout", 21, UserId(4));
+		let _m2 = logout_action::tests::mock_action(user_session_dao::RevokeInput::ById(21));
+
+		let response = client
+			.delete("/user/session")
+			.header(Header::new("auth", "token-logout"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(response.into_json(), Some(()));
+	}
+
+	#[test]
+	fn logout_error() {
+		let client = get_client();
+
+		let response = client.delete("/user/session").dispatch();
+
+		assert_eq!(response.status(), Status::Unauthorized);
+		assert_eq!(
+			response.content_type(),
+			Some(ContentType::new("application", "problem+json"))
+		);
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(json!({
+				"type": "/problems/user.unauthenticated",
+				"title": "Unauthorized",
+				"status": 401,
+				"detail": "You must be authenticated to execute this action.",
+				"code": "user.unauthenticated",
+				"scope": "User",
+				"action_id": 3,
+				"request_id": request_id_web_impl::tests::request_id(),
+			}))
+		);
+	}
+
+	#[test]
+	fn logout_error_request_id() {
+		let client = get_client();
+
+		let response = client
+			.delete("/user/session")
+			.header(Header::new("X-Request-Id", "trace-logout"))
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Unauthorized);
+		assert_eq!(
+			response.into_json::<Value>().unwrap()["request_id"],
+			json!("trace-logout")
+		);
+	}
+
+	#[test]
+	fn logout_error_legacy() {
+		let client = get_client();
+
+		let response = client
+			.delete("/user/session")
+			.header(Accept::JSON)
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Unauthorized);
+		assert_eq!(response.content_type(), Some(ContentType::JSON));
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(json!({
+				"code": "user.unauthenticated",
+				"msg": "You must be authenticated to execute this action.",
+				"params": null,
+				"request_id": request_id_web_impl::tests::request_id(),
+			}))
+		);
+	}
+
+	#[test]
+	fn confirm_email_ok() {
+		let client = get_client();
+
+		let input = confirm_email_action::Input {
+			token: "token-confirm-web".into(),
+		};
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let _m = confirm_email_action::tests::mock_action("token-confirm-web", Some(UserId(8)));
+		let response = client
+			.post("/user/confirmation")
+			.body(input_json)
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(
+			response.into_json(),
+			Some(confirm_email_action::Output { id: UserId(8) })
+		);
+	}
+
+	#[test]
+	fn request_password_reset_ok() {
+		let client = get_client();
+
+		let input = request_password_reset_action::Input {
+			email: "user-10@domain.test".into(),
+		};
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let _m = request_password_reset_action::tests::mock_action("user-10@domain.test");
+		let response = client
+			.post("/user/password-reset")
+			.body(input_json)
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(response.into_json(), Some(()));
+	}
+
+	#[test]
+	fn reset_password_ok() {
+		let client = get_client();
+
+		let input = reset_password_action::Input {
+			token: "token-reset-web".into(),
+			pass: "n3w-p4$$w0rd".into(),
+		};
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let _m = reset_password_action::tests::mock_action(input, Some(UserId(11)));
+		let response = client
+			.put("/user/password-reset")
+			.body(input_json)
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(response.into_json(), Some(()));
+	}
+
+	#[test]
+	fn confirm_email_error() {
+		let client = get_client();
+
+		let input = confirm_email_action::Input {
+			token: "token-confirm-web-invalid".into(),
+		};
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let _m = confirm_email_action::tests::mock_action("token-confirm-web-invalid", None);
+		let response = client
+			.post("/user/confirmation")
+			.body(input_json)
+			.dispatch();
+
+		let output: Option<confirm_email_action::Output> = None;
+
+		assert_eq!(response.status(), Status::BadRequest);
+		assert_eq!(response.into_json(), output);
+	}
+
+	#[test]
+	fn register_user_invalid_input() {
+		let client = get_client();
+
+		let input = register_user_action::Input {
+			email: "a@b.com".into(),
+			name: "User 01".into(),
+			pass: "p4$$".into(),
+		};
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let response = client.post("/user").body(input_json).dispatch();
+
+		assert_eq!(response.status(), Status::BadRequest);
+		assert_eq!(
+			response.into_json::<Value>(),
+			Some(json!({
+				"type": "/problems/validation.invalid_input",
+				"title": "Bad Request",
+				"status": 400,
+				"detail": "The input is invalid.",
+				"code": "validation.invalid_input",
+				"scope": "User",
+				"action_id": 1,
+				"params": { "pass": "length" },
+				"request_id": request_id_web_impl::tests::request_id(),
+			}))
+		);
+	}
+
+	#[test]
+	fn register_user_authenticated() {
+		let client = get_client();
+
+		let _m = mock_session("token-register", 22, UserId(4));
+
+		let input = register_user_action::Input {
+			email: "a@b.com".into(),
+			name: "User 01".into(),
+			pass: "p4$$w0rd".into(),
+		};
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let response = client
+			.post("/user")
+			.header(Header::new("auth", "token-register"))
+			.body(input_json)
+			.dispatch();
+
+		let output: Option<register_user_action::Output> = None;
+
+		assert_eq!(response.status(), Status::Forbidden);
+		assert_eq!(response.into_json(), output);
+	}
+
+	fn mock_idempotency(
+		input: &register_user_action::Input,
+		output: idempotency_dao::BeginOutput,
+	) -> mockito::Mock {
+		let context = UserRequestContextBuilder::new()
+			.request(RequestBuilder::new().ip("10.0.0.7".into()).build())
+			.build();
+		let request = idempotency_impl::request(
+			UserActionType::Register,
+			&context,
+			Some(&"key-register".into()),
+			input,
+		)
+		.unwrap();
+
+		idempotency_dao::Begin::mock(
+			idempotency_dao::BeginInput {
+				id: (&request).into(),
+				fingerprint: request.fingerprint,
+				ttl_seconds: 24 * 3600,
+			},
+			output,
+		)
+	}
+
+	#[test]
+	fn register_user_replay() {
+		let client = get_client();
+
+		let input = register_user_action::Input {
+			email: "a@b.com".into(),
+			name: "User 01".into(),
+			pass: "p4$$w0rd".into(),
+		};
+		let output = register_user_action::Output {
+			id: UserId(7),
+			name: "User 01".into(),
+		};
+		let output_json = rocket::serde::json::serde_json::to_string(&output).unwrap();
+		let _m = mock_idempotency(&input, idempotency_dao::BeginOutput::Replay(output_json));
+
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let response = client
+			.post("/user")
+			.remote("10.0.0.7:80".parse().unwrap())
+			.header(Header::new("Idempotency-Key", "key-register"))
+			.body(input_json)
+			.dispatch();
+
+		assert_eq!(response.status(), Status::Ok);
+		assert_eq!(response.into_json(), Some(output));
+	}
+
+	#[test]
+	fn register_user_idempotency_conflict() {
+		let client = get_client();
+
+		let input = register_user_action::Input {
+			email: "a@b.com".into(),
+			name: "User 01".into(),
+			pass: "p4$$w0rd".into(),
+		};
+		let _m = mock_idempotency(&input, idempotency_dao::BeginOutput::Conflict);
+
+		let input_json = rocket::serde::json::serde_json::to_string(&input).unwrap();
+		let response = client
+			.post("/user")
+			.remote("10.0.0.7:80".parse().unwrap())
+			.header(Header::new("Idempotency-Key", "key-register"))
+			.body(input_json)
+			.dispatch();
+
+		let output: Option<register_user_action::Output> = None;
+
+		assert_eq!(response.status(), Status::Conflict);
 		assert_eq!(response.into_json(), output);
 	}
 }
@@ -1,6 +1,14 @@
-use crate::core::dao::db_migration;
-
-use super::user::web_user;
+use crate::{
+	business::service::password_service,
+	core::{
+		action::main_impl::{event_impl, job_impl, rate_limit_impl, schedule_impl},
+		dao::db_migration,
+		web::main_impl::{application_web_impl, hook_web_impl, request_id_web_impl},
+	},
+	external::mail::mailer,
+};
+
+use super::{hook::web_hook, metrics::web_metrics, moderator::web_moderator, user::web_user};
 use rocket::{Build, Rocket};
 
 #[derive(FromFormField)]
@@ -56,18 +64,55 @@ fn hello(lang: Option<Lang>, opt: Options<'_>) -> String {
 
 pub fn launch_rocket() -> Rocket<Build> {
 	rocket::build()
+		.attach(application_web_impl::stage())
+		.attach(password_service::stage())
 		.attach(db_migration::stage())
+		.attach(mailer::stage())
+		.attach(rate_limit_impl::stage())
+		.attach(event_impl::stage())
+		.attach(job_impl::stage())
+		.attach(schedule_impl::stage())
+		.attach(hook_web_impl::stage())
+		.attach(request_id_web_impl::stage())
 		.mount("/", routes![hello])
 		.mount("/hello", routes![world, mir])
 		.mount("/wave", routes![wave])
 		.mount("/user", web_user::routes())
+		.mount("/moderator", web_moderator::routes())
+		.mount("/metrics", web_metrics::routes())
+		.mount("/hook", web_hook::routes())
 }
 
 #[cfg(test)]
 mod tests {
-	use rocket::http::{RawStr, Status};
+	use rocket::http::{Header, RawStr, Status};
 	use rocket::local::blocking::Client;
 
+	use crate::core::{
+		action::main_impl::registry_impl,
+		web::{data::web_data::REQUEST_ID_HEADER, main_impl::request_id_web_impl},
+	};
+
+	#[test]
+	fn registered_routes() {
+		let rocket = super::launch_rocket();
+		let routes = rocket
+			.routes()
+			.map(|route| (route.method.to_string(), route.uri.path().to_string()))
+			.collect::<Vec<_>>();
+
+		for info in registry_impl::list() {
+			if let Some(route) = info.route {
+				assert!(
+					routes.contains(&(route.method.to_string(), route.path.to_string())),
+					"test if the route of {}::{} is mounted",
+					info.scope,
+					info.name
+				);
+			}
+		}
+	}
+
 	#[test]
 	fn hello() {
 		let langs = &["", "ru", "en", "unknown"];
@@ -122,6 +167,37 @@ mod tests {
 		assert_eq!(response.into_string(), Some("Hello, world!".into()));
 	}
 
+	#[test]
+	fn request_id() {
+		let client = Client::tracked(super::launch_rocket()).unwrap();
+		let generated = request_id_web_impl::tests::request_id();
+
+		let response = client
+			.get("/hello/world")
+			.header(Header::new(REQUEST_ID_HEADER, "trace-01"))
+			.dispatch();
+		assert_eq!(
+			response.headers().get_one(REQUEST_ID_HEADER),
+			Some("trace-01")
+		);
+
+		let response = client.get("/hello/world").dispatch();
+		assert_eq!(
+			response.headers().get_one(REQUEST_ID_HEADER),
+			Some(generated.as_str())
+		);
+
+		let response = client
+			.get("/wave/bird/bird")
+			.header(Header::new(REQUEST_ID_HEADER, "not valid"))
+			.dispatch();
+		assert_eq!(response.status(), Status::NotFound);
+		assert_eq!(
+			response.headers().get_one(REQUEST_ID_HEADER),
+			Some(generated.as_str())
+		);
+	}
+
 	#[test]
 	fn hello_mir() {
 		let client = Client::tracked(super::launch_rocket()).unwrap();